@@ -0,0 +1,960 @@
+use std::iter::FromIterator;
+use std::time::Duration;
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use url::Url;
+use uuid::Uuid;
+use reqwest::{Client, ClientBuilder, Method, StatusCode};
+#[cfg(not(target_arch = "wasm32"))]
+use reqwest::Proxy;
+use reqwest::header::{HeaderMap, USER_AGENT, ACCEPT, ACCEPT_ENCODING, CONTENT_ENCODING, CONTENT_TYPE};
+
+use crate::errors::*;
+#[cfg(feature = "chrono")]
+use crate::http::{format_basetime, format_basetime_timestamp};
+use crate::http::{DEFAULT_BOSONNLP_URL, DEFAULT_COMPRESS_LEVEL, DEFAULT_MAX_RESPONSE_BYTES, MAX_COMPRESS_LEVEL, check_body_size, build_url, user_agent, warn_insecure_url, compress_body, handle_response, is_gzipped, is_idempotent,
+                  parse_header, read_body, redact_headers, truncate_for_log, retry_delay, summary_data, task_endpoint};
+use crate::validate::{validate_basetime, validate_contents, validate_top_k, validate_summary, validate_poll_interval, join_words, single};
+use crate::backoff::{random_seed, Backoff};
+use crate::options::{ClusterOptions, CompressionMode, KeywordsOptions, NerOptions, TagOptions};
+use crate::rep::{Usage, Sentiment, SentimentModel, NewsCategory, ScoredWord, Dependency, NamedEntity, Tag, TextCluster, TextClusterWithText, CommentsCluster, ConvertedTime, ClusterContent,
+                 TaskPushResp, TaskStatus, TaskStatusResp};
+
+/// 异步等待 ``duration``
+#[cfg(not(target_arch = "wasm32"))]
+async fn sleep(duration: Duration) {
+    tokio::time::sleep(duration).await
+}
+
+/// 异步等待 ``duration``
+#[cfg(target_arch = "wasm32")]
+async fn sleep(duration: Duration) {
+    gloo_timers::future::sleep(duration).await
+}
+
+/// 创建 reqwest 异步 Client 的 builder，启用 `rustls` feature 时使用 rustls
+fn client_builder() -> ClientBuilder {
+    let builder = Client::builder();
+    #[cfg(all(feature = "rustls", not(target_arch = "wasm32")))]
+    let builder = builder.use_rustls_tls();
+    builder
+}
+
+/// 压缩请求体，压缩是 CPU 密集型操作，放到阻塞线程池中执行以免阻塞异步运行时
+#[cfg(not(target_arch = "wasm32"))]
+async fn gzip_body(body: Vec<u8>, level: u32) -> Result<(Vec<u8>, bool)> {
+    tokio::task::spawn_blocking(move || compress_body(body, level))
+        .await
+        .expect("gzip compression task panicked")
+}
+
+/// 压缩请求体，WASM 环境中没有阻塞线程池，直接在当前任务中压缩
+#[cfg(target_arch = "wasm32")]
+async fn gzip_body(body: Vec<u8>, level: u32) -> Result<(Vec<u8>, bool)> {
+    compress_body(body, level)
+}
+
+/// [`BosonNLP`](http://bosonnlp.com) REST API 的异步封装
+///
+/// 接口与 [`BosonNLP`](struct.BosonNLP.html) 一致，但所有方法均返回 `Future`，
+/// 需要启用 `async` feature 。
+#[derive(Debug, Clone)]
+pub struct AsyncBosonNLP {
+    /// 用于 API 鉴权的 API Token
+    pub token: String,
+    /// 是否压缩大于 ``compress_threshold`` 的请求体，默认为 true，未启用 `compression` feature 时不生效
+    pub compress: bool,
+    /// 请求体超过该字节数时进行压缩，默认为 10240
+    pub compress_threshold: usize,
+    /// gzip 压缩级别，0-9，默认为 6，超过 9 时按 9 处理
+    pub compress_level: u32,
+    /// 请求遇到临时性错误时的最大重试次数，默认为 0 即不重试
+    pub max_retries: usize,
+    /// 首次重试前等待的时间，之后每次重试等待时间翻倍，默认为 1 秒
+    pub retry_backoff: Duration,
+    /// 批量接口单次请求最多发送的文本数量，超出时自动拆分为多次请求，默认为 100
+    pub batch_size: usize,
+    /// 是否允许发送空文本或只包含空白字符的文本，默认为 false，即发送前返回 `Error::InvalidArgument`
+    pub allow_empty_contents: bool,
+    /// 轮询任务状态时是否为等待时间添加随机抖动，默认为 false
+    pub poll_jitter: bool,
+    /// 解压后的响应内容大小上限，默认为 50 MB
+    pub max_response_bytes: usize,
+    /// 每个请求额外附带的 HTTP 头，会覆盖同名的默认请求头
+    pub headers: HeaderMap,
+    /// 应用标识，设置后会添加在默认的 User-Agent 之前，默认为 None。WASM 环境中无效
+    pub user_agent: Option<String>,
+    /// 插入在 API URL 与接口路径之间的前缀，如 ``/v2``，默认为 None
+    pub api_prefix: Option<String>,
+    /// `BosonNLP` HTTP API 的 URL，默认为 `https://api.bosonnlp.com`
+    bosonnlp_url: String,
+    /// reqwest 异步 Client
+    client: Client,
+}
+
+impl Default for AsyncBosonNLP {
+    fn default() -> AsyncBosonNLP {
+        AsyncBosonNLP {
+            token: "".to_string(),
+            compress: true,
+            compress_threshold: 10240,
+            compress_level: DEFAULT_COMPRESS_LEVEL,
+            max_retries: 0,
+            retry_backoff: Duration::from_secs(1),
+            batch_size: 100,
+            allow_empty_contents: false,
+            poll_jitter: false,
+            max_response_bytes: DEFAULT_MAX_RESPONSE_BYTES,
+            headers: HeaderMap::new(),
+            user_agent: None,
+            api_prefix: None,
+            bosonnlp_url: DEFAULT_BOSONNLP_URL.to_owned(),
+            client: client_builder().build().expect("failed to build reqwest Client"),
+        }
+    }
+}
+
+impl AsyncBosonNLP {
+    /// 初始化一个新的 `AsyncBosonNLP` 实例
+    pub fn new<T: Into<String>>(token: T) -> AsyncBosonNLP {
+        AsyncBosonNLP {
+            token: token.into(),
+            ..Default::default()
+        }
+    }
+
+    /// 使用自定义参数初始化一个新的 ``AsyncBosonNLP`` 实例
+    pub fn with_options<T: Into<String>>(token: T, bosonnlp_url: T, compress: bool) -> AsyncBosonNLP {
+        let bosonnlp_url = bosonnlp_url.into();
+        warn_insecure_url(&bosonnlp_url);
+        AsyncBosonNLP {
+            token: token.into(),
+            compress,
+            bosonnlp_url,
+            ..Default::default()
+        }
+    }
+
+    /// 使用自定义的 reqwest 异步 Client 初始化一个新的 ``AsyncBosonNLP`` 实例
+    pub fn with_client<T: Into<String>>(token: T, client: Client) -> AsyncBosonNLP {
+        AsyncBosonNLP {
+            token: token.into(),
+            client,
+            ..Default::default()
+        }
+    }
+
+    /// 使用设置了 HTTP 请求超时时间的 reqwest Client 初始化一个新的 ``AsyncBosonNLP`` 实例
+    ///
+    /// ``timeout`` 是单次 HTTP 请求（从建立连接到读取完响应）的超时时间，
+    /// 超时后返回 `Error::RequestTimeout`。它与文本聚类、典型意见接口的 ``timeout`` 参数不同，
+    /// 后者是等待整个聚类任务完成的时间，超时后返回 `Error::Timeout`
+    ///
+    /// WASM 环境中不可用
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn with_timeout<T: Into<String>>(token: T, timeout: Duration) -> AsyncBosonNLP {
+        let client = client_builder()
+            .timeout(timeout)
+            .build()
+            .expect("failed to build reqwest Client");
+        AsyncBosonNLP::with_client(token, client)
+    }
+
+    /// 使用 HTTP 代理初始化一个新的 ``AsyncBosonNLP`` 实例，所有请求都会通过代理发送
+    ///
+    /// ``proxy_url``: 代理服务器地址，如 ``http://proxy.example.com:8080``
+    ///
+    /// ``credentials``: 代理服务器需要认证时的用户名和密码
+    ///
+    /// WASM 环境中不可用
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn with_proxy<T: Into<String>>(token: T, proxy_url: &str, credentials: Option<(&str, &str)>) -> Result<AsyncBosonNLP> {
+        let mut proxy = Proxy::all(proxy_url)?;
+        if let Some((username, password)) = credentials {
+            proxy = proxy.basic_auth(username, password);
+        }
+        let client = client_builder().proxy(proxy).build()?;
+        Ok(AsyncBosonNLP::with_client(token, client))
+    }
+
+    /// 设置请求遇到临时性错误时的重试策略，参见 [`BosonNLP::with_retry`](struct.BosonNLP.html#method.with_retry)
+    pub fn with_retry(mut self, max_retries: usize, backoff: Duration) -> AsyncBosonNLP {
+        self.max_retries = max_retries;
+        self.retry_backoff = backoff;
+        self
+    }
+
+    /// 设置压缩请求体的阈值，请求体超过 ``threshold`` 字节时进行压缩
+    pub fn with_compress_threshold(mut self, threshold: usize) -> AsyncBosonNLP {
+        self.compress_threshold = threshold;
+        self
+    }
+
+    /// 设置 gzip 压缩级别，0-9，数值越大压缩率越高，CPU 消耗也越大，超过 9 时按 9 处理
+    pub fn with_compress_level(mut self, level: u32) -> AsyncBosonNLP {
+        self.compress_level = level.min(MAX_COMPRESS_LEVEL);
+        self
+    }
+
+    /// 设置批量接口单次请求最多发送的文本数量
+    pub fn with_batch_size(mut self, batch_size: usize) -> AsyncBosonNLP {
+        self.batch_size = batch_size;
+        self
+    }
+
+    /// 设置是否允许发送空文本，默认在发送前检查文本并返回第一个空文本的位置
+    pub fn with_allow_empty_contents(mut self, allow: bool) -> AsyncBosonNLP {
+        self.allow_empty_contents = allow;
+        self
+    }
+
+    /// 设置轮询任务状态时是否为等待时间添加随机抖动
+    ///
+    /// 启用后每次等待时间乘以 [0.5, 1.5) 之间的随机数，总等待时间仍不超过超时时间
+    pub fn with_poll_jitter(mut self, enabled: bool) -> AsyncBosonNLP {
+        self.poll_jitter = enabled;
+        self
+    }
+
+    /// 设置响应内容大小上限，默认为 50 MB
+    ///
+    /// 响应头中的 Content-Length 超过上限时不会读取响应内容，否则按解压后的字节数检查
+    pub fn with_max_response_bytes(mut self, max_response_bytes: usize) -> AsyncBosonNLP {
+        self.max_response_bytes = max_response_bytes;
+        self
+    }
+
+    /// 为每个请求额外添加一个 HTTP 头，名称或值不合法时返回 `Error::InvalidArgument`
+    pub fn with_header(mut self, name: &str, value: &str) -> Result<AsyncBosonNLP> {
+        let (name, value) = parse_header(name, value)?;
+        self.headers.append(name, value);
+        Ok(self)
+    }
+
+    /// 设置应用标识，如 ``myapp/2.0``，会添加在默认的 User-Agent 之前
+    pub fn with_user_agent<T: Into<String>>(mut self, user_agent: T) -> AsyncBosonNLP {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// `BosonNLP` HTTP API 的 URL
+    pub fn url(&self) -> &str {
+        &self.bosonnlp_url
+    }
+
+    /// 发送请求使用的 reqwest 异步 Client
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
+
+    /// 设置插入在 API URL 与接口路径之间的前缀，如 ``/v2``
+    pub fn with_api_prefix<T: Into<String>>(mut self, api_prefix: T) -> AsyncBosonNLP {
+        self.api_prefix = Some(api_prefix.into());
+        self
+    }
+
+    async fn request<D, E>(&self, method: Method, endpoint: &str, params: Vec<(&str, &str)>, data: &E, compression: CompressionMode) -> Result<D>
+    where
+        D: DeserializeOwned,
+        E: Serialize,
+    {
+        let url = build_url(&self.bosonnlp_url, self.api_prefix.as_deref(), endpoint, params);
+        let body = if method == Method::POST {
+            let body = serde_json::to_vec(data)?;
+            trace!("Request body: {}", truncate_for_log(&body));
+            if compression.should_compress(self.compress, self.compress_threshold, body.len()) {
+                Some(gzip_body(body, self.compress_level).await?)
+            } else {
+                Some((body, false))
+            }
+        } else {
+            None
+        };
+        let max_retries = if is_idempotent(&method, endpoint) { self.max_retries } else { 0 };
+        let mut retries = 0;
+        loop {
+            match self.send(method.clone(), url.clone(), body.clone()).await {
+                Err(ref err) if retries < max_retries && err.is_retryable() => {
+                    let backoff = retry_delay(err, self.retry_backoff, retries);
+                    retries += 1;
+                    warn!(
+                        "Request to {} failed: {}, retrying in {:?} ({}/{})",
+                        endpoint,
+                        err,
+                        backoff,
+                        retries,
+                        max_retries
+                    );
+                    sleep(backoff).await;
+                }
+                res => return res,
+            }
+        }
+    }
+
+    async fn send<D>(&self, method: Method, url: Url, body: Option<(Vec<u8>, bool)>) -> Result<D>
+    where
+        D: DeserializeOwned,
+    {
+        debug!(
+            "{} {}, body {} bytes{}, extra headers [{}]",
+            method,
+            url,
+            body.as_ref().map_or(0, |(body, _)| body.len()),
+            if body.as_ref().is_some_and(|(_, compressed)| *compressed) { " (gzip)" } else { "" },
+            redact_headers(&self.headers)
+        );
+        let mut req = self.client.request(method.clone(), url.clone())
+            .header(ACCEPT, "application/json")
+            .header("X-Token", self.token.clone());
+        // 浏览器不允许设置 User-Agent 和 Accept-Encoding，并且会自动解压响应
+        if !cfg!(target_arch = "wasm32") {
+            req = req.header(USER_AGENT, user_agent(self.user_agent.as_deref()));
+            if cfg!(feature = "compression") {
+                req = req.header(ACCEPT_ENCODING, "gzip");
+            }
+        }
+        req = req.headers(self.headers.clone());
+        let res = match body {
+            Some((body, compressed)) => {
+                let mut req = req.header(CONTENT_TYPE, "application/json");
+                if compressed {
+                    req = req.header(CONTENT_ENCODING, "gzip");
+                }
+                req.body(body).send().await?
+            }
+            None => req.send().await?,
+        };
+        let status = res.status();
+        let headers = res.headers().clone();
+        if let Some(len) = res.content_length() {
+            check_body_size(len as usize, self.max_response_bytes)?;
+        }
+        let bytes = res.bytes().await?;
+        let gzipped = !cfg!(target_arch = "wasm32") && is_gzipped(&headers);
+        let body = read_body(&bytes[..], gzipped, bytes.len(), self.max_response_bytes)?;
+        debug!("{} {} responded {}, body {} bytes", method, url, status, body.len());
+        trace!("Response body: {}", truncate_for_log(body.as_bytes()));
+        handle_response(status, &headers, body)
+    }
+
+    async fn get<D>(&self, endpoint: &str, params: Vec<(&str, &str)>) -> Result<D>
+    where
+        D: DeserializeOwned,
+    {
+        self.request(Method::GET, endpoint, params, &Value::Null, CompressionMode::Auto).await
+    }
+
+    async fn post<D, E>(&self, endpoint: &str, params: Vec<(&str, &str)>, data: &E) -> Result<D>
+    where
+        D: DeserializeOwned,
+        E: Serialize,
+    {
+        self.post_with(endpoint, params, data, CompressionMode::Auto).await
+    }
+
+    /// 按 ``compression`` 指定的方式压缩请求体并发送 POST 请求
+    async fn post_with<D, E>(&self, endpoint: &str, params: Vec<(&str, &str)>, data: &E, compression: CompressionMode) -> Result<D>
+    where
+        D: DeserializeOwned,
+        E: Serialize,
+    {
+        self.request(Method::POST, endpoint, params, data, compression).await
+    }
+
+    /// 直接调用 ``endpoint`` 对应的 POST 接口，返回未经类型转换的 JSON 响应
+    ///
+    /// 用于调用 SDK 尚未封装的接口，请求同样会带上鉴权头并按配置压缩请求体、处理错误响应，
+    /// 但不会校验响应内容的结构，需要调用方自行解析
+    pub async fn raw_post(&self, endpoint: &str, params: Vec<(&str, &str)>, body: &Value) -> Result<Value> {
+        self.post(endpoint, params, body).await
+    }
+
+    /// 与 `raw_post` 相同，但由 ``compression`` 决定本次请求是否压缩请求体
+    pub async fn raw_post_with(&self, endpoint: &str, params: Vec<(&str, &str)>, body: &Value, compression: CompressionMode) -> Result<Value> {
+        self.post_with(endpoint, params, body, compression).await
+    }
+
+    /// 直接调用 ``endpoint`` 对应的 GET 接口，返回未经类型转换的 JSON 响应
+    ///
+    /// 与 `raw_post` 相同，不会校验响应内容的结构
+    pub async fn raw_get(&self, endpoint: &str, params: Vec<(&str, &str)>) -> Result<Value> {
+        self.get(endpoint, params).await
+    }
+
+    /// 调用请求体为单个 JSON 字符串的 POST 接口，如语义联想和关键词提取接口
+    ///
+    /// ``text`` 会被编码为带引号的 JSON 字符串发送，响应解析为 ``D``，可以使用 `serde_json::Value`
+    pub async fn post_text<D: DeserializeOwned>(&self, endpoint: &str, params: Vec<(&str, &str)>, text: &str) -> Result<D> {
+        self.post(endpoint, params, &text).await
+    }
+
+    /// 检查 API Token 是否有效以及服务是否可以访问
+    ///
+    /// 请求 ``/application/rate_limit_status.json`` 接口，该接口只返回调用频率限制状态，不消耗调用次数。
+    /// Token 无效（401 或 403 响应）时返回 ``Ok(false)``，网络错误等其它错误返回 ``Err``
+    pub async fn verify_token(&self) -> Result<bool> {
+        match self.get::<Value>("/application/rate_limit_status.json", vec![]).await {
+            Ok(_) => Ok(true),
+            Err(Error::Api { code, .. }) if code == StatusCode::UNAUTHORIZED || code == StatusCode::FORBIDDEN => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// 查询各接口的调用额度使用情况
+    ///
+    /// 请求 ``/application/rate_limit_status.json`` 接口，不消耗调用次数，可用于批量任务中控制调用速度
+    pub async fn usage(&self) -> Result<Usage> {
+        self.get("/application/rate_limit_status.json", vec![]).await
+    }
+
+    /// 未设置 ``allow_empty_contents`` 时检查 ``contents`` 中是否有空文本
+    fn check_contents<T: AsRef<str>>(&self, contents: &[T]) -> Result<()> {
+        if self.allow_empty_contents {
+            return Ok(());
+        }
+        validate_contents(contents)
+    }
+
+    /// 将 ``contents`` 按照 ``batch_size`` 拆分为多次请求发送，并按原顺序合并结果
+    async fn post_batch<T, D>(&self, endpoint: &str, params: Vec<(&str, &str)>, contents: &[T]) -> Result<Vec<D>>
+    where
+        T: AsRef<str>,
+        D: DeserializeOwned,
+    {
+        self.check_contents(contents)?;
+        let mut result = Vec::with_capacity(contents.len());
+        for parts in contents.chunks(self.batch_size.max(1)) {
+            let data = parts.iter().map(|c| c.as_ref()).collect::<Vec<_>>();
+            let rs: Vec<D> = self.post(endpoint, params.clone(), &data).await?;
+            result.extend(rs);
+        }
+        Ok(result)
+    }
+
+    /// [情感分析接口](http://docs.bosonnlp.com/sentiment.html)
+    ///
+    /// ``contents``: 需要做情感分析的文本序列
+    ///
+    /// ``model``: 使用不同的语料训练的模型，可以传入 [`SentimentModel`](enum.SentimentModel.html) 或模型名称字符串
+    ///
+    /// # 使用示例
+    ///
+    /// ```no_run
+    /// extern crate bosonnlp;
+    /// extern crate tokio;
+    ///
+    /// use bosonnlp::AsyncBosonNLP;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let nlp = AsyncBosonNLP::new(std::env::var("BOSON_API_TOKEN").unwrap());
+    ///     let handles: Vec<_> = vec!["这家味道还不错", "菜品太少了", "服务一般般"]
+    ///         .into_iter()
+    ///         .map(|text| {
+    ///             let nlp = nlp.clone();
+    ///             tokio::spawn(async move { nlp.sentiment(&[text], "food").await })
+    ///         })
+    ///         .collect();
+    ///     for handle in handles {
+    ///         let rs = handle.await.unwrap().unwrap();
+    ///         assert_eq!(1, rs.len());
+    ///     }
+    /// }
+    /// ```
+    pub async fn sentiment<T, M>(&self, contents: &[T], model: M) -> Result<Vec<Sentiment>>
+    where
+        T: AsRef<str>,
+        M: Into<SentimentModel>,
+    {
+        let model = model.into();
+        self.post_batch("/sentiment/analysis", vec![(model.as_ref(), "")], contents).await
+    }
+
+    /// 对单个文本调用[情感分析接口](http://docs.bosonnlp.com/sentiment.html)
+    pub async fn sentiment_one<T, M>(&self, content: T, model: M) -> Result<Sentiment>
+    where
+        T: AsRef<str>,
+        M: Into<SentimentModel>,
+    {
+        single(self.sentiment(&[content], model).await?)
+    }
+
+    /// [时间转换接口](http://docs.bosonnlp.com/time.html)
+    ///
+    /// ``content``: 需要做时间转换的文本
+    ///
+    /// ``basetime``: 时间描述时的基准时间戳。如果为 ``None`` ，使用服务器当前的GMT+8时间
+    pub async fn convert_time<T: AsRef<str>>(&self, content: T, basetime: Option<T>) -> Result<ConvertedTime> {
+        if let Some(base) = basetime {
+            validate_basetime(base.as_ref())?;
+            let params = vec![("pattern", content.as_ref()), ("basetime", base.as_ref())];
+            self.post("/time/analysis", params, &Value::Null).await
+        } else {
+            let params = vec![("pattern", content.as_ref())];
+            self.post("/time/analysis", params, &Value::Null).await
+        }
+    }
+
+    /// 以 ``basetime`` 为基准时间调用[时间转换接口](http://docs.bosonnlp.com/time.html)，需要启用 `chrono` feature
+    ///
+    /// ``basetime`` 会被转换为服务器使用的 GMT+8 时间
+    #[cfg(feature = "chrono")]
+    pub async fn convert_time_at<T, Tz>(&self, content: T, basetime: chrono::DateTime<Tz>) -> Result<ConvertedTime>
+    where
+        T: AsRef<str>,
+        Tz: chrono::TimeZone,
+    {
+        let basetime = format_basetime(&basetime);
+        self.convert_time(content.as_ref(), Some(basetime.as_str())).await
+    }
+
+    /// 以 Unix 时间戳 ``timestamp``（秒）为基准时间调用[时间转换接口](http://docs.bosonnlp.com/time.html)，
+    /// 需要启用 `chrono` feature
+    #[cfg(feature = "chrono")]
+    pub async fn convert_time_at_timestamp<T: AsRef<str>>(&self, content: T, timestamp: i64) -> Result<ConvertedTime> {
+        let basetime = format_basetime_timestamp(timestamp)?;
+        self.convert_time(content.as_ref(), Some(basetime.as_str())).await
+    }
+
+    /// [新闻分类接口](http://docs.bosonnlp.com/classify.html)
+    ///
+    /// ``contents``: 需要做分类的新闻文本序列
+    pub async fn classify<T: AsRef<str>>(&self, contents: &[T]) -> Result<Vec<usize>> {
+        self.post_batch("/classify/analysis", vec![], contents).await
+    }
+
+    /// 对单个文本调用[新闻分类接口](http://docs.bosonnlp.com/classify.html)
+    pub async fn classify_one<T: AsRef<str>>(&self, content: T) -> Result<usize> {
+        single(self.classify(&[content]).await?)
+    }
+
+    /// [新闻分类接口](http://docs.bosonnlp.com/classify.html)，返回 [`NewsCategory`](enum.NewsCategory.html) 分类
+    ///
+    /// ``contents``: 需要做分类的新闻文本序列
+    pub async fn classify_labeled<T: AsRef<str>>(&self, contents: &[T]) -> Result<Vec<NewsCategory>> {
+        let rs = self.classify(contents).await?;
+        Ok(rs.into_iter().map(NewsCategory::from).collect())
+    }
+
+    /// [语义联想接口](http://docs.bosonnlp.com/suggest.html)
+    ///
+    /// ``word``: 需要做语义联想的词
+    ///
+    /// ``top_k``: 返回结果的条数，1-100 有效
+    pub async fn suggest<T: AsRef<str>>(&self, word: T, top_k: usize) -> Result<Vec<ScoredWord>> {
+        validate_top_k(top_k)?;
+        self.post_text("/suggest/analysis", vec![("top_k", &top_k.to_string())], word.as_ref()).await
+    }
+
+    /// [关键词提取接口](http://docs.bosonnlp.com/keywords.html)
+    ///
+    /// ``text``: 需要做关键词提取的文本
+    ///
+    /// ``top_k``: 返回结果的条数，1-100 有效
+    ///
+    /// ``segmented``: `text` 是否已经进行了分词，若为 `true` 则不会再对内容进行分词处理
+    pub async fn keywords<T: AsRef<str>>(&self, text: T, top_k: usize, segmented: bool) -> Result<Vec<ScoredWord>> {
+        self.keywords_with(text, &KeywordsOptions::default().top_k(top_k).segmented(segmented)).await
+    }
+
+    /// 使用 [`KeywordsOptions`](struct.KeywordsOptions.html) 调用[关键词提取接口](http://docs.bosonnlp.com/keywords.html)
+    pub async fn keywords_with<T: AsRef<str>>(&self, text: T, options: &KeywordsOptions) -> Result<Vec<ScoredWord>> {
+        validate_top_k(options.top_k)?;
+        self.check_contents(&[text.as_ref()])?;
+        let top_k_str = options.top_k.to_string();
+        let mut params = vec![("top_k", top_k_str.as_ref())];
+        if options.segmented || options.t2s {
+            params.push(("segmented", "1"));
+        }
+        if options.t2s {
+            let words = self.simplified_words(&[text]).await?;
+            return self.post_text("/keywords/analysis", params, &words[0]).await;
+        }
+        self.post_text("/keywords/analysis", params, text.as_ref()).await
+    }
+
+    /// 使用已分词的输入调用[关键词提取接口](http://docs.bosonnlp.com/keywords.html)
+    ///
+    /// ``words``: 分词结果，发送时以空格连接，词为空或包含空白字符时返回 `Error::InvalidArgument`
+    pub async fn keywords_segmented<W: AsRef<str>>(&self, words: &[W], top_k: usize) -> Result<Vec<ScoredWord>> {
+        let text = join_words(words)?;
+        self.keywords_with(text, &KeywordsOptions::default().top_k(top_k).segmented(true)).await
+    }
+
+    /// 使用分词与词性标注接口将繁体转换为简体并分词，返回以空格分隔的分词结果
+    async fn simplified_words<T: AsRef<str>>(&self, contents: &[T]) -> Result<Vec<String>> {
+        let tags = self.tag_with(contents, &TagOptions::default().t2s(true)).await?;
+        Ok(tags.into_iter().map(|tag| tag.word.join(" ")).collect())
+    }
+
+    /// [依存文法分析接口](http://docs.bosonnlp.com/depparser.html)
+    ///
+    /// ``contents``: 需要做依存文法分析的文本序列
+    pub async fn depparser<T: AsRef<str>>(&self, contents: &[T]) -> Result<Vec<Dependency>> {
+        self.depparser_segmented(contents, false).await
+    }
+
+    /// 对单个文本调用[依存文法分析接口](http://docs.bosonnlp.com/depparser.html)
+    pub async fn depparser_one<T: AsRef<str>>(&self, content: T) -> Result<Dependency> {
+        single(self.depparser(&[content]).await?)
+    }
+
+    /// [依存文法分析接口](http://docs.bosonnlp.com/depparser.html)，支持已经分词的输入
+    ///
+    /// ``segmented``: 输入是否已经为分词结果，若为 `true` 则不会再对内容进行分词处理，词之间以空格分隔
+    pub async fn depparser_segmented<T: AsRef<str>>(&self, contents: &[T], segmented: bool) -> Result<Vec<Dependency>> {
+        let params = if segmented { vec![("segmented", "1")] } else { vec![] };
+        let rs: Vec<Dependency> = self.post_batch("/depparser/analysis", params, contents).await?;
+        for dep in &rs {
+            dep.validate()?;
+        }
+        Ok(rs)
+    }
+
+    /// [命名实体识别接口](http://docs.bosonnlp.com/ner.html)
+    ///
+    /// ``contents``: 需要做命名实体识别的文本序列
+    ///
+    /// ``sensitivity``: 准确率与召回率之间的平衡。
+    /// 设置成 1 能找到更多的实体，设置成 5 能以更高的精度寻找实体
+    /// 一般设置为 3
+    ///
+    /// ``segmented``: 输入是否已经为分词结果
+    pub async fn ner<T: AsRef<str>>(&self, contents: &[T], sensitivity: usize, segmented: bool) -> Result<Vec<NamedEntity>> {
+        let options = NerOptions::default().sensitivity(sensitivity).segmented(segmented);
+        self.ner_with(contents, &options).await
+    }
+
+    /// 对单个文本调用[命名实体识别接口](http://docs.bosonnlp.com/ner.html)
+    pub async fn ner_one<T: AsRef<str>>(&self, content: T, sensitivity: usize, segmented: bool) -> Result<NamedEntity> {
+        single(self.ner(&[content], sensitivity, segmented).await?)
+    }
+
+    /// 使用已分词的输入调用[命名实体识别接口](http://docs.bosonnlp.com/ner.html)
+    ///
+    /// ``contents``: 每个文本的分词结果，发送时以空格连接，词为空或包含空白字符时返回 `Error::InvalidArgument`
+    pub async fn ner_segmented<W: AsRef<str>>(&self, contents: &[Vec<W>], sensitivity: usize) -> Result<Vec<NamedEntity>> {
+        let contents = contents.iter().map(|words| join_words(words)).collect::<Result<Vec<_>>>()?;
+        self.ner_with(&contents, &NerOptions::default().sensitivity(sensitivity).segmented(true)).await
+    }
+
+    /// 使用 [`NerOptions`](struct.NerOptions.html) 调用[命名实体识别接口](http://docs.bosonnlp.com/ner.html)
+    pub async fn ner_with<T: AsRef<str>>(&self, contents: &[T], options: &NerOptions) -> Result<Vec<NamedEntity>> {
+        let sensitivity_str = options.sensitivity.to_string();
+        let mut params = vec![("sensitivity", sensitivity_str.as_ref())];
+        if options.segmented || options.t2s {
+            params.push(("segmented", "1"));
+        }
+        if options.t2s {
+            let contents = self.simplified_words(contents).await?;
+            return self.post_batch("/ner/analysis", params, &contents).await;
+        }
+        self.post_batch("/ner/analysis", params, contents).await
+    }
+
+    /// [分词与词性标注接口](http://docs.bosonnlp.com/tag.html)
+    ///
+    /// ``contents``: 需要做分词与词性标注的文本序列
+    ///
+    /// ``space_mode``: 空格保留选项，0-3 有效
+    ///
+    /// ``oov_level``: 枚举强度选项，0-4 有效
+    ///
+    /// ``t2s``: 是否开启繁体转简体
+    ///
+    /// ``special_char_conv``: 是否转化特殊字符，针对回车、Tab 等特殊字符。
+    pub async fn tag<T: AsRef<str>>(
+        &self,
+        contents: &[T],
+        space_mode: usize,
+        oov_level: usize,
+        t2s: bool,
+        special_char_conv: bool,
+    ) -> Result<Vec<Tag>> {
+        let options = TagOptions::default()
+            .space_mode(space_mode)
+            .oov_level(oov_level)
+            .t2s(t2s)
+            .special_char_conv(special_char_conv);
+        self.tag_with(contents, &options).await
+    }
+
+    /// 对单个文本调用[分词与词性标注接口](http://docs.bosonnlp.com/tag.html)
+    pub async fn tag_one<T: AsRef<str>>(
+        &self,
+        content: T,
+        space_mode: usize,
+        oov_level: usize,
+        t2s: bool,
+        special_char_conv: bool,
+    ) -> Result<Tag> {
+        single(self.tag(&[content], space_mode, oov_level, t2s, special_char_conv).await?)
+    }
+
+    /// 使用 [`TagOptions`](struct.TagOptions.html) 调用[分词与词性标注接口](http://docs.bosonnlp.com/tag.html)
+    ///
+    /// ``space_mode`` 或 ``oov_level`` 超出有效范围时返回 `Error::InvalidArgument`，不会发送请求
+    pub async fn tag_with<T: AsRef<str>>(&self, contents: &[T], options: &TagOptions) -> Result<Vec<Tag>> {
+        options.validate()?;
+        let params = options.params();
+        self.post_batch("/tag/analysis", params.iter().map(|(k, v)| (*k, v.as_str())).collect(), contents).await
+    }
+
+    /// [新闻摘要接口](http://docs.bosonnlp.com/summary.html)
+    ///
+    /// ``title``: 需要做摘要的新闻标题，如果没有则传入空字符串
+    ///
+    /// ``content``: 需要做摘要的新闻正文，最多 10000 字
+    ///
+    /// ``percentage``: 摘要长度占原文长度的比例，大于 0 且不超过 1 的小数
+    ///
+    /// ``not_exceed``: 是否严格限制字数
+    pub async fn summary<T: AsRef<str>, U: AsRef<str>>(&self, title: T, content: U, percentage: f32, not_exceed: bool) -> Result<String> {
+        validate_summary(content.as_ref(), Some(percentage))?;
+        let data = summary_data(title.as_ref(), content.as_ref(), json!(percentage), not_exceed);
+        self.post("/summary/analysis", vec![], &data).await
+    }
+
+    /// [新闻摘要接口](http://docs.bosonnlp.com/summary.html)，按字数限制摘要长度
+    ///
+    /// ``max_words``: 摘要的最大字数
+    pub async fn summary_by_words<T: AsRef<str>, U: AsRef<str>>(&self, title: T, content: U, max_words: usize, not_exceed: bool) -> Result<String> {
+        validate_summary(content.as_ref(), None)?;
+        let data = summary_data(title.as_ref(), content.as_ref(), json!(max_words), not_exceed);
+        self.post("/summary/analysis", vec![], &data).await
+    }
+
+    /// [文本聚类接口](http://docs.bosonnlp.com/cluster.html)
+    ///
+    /// ``task_id``: 唯一的 task_id，话题聚类任务的名字，可由字母和数字组成
+    ///
+    /// ``alpha``: 聚类最大 cluster 大小，一般为 0.8
+    ///
+    /// ``beta``: 聚类平均 cluster 大小，一般为 0.45
+    ///
+    /// ``timeout``: 等待文本聚类任务完成的秒数，一般为 1800 秒
+    pub async fn cluster<I, T>(
+        &self,
+        contents: I,
+        task_id: Option<&str>,
+        alpha: f32,
+        beta: f32,
+        timeout: Option<u64>,
+    ) -> Result<Vec<TextCluster>>
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<str>,
+    {
+        let mut options = ClusterOptions::default().alpha(alpha).beta(beta).timeout(timeout);
+        options.task_id = task_id.map(|id| id.to_owned());
+        self.cluster_with(contents, &options).await
+    }
+
+    /// 使用 [`ClusterOptions`](struct.ClusterOptions.html) 调用[文本聚类接口](http://docs.bosonnlp.com/cluster.html)
+    ///
+    /// 获取结果后会清空服务器端的任务数据，清空失败不影响返回的结果，只会记录一条警告日志。
+    /// 设置 ``keep_result`` 后不会清空
+    pub async fn cluster_with<I, T>(&self, contents: I, options: &ClusterOptions) -> Result<Vec<TextCluster>>
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<str>,
+    {
+        let contents = contents.into_iter().map(|c| ClusterContent::from(c.as_ref())).collect();
+        self.run_task("cluster", contents, options).await
+    }
+
+    /// 调用[文本聚类接口](http://docs.bosonnlp.com/cluster.html)，结果中的文档编号会被替换为对应的文本
+    pub async fn cluster_with_text<I, T>(&self, contents: I, options: &ClusterOptions) -> Result<Vec<TextClusterWithText>>
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<str>,
+    {
+        let contents: Vec<ClusterContent> = contents.into_iter().map(|c| ClusterContent::from(c.as_ref())).collect();
+        let result: Vec<TextCluster> = self.run_task("cluster", contents.clone(), options).await?;
+        result.iter().map(|cluster| cluster.with_text(&contents)).collect()
+    }
+
+    /// 使用自定义的文档编号调用[文本聚类接口](http://docs.bosonnlp.com/cluster.html)
+    ///
+    /// ``contents``: ``(文档编号, 文本)`` 序列，结果中使用这里的文档编号表示文档
+    pub async fn cluster_with_ids<I, K, V>(&self, contents: I, options: &ClusterOptions) -> Result<Vec<TextCluster>>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        let contents = Vec::from_iter(contents.into_iter().map(|(id, text)| ClusterContent::with_id(id, text)));
+        self.run_task("cluster", contents, options).await
+    }
+
+    /// [典型意见接口](http://docs.bosonnlp.com/comments.html)
+    ///
+    /// ``task_id``: 唯一的 task_id，典型意见任务的名字，可由字母和数字组成
+    ///
+    /// ``alpha``: 聚类最大 cluster 大小，一般为 0.8
+    ///
+    /// ``beta``: 聚类平均 cluster 大小，一般为 0.45
+    ///
+    /// ``timeout``: 等待典型意见任务完成的秒数，一般为 1800 秒
+    pub async fn comments<I, T>(
+        &self,
+        contents: I,
+        task_id: Option<&str>,
+        alpha: f32,
+        beta: f32,
+        timeout: Option<u64>,
+    ) -> Result<Vec<CommentsCluster>>
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<str>,
+    {
+        let mut options = ClusterOptions::default().alpha(alpha).beta(beta).timeout(timeout);
+        options.task_id = task_id.map(|id| id.to_owned());
+        self.comments_with(contents, &options).await
+    }
+
+    /// 使用 [`ClusterOptions`](struct.ClusterOptions.html) 调用[典型意见接口](http://docs.bosonnlp.com/comments.html)
+    ///
+    /// 获取结果后会清空服务器端的任务数据，清空失败不影响返回的结果，只会记录一条警告日志。
+    /// 设置 ``keep_result`` 后不会清空
+    pub async fn comments_with<I, T>(&self, contents: I, options: &ClusterOptions) -> Result<Vec<CommentsCluster>>
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<str>,
+    {
+        let contents = contents.into_iter().map(|c| ClusterContent::from(c.as_ref())).collect();
+        self.run_task("comments", contents, options).await
+    }
+
+    /// 使用自定义的文档编号调用[典型意见接口](http://docs.bosonnlp.com/comments.html)
+    ///
+    /// ``contents``: ``(文档编号, 文本)`` 序列，结果中使用这里的文档编号表示文档
+    pub async fn comments_with_ids<I, K, V>(&self, contents: I, options: &ClusterOptions) -> Result<Vec<CommentsCluster>>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        let contents = Vec::from_iter(contents.into_iter().map(|(id, text)| ClusterContent::with_id(id, text)));
+        self.run_task("comments", contents, options).await
+    }
+
+    /// 清空 ``task_id`` 对应的文本聚类任务在服务器端缓存的文本和结果
+    pub async fn clear_cluster_task(&self, task_id: &str) -> Result<()> {
+        self.clear_task("cluster", task_id).await
+    }
+
+    /// 清空 ``task_id`` 对应的典型意见任务在服务器端缓存的文本和结果
+    pub async fn clear_comments_task(&self, task_id: &str) -> Result<()> {
+        self.clear_task("comments", task_id).await
+    }
+
+    /// 继续等待已提交的文本聚类任务完成，获取结果后清空服务器端的任务数据，
+    /// 参见 [`BosonNLP::resume_cluster`](struct.BosonNLP.html#method.resume_cluster)
+    pub async fn resume_cluster(&self, task_id: &str, timeout: Option<u64>) -> Result<Vec<TextCluster>> {
+        self.resume_task("cluster", task_id, timeout).await
+    }
+
+    /// 继续等待已提交的典型意见任务完成，获取结果后清空服务器端的任务数据
+    pub async fn resume_comments(&self, task_id: &str, timeout: Option<u64>) -> Result<Vec<CommentsCluster>> {
+        self.resume_task("comments", task_id, timeout).await
+    }
+
+    /// 等待已提交的任务完成，获取结果并清空
+    async fn resume_task<D: DeserializeOwned>(&self, kind: &str, task_id: &str, timeout: Option<u64>) -> Result<Vec<D>> {
+        self.wait(kind, task_id, &ClusterOptions::default().timeout(timeout)).await?;
+        let endpoint = task_endpoint(kind, "result", task_id)?;
+        let result = self.get(&endpoint, vec![]).await?;
+        if let Err(err) = self.clear_task(kind, task_id).await {
+            warn!("Failed to clear {} task {}: {}", kind, task_id, err);
+        }
+        Ok(result)
+    }
+
+    /// 执行一个完整的聚类任务：上传、分析、等待、获取结果并清空
+    async fn run_task<D: DeserializeOwned>(
+        &self,
+        kind: &str,
+        tasks: Vec<ClusterContent>,
+        options: &ClusterOptions,
+    ) -> Result<Vec<D>> {
+        self.check_contents(&tasks.iter().map(|c| c.text.as_str()).collect::<Vec<_>>())?;
+        options.validate()?;
+        let task_id = match options.task_id {
+            Some(ref _id) => _id.to_owned(),
+            None => Uuid::new_v4().to_simple_ref().to_string(),
+        };
+        if tasks.is_empty() {
+            return Ok(vec![]);
+        }
+        let endpoint = task_endpoint(kind, "push", &task_id)?;
+        let mut count = 0;
+        for parts in tasks.chunks(options.chunk_size.max(1)) {
+            let resp: TaskPushResp = self.post(&endpoint, vec![], &parts).await?;
+            count += resp.count;
+            info!("Pushed {} of {} documents for {}", parts.len(), tasks.len(), kind);
+        }
+        if count != tasks.len() {
+            warn!("{} task {} received {} of {} pushed documents", kind, task_id, count, tasks.len());
+        }
+
+        let endpoint = task_endpoint(kind, "analysis", &task_id)?;
+        let alpha_str = options.alpha.to_string();
+        let beta_str = options.beta.to_string();
+        let params = vec![("alpha", alpha_str.as_ref()), ("beta", beta_str.as_ref())];
+        let _: TaskStatusResp = self.get(&endpoint, params).await?;
+        info!("{} task {} analysis started", kind, task_id);
+
+        self.wait(kind, &task_id, options).await?;
+
+        let endpoint = task_endpoint(kind, "result", &task_id)?;
+        let result = self.get(&endpoint, vec![]).await?;
+
+        if options.keep_result {
+            return Ok(result);
+        }
+        if let Err(err) = self.clear_task(kind, &task_id).await {
+            warn!("Failed to clear {} task {}: {}", kind, task_id, err);
+        }
+        Ok(result)
+    }
+
+    /// 清空服务器端缓存的文本和结果
+    async fn clear_task(&self, kind: &str, task_id: &str) -> Result<()> {
+        let endpoint = task_endpoint(kind, "clear", task_id)?;
+        let _: Value = self.get(&endpoint, vec![]).await?;
+        info!("{} task {} cleared", kind, task_id);
+        Ok(())
+    }
+
+    /// 等待任务完成，``options`` 中的取消标志被设置时返回 `Error::Cancelled`
+    async fn wait(&self, kind: &str, task_id: &str, options: &ClusterOptions) -> Result<()> {
+        let endpoint = task_endpoint(kind, "status", task_id)?;
+        validate_poll_interval(options.poll_interval)?;
+        let mut backoff = Backoff::new(options.timeout, options.poll_interval);
+        if self.poll_jitter {
+            backoff = backoff.with_jitter(random_seed());
+        }
+        let cancelled = || options.cancel.as_ref().is_some_and(|cancel| cancel.is_set());
+        for seconds_to_sleep in backoff {
+            if cancelled() {
+                return Err(Error::Cancelled(task_id.to_owned()));
+            }
+            sleep(seconds_to_sleep).await;
+            if cancelled() {
+                return Err(Error::Cancelled(task_id.to_owned()));
+            }
+            let status_resp: TaskStatusResp = self.get(&endpoint, vec![]).await?;
+            info!("{} task {} status: {:?}", kind, task_id, status_resp.status);
+            match status_resp.status {
+                TaskStatus::Done => return Ok(()),
+                TaskStatus::NotFound => return Err(Error::TaskNotFound(task_id.to_owned())),
+                _ => {}
+            }
+        }
+        Err(Error::Timeout(task_id.to_owned()))
+    }
+}
@@ -0,0 +1,68 @@
+use std::cmp::{max, min};
+use std::time::Duration;
+
+use uuid::Uuid;
+
+/// 轮询任务状态的退避策略
+///
+/// 首次等待 ``poll_interval``（默认 1 秒），每轮询 3 次等待时间翻倍，最长 64 秒，
+/// 且不会超过剩余的超时时间。即使超时时间为 0 也至少轮询一次
+///
+/// 启用随机抖动后每次等待时间乘以 [0.5, 1.5) 之间的随机数，避免多个任务同步轮询
+pub(crate) struct Backoff {
+    timeout: Option<Duration>,
+    elapsed: Duration,
+    interval: Duration,
+    max_interval: Duration,
+    polls: usize,
+    rng: Option<fastrand::Rng>,
+}
+
+impl Backoff {
+    pub fn new(timeout: Option<u64>, poll_interval: Option<Duration>) -> Backoff {
+        let interval = poll_interval.unwrap_or_else(|| Duration::from_secs(1u64));
+        Backoff {
+            timeout: timeout.map(Duration::from_secs),
+            elapsed: Duration::from_secs(0u64),
+            interval,
+            max_interval: max(interval, Duration::from_secs(64u64)),
+            polls: 0usize,
+            rng: None,
+        }
+    }
+
+    /// 使用 ``seed`` 初始化随机数生成器，为每次等待时间添加随机抖动
+    pub fn with_jitter(mut self, seed: u64) -> Backoff {
+        self.rng = Some(fastrand::Rng::with_seed(seed));
+        self
+    }
+}
+
+/// 生成随机数种子，WASM 环境中同样可用
+pub(crate) fn random_seed() -> u64 {
+    Uuid::new_v4().as_u128() as u64
+}
+
+impl Iterator for Backoff {
+    type Item = Duration;
+
+    /// 下一次轮询前需要等待的时间，已轮询过且超时则返回 `None`
+    fn next(&mut self) -> Option<Duration> {
+        let mut seconds_to_sleep = match self.rng {
+            Some(ref mut rng) => self.interval.mul_f64(0.5 + rng.f64()),
+            None => self.interval,
+        };
+        if let Some(timeout) = self.timeout {
+            if self.polls > 0 && self.elapsed >= timeout {
+                return None;
+            }
+            seconds_to_sleep = min(seconds_to_sleep, timeout.saturating_sub(self.elapsed));
+        }
+        self.elapsed += seconds_to_sleep;
+        self.polls += 1usize;
+        if self.polls % 3usize == 0 {
+            self.interval = min(self.interval * 2, self.max_interval);
+        }
+        Some(seconds_to_sleep)
+    }
+}
@@ -1,150 +1,634 @@
-use std::io::{Read, Write};
-use std::iter::FromIterator;
+use std::fmt;
+use std::io::{BufRead, Read};
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
 
 use serde::Serialize;
 use serde::de::DeserializeOwned;
-use serde_json::{self, Value, Map};
+use serde_json::Value;
 use url::Url;
 use uuid::Uuid;
-use flate2::Compression;
-use flate2::write::GzEncoder;
-use reqwest::Method;
-use reqwest::blocking::Client;
-use reqwest::header::{USER_AGENT, ACCEPT, CONTENT_ENCODING, CONTENT_TYPE};
+#[cfg(feature = "compression")]
+use flate2::read::GzDecoder;
+use reqwest::{Method, Proxy, StatusCode};
+use reqwest::blocking::{Client, Response};
+use reqwest::header::{HeaderMap, HeaderValue, USER_AGENT, ACCEPT, ACCEPT_ENCODING, CONTENT_ENCODING, CONTENT_TYPE};
 
-use errors::*;
-use rep::{Dependency, NamedEntity, Tag, TextCluster, CommentsCluster, ConvertedTime, ClusterContent};
-use task::{ClusterTask, CommentsTask, Task};
+use crate::errors::*;
+use crate::rep::{Usage, ClusterContent, Sentiment, SentimentModel, NewsCategory, ScoredWord, Dependency, NamedEntity, Tag, TextCluster, TextClusterWithText, CommentsCluster, ConvertedTime};
+use crate::options::{ClusterOptions, CompressionMode, KeywordsOptions, NerOptions, TagOptions};
+use crate::task::{ClusterTask, ClusterTaskHandle, CommentsTask, CommentsTaskHandle, Task};
+#[cfg(feature = "chrono")]
+use crate::http::{format_basetime, format_basetime_timestamp};
+use crate::http::{DEFAULT_BOSONNLP_URL, DEFAULT_COMPRESS_LEVEL, DEFAULT_MAX_RESPONSE_BYTES, MAX_COMPRESS_LEVEL, build_url, user_agent, warn_insecure_url, compress_body, handle_response, is_gzipped, is_idempotent,
+                  parse_header, read_body, redact_headers, truncate_for_log, retry_delay, summary_data, LimitedReader, PreparedRequest};
+use crate::validate::{validate_basetime, validate_contents, validate_top_k, validate_summary, join_words, single};
+use crate::backoff::{random_seed, Backoff};
 
 
-/// 默认的 `BosonNLP` API 服务器地址
-const DEFAULT_BOSONNLP_URL: &'static str = "https://api.bosonnlp.com";
+/// 请求重试和轮询任务状态之间的等待方式
+///
+/// 默认为 [`ThreadSleeper`](struct.ThreadSleeper.html)，测试中可以通过
+/// [`BosonNLP::with_sleeper`](struct.BosonNLP.html#method.with_sleeper) 替换为不实际等待的实现
+pub trait Sleeper: fmt::Debug + Send + Sync {
+    /// 等待 ``duration``
+    fn sleep(&self, duration: Duration);
+}
+
+/// 使用 `std::thread::sleep` 阻塞当前线程
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ThreadSleeper;
+
+impl Sleeper for ThreadSleeper {
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration)
+    }
+}
+
+/// 单次 HTTP 请求的统计信息，参见 [`BosonNLP::with_on_request`](struct.BosonNLP.html#method.with_on_request)
+#[derive(Debug, Clone)]
+pub struct RequestMetrics {
+    /// 接口路径，如 ``/sentiment/analysis``
+    pub endpoint: String,
+    /// 请求方法
+    pub method: Method,
+    /// 响应状态码，未收到响应（如连接失败）时为 ``None``
+    pub status: Option<StatusCode>,
+    /// 从发送请求到读取完响应内容的耗时，以流的方式获取聚类结果时为收到响应头的耗时
+    pub duration: Duration,
+    /// 请求体字节数，压缩时为压缩后的大小
+    pub request_size: usize,
+    /// 解压后的响应内容字节数，以流的方式成功获取聚类结果时为 0
+    pub response_size: usize,
+}
+
+/// 每次请求完成后调用的回调
+#[derive(Clone)]
+struct RequestHook(Arc<dyn Fn(&RequestMetrics) + Send + Sync>);
+
+impl fmt::Debug for RequestHook {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("RequestHook")
+    }
+}
+
+type TaskCallback = dyn Fn(&str, &str) + Send + Sync;
+
+/// 提交聚类任务前调用的回调，参数为任务类型和 task_id
+#[derive(Clone)]
+struct TaskHook(Arc<TaskCallback>);
+
+impl fmt::Debug for TaskHook {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("TaskHook")
+    }
+}
 
 /// [`BosonNLP`](http://bosonnlp.com) REST API 访问的封装
 #[derive(Debug, Clone)]
 pub struct BosonNLP {
     /// 用于 API 鉴权的 API Token
     pub token: String,
-    /// 是否压缩大于 10K 的请求体，默认为 true
+    /// 是否压缩大于 ``compress_threshold`` 的请求体，默认为 true，未启用 `compression` feature 时不生效
     pub compress: bool,
-    /// `BosonNLP` HTTP API 的 URL，默认为 `http://api.bosonnlp.com`
+    /// 请求体超过该字节数时进行压缩，默认为 10240
+    pub compress_threshold: usize,
+    /// gzip 压缩级别，0-9，默认为 6，超过 9 时按 9 处理
+    pub compress_level: u32,
+    /// 请求遇到临时性错误时的最大重试次数，默认为 0 即不重试
+    pub max_retries: usize,
+    /// 首次重试前等待的时间，之后每次重试等待时间翻倍，默认为 1 秒
+    pub retry_backoff: Duration,
+    /// 批量接口单次请求最多发送的文本数量，超出时自动拆分为多次请求，默认为 100
+    pub batch_size: usize,
+    /// 是否允许发送空文本或只包含空白字符的文本，默认为 false，即发送前返回 `Error::InvalidArgument`
+    pub allow_empty_contents: bool,
+    /// 轮询任务状态时是否为等待时间添加随机抖动，默认为 false
+    pub poll_jitter: bool,
+    /// 单次 HTTP 请求的超时时间，设置后覆盖 Client 的超时设置，默认为 None
+    pub request_timeout: Option<Duration>,
+    /// 轮询任务状态请求的超时时间，默认为 30 秒，不受 ``request_timeout`` 影响
+    pub poll_request_timeout: Option<Duration>,
+    /// 解压后的响应内容大小上限，默认为 50 MB
+    pub max_response_bytes: usize,
+    /// 随机抖动使用的随机数种子，默认为 None 即每次等待任务时随机生成
+    jitter_seed: Option<u64>,
+    /// 每个请求额外附带的 HTTP 头，会覆盖同名的默认请求头
+    pub headers: HeaderMap,
+    /// 应用标识，设置后会添加在默认的 User-Agent 之前，默认为 None
+    pub user_agent: Option<String>,
+    /// 插入在 API URL 与接口路径之间的前缀，如 ``/v2``，默认为 None
+    pub api_prefix: Option<String>,
+    /// `BosonNLP` HTTP API 的 URL，默认为 `https://api.bosonnlp.com`
     bosonnlp_url: String,
     /// hyper http Client
     client: Client,
+    /// 请求重试和轮询任务状态之间的等待方式
+    pub(crate) sleeper: Arc<dyn Sleeper>,
+    /// 每次请求完成后调用的回调
+    on_request: Option<RequestHook>,
+    /// 提交聚类任务前调用的回调
+    on_task: Option<TaskHook>,
 }
 
+/// 编译期检查 `BosonNLP` 可以在线程间共享，新增字段不能破坏这一点
+const _: fn() = || {
+    fn assert<T: Send + Sync>() {}
+    assert::<BosonNLP>();
+};
+
 impl Default for BosonNLP {
     fn default() -> BosonNLP {
-        BosonNLP {
-            token: "".to_string(),
-            compress: true,
-            bosonnlp_url: DEFAULT_BOSONNLP_URL.to_owned(),
-            client: Client::new(),
+        BosonNLP::new("")
+    }
+}
+
+/// 用于构建 [`BosonNLP`](struct.BosonNLP.html) 实例的 builder
+///
+/// 未设置的选项使用默认值，``token`` 为必填项
+///
+/// # 使用示例
+///
+/// ```
+/// extern crate bosonnlp;
+///
+/// use std::time::Duration;
+/// use bosonnlp::{BosonNLP, BosonNLPBuilder};
+///
+/// fn main() {
+///     let nlp = BosonNLP::builder()
+///         .token("token")
+///         .compress(false)
+///         .timeout(Duration::from_secs(10))
+///         .build()
+///         .unwrap();
+///     assert_eq!("token", nlp.token);
+///     assert!(!nlp.compress);
+///
+///     assert!(BosonNLPBuilder::new().build().is_err());
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct BosonNLPBuilder {
+    token: Option<String>,
+    url: Option<String>,
+    compress: Option<bool>,
+    timeout: Option<Duration>,
+    proxy: Option<(String, Option<(String, String)>)>,
+    pool_idle_timeout: Option<Duration>,
+    pool_max_idle_per_host: Option<usize>,
+    http2_prior_knowledge: bool,
+    headers: Vec<(String, String)>,
+    user_agent: Option<String>,
+    api_prefix: Option<String>,
+    client: Option<Client>,
+}
+
+impl BosonNLPBuilder {
+    /// 创建一个新的 `BosonNLPBuilder`
+    pub fn new() -> BosonNLPBuilder {
+        Default::default()
+    }
+
+    /// 用于 API 鉴权的 API Token
+    pub fn token<T: Into<String>>(mut self, token: T) -> BosonNLPBuilder {
+        self.token = Some(token.into());
+        self
+    }
+
+    /// `BosonNLP` HTTP API 的 URL，默认为 `https://api.bosonnlp.com`
+    ///
+    /// 使用非本机的 ``http://`` 地址时 API Token 会以明文传输，构建时会输出警告日志
+    ///
+    /// # 使用示例
+    ///
+    /// ```
+    /// extern crate bosonnlp;
+    /// extern crate log;
+    ///
+    /// use std::sync::Mutex;
+    /// use log::{Level, LevelFilter, Log, Metadata, Record};
+    /// use bosonnlp::BosonNLP;
+    ///
+    /// static WARNINGS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+    ///
+    /// struct Logger;
+    ///
+    /// impl Log for Logger {
+    ///     fn enabled(&self, _: &Metadata) -> bool { true }
+    ///     fn log(&self, record: &Record) {
+    ///         if record.level() == Level::Warn {
+    ///             WARNINGS.lock().unwrap().push(record.args().to_string());
+    ///         }
+    ///     }
+    ///     fn flush(&self) {}
+    /// }
+    ///
+    /// fn main() {
+    ///     log::set_logger(&Logger).unwrap();
+    ///     log::set_max_level(LevelFilter::Warn);
+    ///
+    ///     let nlp = BosonNLP::new("token");
+    ///     assert_eq!("https://api.bosonnlp.com", nlp.url());
+    ///     BosonNLP::with_options("token", "http://127.0.0.1:8080", false);
+    ///     BosonNLP::with_options("token", "http://localhost:8080", false);
+    ///     assert!(WARNINGS.lock().unwrap().is_empty());
+    ///
+    ///     BosonNLP::with_options("token", "http://gateway.example.com", false);
+    ///     let warnings = WARNINGS.lock().unwrap();
+    ///     assert_eq!(1, warnings.len());
+    ///     assert!(warnings[0].contains("http://gateway.example.com"));
+    /// }
+    /// ```
+    pub fn url<T: Into<String>>(mut self, url: T) -> BosonNLPBuilder {
+        self.url = Some(url.into());
+        self
+    }
+
+    /// 是否压缩大于 ``compress_threshold`` 的请求体，默认为 true
+    pub fn compress(mut self, compress: bool) -> BosonNLPBuilder {
+        self.compress = Some(compress);
+        self
+    }
+
+    /// 单次 HTTP 请求的超时时间，默认不超时
+    ///
+    /// 设置了 ``client`` 时该选项无效
+    pub fn timeout(mut self, timeout: Duration) -> BosonNLPBuilder {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// 通过 HTTP 代理发送请求，``credentials`` 为代理服务器的用户名和密码
+    ///
+    /// 设置了 ``client`` 时该选项无效
+    pub fn proxy<T: Into<String>>(mut self, proxy_url: T, credentials: Option<(&str, &str)>) -> BosonNLPBuilder {
+        let credentials = credentials.map(|(username, password)| (username.to_owned(), password.to_owned()));
+        self.proxy = Some((proxy_url.into(), credentials));
+        self
+    }
+
+    /// 连接池中空闲连接的保留时间，默认为 90 秒
+    ///
+    /// 批量处理时请求间隔较长的话可以适当调大，避免每批请求都重新建立 TLS 连接。
+    /// 设置了 ``client`` 时该选项无效
+    pub fn pool_idle_timeout(mut self, timeout: Duration) -> BosonNLPBuilder {
+        self.pool_idle_timeout = Some(timeout);
+        self
+    }
+
+    /// 连接池中每个主机最多保留的空闲连接数，默认不限制
+    ///
+    /// 使用 ``sentiment_parallel`` 等并发接口时建议设置为并发数，以便复用连接。
+    /// 设置了 ``client`` 时该选项无效
+    pub fn pool_max_idle_per_host(mut self, max_idle: usize) -> BosonNLPBuilder {
+        self.pool_max_idle_per_host = Some(max_idle);
+        self
+    }
+
+    /// 不经过协商直接使用 HTTP/2 发送请求，只应在确定服务器支持 HTTP/2 时启用
+    ///
+    /// 启用 `rustls` feature 时会通过 TLS ALPN 自动协商，服务器支持时优先使用 HTTP/2，
+    /// 不需要设置该选项。设置了 ``client`` 时该选项无效
+    pub fn http2_prior_knowledge(mut self) -> BosonNLPBuilder {
+        self.http2_prior_knowledge = true;
+        self
+    }
+
+    /// 为每个请求额外添加一个 HTTP 头，如 API 网关要求的鉴权头
+    ///
+    /// # 使用示例
+    ///
+    /// ```
+    /// extern crate bosonnlp;
+    ///
+    /// use bosonnlp::BosonNLP;
+    ///
+    /// fn main() {
+    ///     let nlp = BosonNLP::builder()
+    ///         .token("token")
+    ///         .header("X-Api-Gateway-Key", "secret")
+    ///         .build()
+    ///         .unwrap();
+    /// }
+    /// ```
+    pub fn header<N: Into<String>, V: Into<String>>(mut self, name: N, value: V) -> BosonNLPBuilder {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// 应用标识，如 ``myapp/2.0``，会添加在默认的 User-Agent 之前，
+    /// 即请求的 User-Agent 为 ``myapp/2.0 bosonnlp-rs/{版本号}``
+    ///
+    /// # 使用示例
+    ///
+    /// ```
+    /// extern crate bosonnlp;
+    ///
+    /// use bosonnlp::BosonNLP;
+    ///
+    /// fn main() {
+    ///     let nlp = BosonNLP::builder()
+    ///         .token("token")
+    ///         .user_agent("myapp/2.0")
+    ///         .build()
+    ///         .unwrap();
+    /// }
+    /// ```
+    pub fn user_agent<T: Into<String>>(mut self, user_agent: T) -> BosonNLPBuilder {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// 插入在 API URL 与接口路径之间的前缀，如 ``/v2``，用于带有版本号或经过网关转发的 API
+    ///
+    /// # 使用示例
+    ///
+    /// ```
+    /// extern crate bosonnlp;
+    ///
+    /// use bosonnlp::BosonNLP;
+    ///
+    /// fn main() {
+    ///     let nlp = BosonNLP::builder()
+    ///         .token("token")
+    ///         .api_prefix("/v2/")
+    ///         .build()
+    ///         .unwrap();
+    /// }
+    /// ```
+    pub fn api_prefix<T: Into<String>>(mut self, api_prefix: T) -> BosonNLPBuilder {
+        self.api_prefix = Some(api_prefix.into());
+        self
+    }
+
+    /// 使用自定义的 reqwest Client
+    pub fn client(mut self, client: Client) -> BosonNLPBuilder {
+        self.client = Some(client);
+        self
+    }
+
+    /// 构建 `BosonNLP` 实例，未设置 ``token`` 或 HTTP 头不合法时返回 `Error::InvalidArgument`
+    pub fn build(self) -> Result<BosonNLP> {
+        let token = self.token.ok_or_else(|| Error::InvalidArgument {
+            name: "token".to_owned(),
+            message: "token is required".to_owned(),
+        })?;
+        let mut headers = HeaderMap::new();
+        for (name, value) in &self.headers {
+            let (name, value) = parse_header(name, value)?;
+            headers.append(name, value);
         }
+        let bosonnlp_url = self.url.unwrap_or_else(|| DEFAULT_BOSONNLP_URL.to_owned());
+        warn_insecure_url(&bosonnlp_url);
+        let client = match self.client {
+            Some(client) => client,
+            None => {
+                let mut builder = Client::builder();
+                #[cfg(feature = "rustls")]
+                {
+                    builder = builder.use_rustls_tls();
+                }
+                if let Some(timeout) = self.timeout {
+                    builder = builder.timeout(timeout);
+                }
+                if let Some((proxy_url, credentials)) = self.proxy {
+                    let mut proxy = Proxy::all(&proxy_url)?;
+                    if let Some((username, password)) = credentials {
+                        proxy = proxy.basic_auth(&username, &password);
+                    }
+                    builder = builder.proxy(proxy);
+                }
+                if let Some(timeout) = self.pool_idle_timeout {
+                    builder = builder.pool_idle_timeout(timeout);
+                }
+                if let Some(max_idle) = self.pool_max_idle_per_host {
+                    builder = builder.pool_max_idle_per_host(max_idle);
+                }
+                if self.http2_prior_knowledge {
+                    builder = builder.http2_prior_knowledge();
+                }
+                builder.build()?
+            }
+        };
+        Ok(BosonNLP {
+            token,
+            compress: self.compress.unwrap_or(true),
+            compress_threshold: 10240,
+            compress_level: DEFAULT_COMPRESS_LEVEL,
+            max_retries: 0,
+            retry_backoff: Duration::from_secs(1),
+            batch_size: 100,
+            allow_empty_contents: false,
+            poll_jitter: false,
+            jitter_seed: None,
+            request_timeout: None,
+            poll_request_timeout: Some(Duration::from_secs(30)),
+            max_response_bytes: DEFAULT_MAX_RESPONSE_BYTES,
+            headers,
+            user_agent: self.user_agent,
+            api_prefix: self.api_prefix,
+            bosonnlp_url,
+            client,
+            sleeper: Arc::new(ThreadSleeper),
+            on_request: None,
+            on_task: None,
+        })
     }
 }
 
 impl BosonNLP {
+    /// 创建一个 [`BosonNLPBuilder`](struct.BosonNLPBuilder.html)
+    pub fn builder() -> BosonNLPBuilder {
+        BosonNLPBuilder::new()
+    }
+
+    /// `BosonNLP` HTTP API 的 URL
+    pub fn url(&self) -> &str {
+        &self.bosonnlp_url
+    }
+
+    /// 发送请求使用的 reqwest Client，可用于在其它地方复用同一个连接池
+    ///
+    /// # 使用示例
+    ///
+    /// ```
+    /// extern crate bosonnlp;
+    /// extern crate reqwest;
+    ///
+    /// use reqwest::blocking::Client;
+    /// use reqwest::header::{HeaderMap, HeaderValue};
+    /// use bosonnlp::BosonNLP;
+    ///
+    /// fn main() {
+    ///     let mut headers = HeaderMap::new();
+    ///     headers.insert("X-Tenant", HeaderValue::from_static("tenant-42"));
+    ///     let client = Client::builder().default_headers(headers).build().unwrap();
+    ///     let nlp = BosonNLP::builder()
+    ///         .token("token")
+    ///         .compress(false)
+    ///         .client(client)
+    ///         .build()
+    ///         .unwrap();
+    ///     assert!(!nlp.compress);
+    ///     // 通过 client() 发送的请求同样带有注入的请求头
+    ///     let _req = nlp.client().get(nlp.url());
+    /// }
+    /// ```
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
+
     /// 初始化一个新的 `BosonNLP` 实例
     pub fn new<T: Into<String>>(token: T) -> BosonNLP {
-        BosonNLP {
-            token: token.into(),
-            ..Default::default()
-        }
+        BosonNLPBuilder::new()
+            .token(token)
+            .build()
+            .expect("failed to build BosonNLP")
     }
 
     /// 使用自定义参数初始化一个新的 ``BosonNLP`` 实例
     pub fn with_options<T: Into<String>>(token: T, bosonnlp_url: T, compress: bool) -> BosonNLP {
-        BosonNLP {
-            token: token.into(),
-            compress: compress,
-            bosonnlp_url: bosonnlp_url.into(),
-            ..Default::default()
-        }
+        BosonNLPBuilder::new()
+            .token(token)
+            .url(bosonnlp_url)
+            .compress(compress)
+            .build()
+            .expect("failed to build BosonNLP")
     }
 
     /// 使用自定义的 reqwest Client 初始化一个新的 ``BosonNLP`` 实例
+    ///
+    /// 其它选项使用默认值，需要同时设置 URL、压缩等选项时使用 [`BosonNLPBuilder::client`](struct.BosonNLPBuilder.html#method.client)
     pub fn with_client<T: Into<String>>(token: T, client: Client) -> BosonNLP {
+        BosonNLPBuilder::new()
+            .token(token)
+            .client(client)
+            .build()
+            .expect("failed to build BosonNLP")
+    }
+
+    /// 使用设置了 HTTP 请求超时时间的 reqwest Client 初始化一个新的 ``BosonNLP`` 实例
+    ///
+    /// ``timeout`` 是单次 HTTP 请求（从建立连接到读取完响应）的超时时间，
+    /// 超时后返回 `Error::RequestTimeout`。它与文本聚类、典型意见接口的 ``timeout`` 参数不同，
+    /// 后者是等待整个聚类任务完成的时间，超时后返回 `Error::Timeout`
+    pub fn with_timeout<T: Into<String>>(token: T, timeout: Duration) -> BosonNLP {
+        BosonNLPBuilder::new()
+            .token(token)
+            .timeout(timeout)
+            .build()
+            .expect("failed to build reqwest Client")
+    }
+
+    /// 使用 HTTP 代理初始化一个新的 ``BosonNLP`` 实例，所有请求都会通过代理发送
+    ///
+    /// ``proxy_url``: 代理服务器地址，如 ``http://proxy.example.com:8080``
+    ///
+    /// ``credentials``: 代理服务器需要认证时的用户名和密码
+    ///
+    /// # 使用示例
+    ///
+    /// ```
+    /// extern crate bosonnlp;
+    ///
+    /// use bosonnlp::{BosonNLP, Error};
+    ///
+    /// fn main() {
+    ///     // 本地没有监听 1 号端口，请求会因为代理连接被拒绝而失败
+    ///     let nlp = BosonNLP::with_proxy("token", "http://127.0.0.1:1", Some(("user", "password"))).unwrap();
+    ///     match nlp.classify(&["俄否决安理会谴责叙军战机空袭阿勒颇平民"]) {
+    ///         Err(Error::Http(ref err)) => assert!(err.is_connect()),
+    ///         _ => panic!("expected proxy connection error"),
+    ///     }
+    /// }
+    /// ```
+    pub fn with_proxy<T: Into<String>>(token: T, proxy_url: &str, credentials: Option<(&str, &str)>) -> Result<BosonNLP> {
+        BosonNLPBuilder::new()
+            .token(token)
+            .proxy(proxy_url, credentials)
+            .build()
+    }
+
+    /// 复制当前实例并替换 API Token，其它设置保持不变
+    ///
+    /// 复制出的实例与原实例共享同一个 reqwest Client 及其连接池，
+    /// 为多个用户代理请求时可以避免为每个 Token 重新创建 Client 和建立连接
+    ///
+    /// # 使用示例
+    ///
+    /// ```
+    /// extern crate bosonnlp;
+    ///
+    /// use bosonnlp::BosonNLP;
+    ///
+    /// fn main() {
+    ///     let nlp = BosonNLP::with_options("token", "http://127.0.0.1:8080", false);
+    ///     let tenant = nlp.clone_with_token("tenant-token");
+    ///     assert_eq!("tenant-token", tenant.token);
+    ///     assert_eq!("token", nlp.token);
+    ///     assert_eq!(nlp.url(), tenant.url());
+    ///     assert!(!tenant.compress);
+    /// }
+    /// ```
+    pub fn clone_with_token<T: Into<String>>(&self, token: T) -> BosonNLP {
         BosonNLP {
             token: token.into(),
-            client: client,
-            ..Default::default()
+            ..self.clone()
         }
     }
 
-    fn request<D, E>(&self, method: Method, endpoint: &str, params: Vec<(&str, &str)>, data: &E) -> Result<D>
-    where
-        D: DeserializeOwned,
-        E: Serialize,
-    {
-        let url_string = format!("{}{}", self.bosonnlp_url, endpoint);
-        let mut url = Url::parse(&url_string).unwrap();
-        url.query_pairs_mut().extend_pairs(params.into_iter());
-        let mut req = self.client.request(method.clone(), url);
-        req = req.header(
-                USER_AGENT,
-                format!("bosonnlp-rs/{}", env!("CARGO_PKG_VERSION")),
-            )
-            .header(ACCEPT, "application/json")
-            .header("X-Token", self.token.clone());
-        let mut res = if method == Method::POST {
-            let req = req.header(CONTENT_TYPE, "application/json");
-            let body = serde_json::to_vec(data)?;
-            if self.compress && body.len() > 10240 {
-                let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
-                encoder.write_all(&body)?;
-                let compressed = encoder.finish()?;
-                let req = req.header(CONTENT_ENCODING, "gzip");
-                req.body(compressed).send()?
-            } else {
-                req.body(body).send()?
-            }
-        } else {
-            req.send()?
-        };
-        let content_len = res.content_length().unwrap_or(0) as usize;
-        let mut body = String::with_capacity(content_len);
-        res.read_to_string(&mut body)?;
-        let status = res.status();
-        if !status.is_success() {
-            let result: Value = match serde_json::from_str(&body) {
-                Ok(obj) => obj,
-                Err(..) => Value::Object(Map::new()),
-            };
-            let message = match result.get("message") {
-                Some(msg) => msg.as_str().unwrap_or("").to_owned(),
-                None => body,
-            };
-            return Err(
-                Error::Api {
-                    code: status,
-                    reason: message
-                }
-            );
-        }
-        Ok(serde_json::from_str::<D>(&body)?)
+    /// 设置请求遇到临时性错误时的重试策略
+    ///
+    /// ``max_retries``: 最大重试次数
+    ///
+    /// ``backoff``: 首次重试前等待的时间，之后每次重试等待时间翻倍，最长 64 秒
+    ///
+    /// 只有 GET 请求和可以安全重复发送的分析接口请求会被重试，
+    /// 连接错误、超时、5xx 和 429 响应会触发重试，其它 4xx 响应不会重试。
+    /// 429 响应带有 `Retry-After` 时按照服务器建议的时间等待
+    pub fn with_retry(mut self, max_retries: usize, backoff: Duration) -> BosonNLP {
+        self.max_retries = max_retries;
+        self.retry_backoff = backoff;
+        self
     }
 
-    pub(crate) fn get<D>(&self, endpoint: &str, params: Vec<(&str, &str)>) -> Result<D>
-    where
-        D: DeserializeOwned,
-    {
-        self.request(Method::GET, endpoint, params, &Value::Null)
+    /// 设置压缩请求体的阈值，请求体超过 ``threshold`` 字节时进行压缩
+    pub fn with_compress_threshold(mut self, threshold: usize) -> BosonNLP {
+        self.compress_threshold = threshold;
+        self
     }
 
-    pub(crate) fn post<D, E>(&self, endpoint: &str, params: Vec<(&str, &str)>, data: &E) -> Result<D>
-    where
-        D: DeserializeOwned,
-        E: Serialize,
-    {
-        self.request(Method::POST, endpoint, params, data)
+    /// 设置 gzip 压缩级别，0-9，数值越大压缩率越高，CPU 消耗也越大，超过 9 时按 9 处理
+    pub fn with_compress_level(mut self, level: u32) -> BosonNLP {
+        self.compress_level = level.min(MAX_COMPRESS_LEVEL);
+        self
     }
 
-    /// [情感分析接口](http://docs.bosonnlp.com/sentiment.html)
-    ///
-    /// ``contents``: 需要做情感分析的文本序列
+    /// 设置批量接口单次请求最多发送的文本数量
+    pub fn with_batch_size(mut self, batch_size: usize) -> BosonNLP {
+        self.batch_size = batch_size;
+        self
+    }
+
+    /// 设置是否允许发送空文本，默认在发送前检查文本并返回第一个空文本的位置
+    pub fn with_allow_empty_contents(mut self, allow: bool) -> BosonNLP {
+        self.allow_empty_contents = allow;
+        self
+    }
+
+    /// 设置轮询任务状态时是否为等待时间添加随机抖动
     ///
-    /// ``model``: 使用不同的语料训练的模型
+    /// 启用后每次等待时间乘以 [0.5, 1.5) 之间的随机数，总等待时间仍不超过超时时间，
+    /// 避免大量同时提交的任务以相同的节奏轮询 API
+    pub fn with_poll_jitter(mut self, enabled: bool) -> BosonNLP {
+        self.poll_jitter = enabled;
+        self
+    }
+
+    /// 启用随机抖动并固定随机数种子，使等待时间可以复现，主要用于测试
     ///
     /// # 使用示例
     ///
@@ -154,50 +638,1244 @@ impl BosonNLP {
     /// use bosonnlp::BosonNLP;
     ///
     /// fn main() {
-    ///     let nlp = BosonNLP::new(env!("BOSON_API_TOKEN"));
-    ///     let rs = nlp.sentiment(&["这家味道还不错"], "food").unwrap();
+    ///     let nlp = BosonNLP::new("token").with_jitter_seed(42);
+    ///     assert!(nlp.poll_jitter);
+    /// }
+    /// ```
+    pub fn with_jitter_seed(mut self, seed: u64) -> BosonNLP {
+        self.poll_jitter = true;
+        self.jitter_seed = Some(seed);
+        self
+    }
+
+    /// 设置单次 HTTP 请求的超时时间，覆盖 Client 的超时设置，超时后返回 `Error::RequestTimeout`
+    ///
+    /// 克隆 `BosonNLP` 不会重新创建连接池，可以为个别调用使用不同的超时时间：
+    ///
+    /// ```no_run
+    /// extern crate bosonnlp;
+    ///
+    /// use std::time::Duration;
+    /// use bosonnlp::BosonNLP;
+    ///
+    /// fn main() {
+    ///     let nlp = BosonNLP::new(std::env::var("BOSON_API_TOKEN").unwrap());
+    ///     let quick = nlp.clone().with_request_timeout(Duration::from_secs(5));
+    ///     let rs = quick.sentiment(&["这家味道还不错"], "food").unwrap();
     ///     assert_eq!(1, rs.len());
     /// }
     /// ```
-    pub fn sentiment<T: AsRef<str>>(&self, contents: &[T], model: &str) -> Result<Vec<(f32, f32)>> {
-        let endpoint = format!("/sentiment/analysis?{}", model);
-        let data = contents.iter().map(|c| c.as_ref()).collect::<Vec<_>>();
-        self.post(&endpoint, vec![], &data)
+    pub fn with_request_timeout(mut self, timeout: Duration) -> BosonNLP {
+        self.request_timeout = Some(timeout);
+        self
     }
 
-    /// [时间转换接口](http://docs.bosonnlp.com/time.html)
+    /// 设置轮询任务状态请求的超时时间，传入 ``None`` 时使用 Client 的超时设置
     ///
-    /// ``content``: 需要做时间转换的文本
+    /// 等待聚类任务完成可能需要很长时间，但每次查询状态的请求应当很快返回，
+    /// 单独设置较短的超时时间可以避免某次查询卡住整个等待过程
+    pub fn with_poll_request_timeout(mut self, timeout: Option<Duration>) -> BosonNLP {
+        self.poll_request_timeout = timeout;
+        self
+    }
+
+    /// 设置响应内容大小上限，默认为 50 MB
     ///
-    /// ``basetime``: 时间描述时的基准时间戳。如果为 ``None`` ，使用服务器当前的GMT+8时间
+    /// 按解压后的字节数计算，超过时停止读取并返回 `Error::InvalidResponse`，
+    /// 避免异常的响应耗尽内存。以流的方式获取聚类结果时同样生效，聚类结果较大时可以适当调大
+    pub fn with_max_response_bytes(mut self, max_response_bytes: usize) -> BosonNLP {
+        self.max_response_bytes = max_response_bytes;
+        self
+    }
+
+    /// 轮询任务状态的退避策略
+    pub(crate) fn poll_backoff(&self, timeout: Option<u64>, poll_interval: Option<Duration>) -> Backoff {
+        let backoff = Backoff::new(timeout, poll_interval);
+        if !self.poll_jitter {
+            return backoff;
+        }
+        backoff.with_jitter(self.jitter_seed.unwrap_or_else(random_seed))
+    }
+
+    /// 设置请求重试和轮询任务状态之间的等待方式，默认为 [`ThreadSleeper`](struct.ThreadSleeper.html)
+    ///
+    /// # 使用示例
+    ///
+    /// 记录轮询任务状态时的等待时间，而不实际等待：
+    ///
+    /// ```
+    /// extern crate bosonnlp;
+    ///
+    /// use std::sync::{Arc, Mutex};
+    /// use std::time::Duration;
+    /// use bosonnlp::{BosonNLP, Sleeper};
+    ///
+    /// #[derive(Debug, Default)]
+    /// struct FakeSleeper(Mutex<Vec<Duration>>);
+    ///
+    /// impl Sleeper for FakeSleeper {
+    ///     fn sleep(&self, duration: Duration) {
+    ///         self.0.lock().unwrap().push(duration);
+    ///     }
+    /// }
+    ///
+    /// fn main() {
+    ///     let sleeper = Arc::new(FakeSleeper::default());
+    ///     let nlp = BosonNLP::new("token").with_sleeper(sleeper.clone());
+    /// }
+    /// ```
+    pub fn with_sleeper(mut self, sleeper: Arc<dyn Sleeper>) -> BosonNLP {
+        self.sleeper = sleeper;
+        self
+    }
+
+    /// 设置每次请求完成后调用的回调，可用于统计各接口的耗时和请求大小
+    ///
+    /// 请求失败时同样会调用，重试的每次请求都会单独调用一次。流式读取聚类结果的请求在收到响应头时调用
+    ///
+    /// # 使用示例
+    ///
+    /// ```
+    /// extern crate bosonnlp;
+    ///
+    /// use bosonnlp::{BosonNLP, RequestMetrics};
+    ///
+    /// fn main() {
+    ///     let nlp = BosonNLP::new("token").with_on_request(|m: &RequestMetrics| {
+    ///         println!("{} {} {:?} took {:?}", m.method, m.endpoint, m.status, m.duration);
+    ///     });
+    /// }
+    /// ```
+    pub fn with_on_request<F>(mut self, on_request: F) -> BosonNLP
+    where
+        F: Fn(&RequestMetrics) + Send + Sync + 'static,
+    {
+        self.on_request = Some(RequestHook(Arc::new(on_request)));
+        self
+    }
+
+    /// 设置文本聚类和典型意见接口提交任务前调用的回调，参数为任务类型（``cluster`` 或 ``comments``）和 task_id
+    ///
+    /// `BosonNLP` 没有列出服务器端任务的接口，任务失败、超时或设置了 ``keep_result`` 时，
+    /// 服务器端的任务数据需要调用方自行清空。可以通过该回调记录提交过的 task_id，
+    /// 之后使用 `clear_cluster_task` 或 `clear_comments_task` 清理。
+    /// 通过 `cluster_task` 等方法获取的任务句柄由调用方管理，不会触发该回调
+    ///
+    /// # 使用示例
+    ///
+    /// ```
+    /// extern crate bosonnlp;
+    ///
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// use bosonnlp::BosonNLP;
+    ///
+    /// fn main() {
+    ///     let tasks = Arc::new(Mutex::new(Vec::new()));
+    ///     let recorded = tasks.clone();
+    ///     let nlp = BosonNLP::new("token")
+    ///         .with_on_task(move |kind: &str, task_id: &str| recorded.lock().unwrap().push(format!("{}/{}", kind, task_id)));
+    /// }
+    /// ```
+    pub fn with_on_task<F>(mut self, on_task: F) -> BosonNLP
+    where
+        F: Fn(&str, &str) + Send + Sync + 'static,
+    {
+        self.on_task = Some(TaskHook(Arc::new(on_task)));
+        self
+    }
+
+    /// 调用 ``on_task`` 回调
+    fn task_submitted(&self, kind: &str, task_id: &str) {
+        if let Some(TaskHook(ref on_task)) = self.on_task {
+            on_task(kind, task_id);
+        }
+    }
+
+    fn request<D, E>(
+        &self,
+        method: Method,
+        endpoint: &str,
+        params: Vec<(&str, &str)>,
+        data: &E,
+        timeout: Option<Duration>,
+        compression: CompressionMode,
+    ) -> Result<D>
+    where
+        D: DeserializeOwned,
+        E: Serialize,
+    {
+        let url = build_url(&self.bosonnlp_url, self.api_prefix.as_deref(), endpoint, params);
+        let body = if method == Method::POST {
+            let body = serde_json::to_vec(data)?;
+            trace!("Request body: {}", truncate_for_log(&body));
+            if compression.should_compress(self.compress, self.compress_threshold, body.len()) {
+                Some(compress_body(body, self.compress_level)?)
+            } else {
+                Some((body, false))
+            }
+        } else {
+            None
+        };
+        self.retry(&method, endpoint, || self.send(method.clone(), endpoint, url.clone(), body.clone(), timeout))
+    }
+
+    /// 调用 ``send``，对可以安全重复发送的请求在遇到临时性错误时按 ``max_retries`` 重试
+    fn retry<T, F>(&self, method: &Method, endpoint: &str, send: F) -> Result<T>
+    where
+        F: Fn() -> Result<T>,
+    {
+        let max_retries = if is_idempotent(method, endpoint) { self.max_retries } else { 0 };
+        let mut retries = 0;
+        loop {
+            match send() {
+                Err(ref err) if retries < max_retries && err.is_retryable() => {
+                    let backoff = retry_delay(err, self.retry_backoff, retries);
+                    retries += 1;
+                    warn!(
+                        "Request to {} failed: {}, retrying in {:?} ({}/{})",
+                        endpoint,
+                        err,
+                        backoff,
+                        retries,
+                        max_retries
+                    );
+                    self.sleeper.sleep(backoff);
+                }
+                res => return res,
+            }
+        }
+    }
+
+    fn send<D>(&self, method: Method, endpoint: &str, url: Url, body: Option<(Vec<u8>, bool)>, timeout: Option<Duration>) -> Result<D>
+    where
+        D: DeserializeOwned,
+    {
+        let start = Instant::now();
+        let request_size = body.as_ref().map_or(0, |(body, _)| body.len());
+        let mut status = None;
+        let res = self.execute(method.clone(), url.clone(), body, timeout).and_then(|mut res| {
+            status = Some(res.status());
+            let content_len = res.content_length().unwrap_or(0) as usize;
+            let headers = res.headers().clone();
+            let body = read_body(&mut res, is_gzipped(&headers), content_len, self.max_response_bytes)?;
+            Ok((res.status(), headers, body))
+        });
+        let response_size = res.as_ref().map_or(0, |(_, _, body)| body.len());
+        self.request_completed(endpoint, &method, status, start, request_size, response_size);
+        let (status, headers, body) = res?;
+        debug!("{} {} responded {}, body {} bytes", method, url, status, body.len());
+        trace!("Response body: {}", truncate_for_log(body.as_bytes()));
+        handle_response(status, &headers, body)
+    }
+
+    /// 调用 ``on_request`` 回调
+    fn request_completed(&self, endpoint: &str, method: &Method, status: Option<StatusCode>, start: Instant, request_size: usize, response_size: usize) {
+        if let Some(RequestHook(ref on_request)) = self.on_request {
+            on_request(&RequestMetrics {
+                endpoint: endpoint.to_owned(),
+                method: method.clone(),
+                status,
+                duration: start.elapsed(),
+                request_size,
+                response_size,
+            });
+        }
+    }
+
+    /// 发送请求，返回尚未读取响应内容的 `Response`，``timeout`` 覆盖 Client 的超时设置
+    fn execute(&self, method: Method, url: Url, body: Option<(Vec<u8>, bool)>, timeout: Option<Duration>) -> Result<Response> {
+        debug!(
+            "{} {}, body {} bytes{}, extra headers [{}]",
+            method,
+            url,
+            body.as_ref().map_or(0, |(body, _)| body.len()),
+            if body.as_ref().is_some_and(|(_, compressed)| *compressed) { " (gzip)" } else { "" },
+            redact_headers(&self.headers)
+        );
+        let mut req = self.client.request(method.clone(), url.clone())
+            .header("X-Token", self.token.clone())
+            .headers(self.request_headers()?);
+        if let Some(timeout) = timeout {
+            req = req.timeout(timeout);
+        }
+        let res = match body {
+            Some((body, compressed)) => {
+                let mut req = req.header(CONTENT_TYPE, "application/json");
+                if compressed {
+                    req = req.header(CONTENT_ENCODING, "gzip");
+                }
+                req.body(body).send()?
+            }
+            None => req.send()?,
+        };
+        Ok(res)
+    }
+
+    /// 除 API Token 以外的请求头，``headers`` 中的同名请求头会覆盖默认值
+    fn request_headers(&self) -> Result<HeaderMap> {
+        let mut headers = HeaderMap::new();
+        let agent = user_agent(self.user_agent.as_deref());
+        let agent = HeaderValue::from_str(&agent).map_err(|_| Error::InvalidArgument {
+            name: "user_agent".to_owned(),
+            message: format!("invalid header value {:?}", agent),
+        })?;
+        headers.insert(USER_AGENT, agent);
+        headers.insert(ACCEPT, HeaderValue::from_static("application/json"));
+        if cfg!(feature = "compression") {
+            headers.insert(ACCEPT_ENCODING, HeaderValue::from_static("gzip"));
+        }
+        for name in self.headers.keys() {
+            headers.remove(name);
+        }
+        for (name, value) in self.headers.iter() {
+            headers.append(name, value.clone());
+        }
+        Ok(headers)
+    }
+
+    /// 组装请求但不发送，``data`` 为 None 时表示没有请求体
+    fn prepare<E: Serialize>(
+        &self,
+        method: Method,
+        endpoint: &str,
+        params: Vec<(&str, &str)>,
+        data: Option<&E>,
+        compression: CompressionMode,
+    ) -> Result<PreparedRequest> {
+        let url = build_url(&self.bosonnlp_url, self.api_prefix.as_deref(), endpoint, params);
+        let body = match data {
+            Some(data) => Some(serde_json::to_string(data)?),
+            None => None,
+        };
+        let mut headers = self.request_headers()?;
+        let compressed = cfg!(feature = "compression")
+            && body.as_ref().is_some_and(|body| compression.should_compress(self.compress, self.compress_threshold, body.len()));
+        if body.is_some() {
+            headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        }
+        if compressed {
+            headers.insert(CONTENT_ENCODING, HeaderValue::from_static("gzip"));
+        }
+        Ok(PreparedRequest { method, url, headers, body, compressed })
+    }
+
+    /// 发送 GET 请求，成功时返回解压后的响应内容流，不会将响应内容一次性读入内存
+    pub(crate) fn get_reader(&self, endpoint: &str, params: Vec<(&str, &str)>) -> Result<Box<dyn Read + Send>> {
+        let url = build_url(&self.bosonnlp_url, self.api_prefix.as_deref(), endpoint, params);
+        let res = self.retry(&Method::GET, endpoint, || {
+            let start = Instant::now();
+            let mut res = match self.execute(Method::GET, url.clone(), None, self.request_timeout) {
+                Ok(res) => res,
+                Err(err) => {
+                    self.request_completed(endpoint, &Method::GET, None, start, 0, 0);
+                    return Err(err);
+                }
+            };
+            let status = res.status();
+            debug!("GET {} responded {}, streaming body", url, status);
+            if !status.is_success() {
+                let headers = res.headers().clone();
+                let body = read_body(&mut res, is_gzipped(&headers), 0, self.max_response_bytes);
+                let response_size = body.as_ref().map_or(0, |body| body.len());
+                self.request_completed(endpoint, &Method::GET, Some(status), start, 0, response_size);
+                let body = body?;
+                trace!("Response body: {}", truncate_for_log(body.as_bytes()));
+                return Err(Error::from_response(status, &headers, body));
+            }
+            self.request_completed(endpoint, &Method::GET, Some(status), start, 0, 0);
+            Ok(res)
+        })?;
+        if is_gzipped(res.headers()) {
+            #[cfg(feature = "compression")]
+            return Ok(Box::new(LimitedReader::new(GzDecoder::new(res), self.max_response_bytes)));
+            #[cfg(not(feature = "compression"))]
+            return Err(Error::InvalidResponse(
+                "received a gzip response but the `compression` feature is disabled".to_owned(),
+            ));
+        }
+        Ok(Box::new(LimitedReader::new(res, self.max_response_bytes)))
+    }
+
+    pub(crate) fn get<D>(&self, endpoint: &str, params: Vec<(&str, &str)>) -> Result<D>
+    where
+        D: DeserializeOwned,
+    {
+        self.request(Method::GET, endpoint, params, &Value::Null, self.request_timeout, CompressionMode::Auto)
+    }
+
+    /// 使用轮询任务状态的超时时间发送 GET 请求
+    pub(crate) fn get_status<D>(&self, endpoint: &str) -> Result<D>
+    where
+        D: DeserializeOwned,
+    {
+        self.request(Method::GET, endpoint, vec![], &Value::Null, self.poll_request_timeout, CompressionMode::Auto)
+    }
+
+    pub(crate) fn post<D, E>(&self, endpoint: &str, params: Vec<(&str, &str)>, data: &E) -> Result<D>
+    where
+        D: DeserializeOwned,
+        E: Serialize,
+    {
+        self.post_with(endpoint, params, data, CompressionMode::Auto)
+    }
+
+    /// 按 ``compression`` 指定的方式压缩请求体并发送 POST 请求
+    pub(crate) fn post_with<D, E>(&self, endpoint: &str, params: Vec<(&str, &str)>, data: &E, compression: CompressionMode) -> Result<D>
+    where
+        D: DeserializeOwned,
+        E: Serialize,
+    {
+        self.request(Method::POST, endpoint, params, data, self.request_timeout, compression)
+    }
+
+    /// 直接调用 ``endpoint`` 对应的 POST 接口，返回未经类型转换的 JSON 响应
+    ///
+    /// 用于调用 SDK 尚未封装的接口，请求同样会带上鉴权头并按配置压缩请求体、处理错误响应，
+    /// 但不会校验响应内容的结构，需要调用方自行解析
+    ///
+    /// # 使用示例
+    ///
+    /// ```no_run
+    /// extern crate bosonnlp;
+    /// extern crate serde_json;
+    ///
+    /// use bosonnlp::BosonNLP;
+    ///
+    /// fn main() {
+    ///     let nlp = BosonNLP::new(std::env::var("BOSON_API_TOKEN").unwrap());
+    ///     let data = serde_json::json!(["俄否决安理会谴责叙军战机空袭阿勒颇平民"]);
+    ///     let rs = nlp.raw_post("/classify/analysis", vec![], &data).unwrap();
+    ///     assert_eq!(serde_json::json!([5]), rs);
+    /// }
+    /// ```
+    pub fn raw_post(&self, endpoint: &str, params: Vec<(&str, &str)>, body: &Value) -> Result<Value> {
+        self.post(endpoint, params, body)
+    }
+
+    /// 与 `raw_post` 相同，但由 ``compression`` 决定本次请求是否压缩请求体，
+    /// 不受 ``compress`` 和 ``compress_threshold`` 设置的影响（`CompressionMode::Auto` 除外）
+    ///
+    /// # 使用示例
+    ///
+    /// ```
+    /// extern crate bosonnlp;
+    /// extern crate serde_json;
+    ///
+    /// use bosonnlp::{BosonNLP, CompressionMode};
+    ///
+    /// fn main() {
+    ///     let nlp = BosonNLP::new("token");
+    ///     let data = serde_json::json!(["俄否决安理会谴责叙军战机空袭阿勒颇平民"]);
+    ///     let req = nlp.preview_post("/classify/analysis", vec![], &data, CompressionMode::Always).unwrap();
+    ///     assert_eq!(cfg!(feature = "compression"), req.compressed);
+    /// }
+    /// ```
+    pub fn raw_post_with(&self, endpoint: &str, params: Vec<(&str, &str)>, body: &Value, compression: CompressionMode) -> Result<Value> {
+        self.post_with(endpoint, params, body, compression)
+    }
+
+    /// 直接调用 ``endpoint`` 对应的 GET 接口，返回未经类型转换的 JSON 响应
+    ///
+    /// 与 `raw_post` 相同，不会校验响应内容的结构
+    pub fn raw_get(&self, endpoint: &str, params: Vec<(&str, &str)>) -> Result<Value> {
+        self.get(endpoint, params)
+    }
+
+    /// 组装 `raw_post_with` 将要发送的请求但不发送，不会访问网络
+    ///
+    /// 可用于调试或查看 SDK 实际发送的 URL、请求头和请求体，返回的请求头不包含 API Token
+    ///
+    /// # 使用示例
+    ///
+    /// ```
+    /// extern crate bosonnlp;
+    /// extern crate serde_json;
+    ///
+    /// use bosonnlp::{BosonNLP, CompressionMode};
+    ///
+    /// fn main() {
+    ///     let nlp = BosonNLP::new("token");
+    ///     let data = serde_json::json!(["这家味道还不错"]);
+    ///     let req = nlp.preview_post("/sentiment/analysis", vec![("food", "")], &data, CompressionMode::Auto).unwrap();
+    ///     assert_eq!("POST", req.method.as_str());
+    ///     assert_eq!("https://api.bosonnlp.com/sentiment/analysis?food", req.url.as_str());
+    ///     assert_eq!(Some(r#"["这家味道还不错"]"#.to_owned()), req.body);
+    ///     assert!(req.headers.get("X-Token").is_none());
+    /// }
+    /// ```
+    pub fn preview_post(&self, endpoint: &str, params: Vec<(&str, &str)>, body: &Value, compression: CompressionMode) -> Result<PreparedRequest> {
+        self.prepare(Method::POST, endpoint, params, Some(body), compression)
+    }
+
+    /// 组装 `raw_get` 将要发送的请求但不发送，不会访问网络
+    pub fn preview_get(&self, endpoint: &str, params: Vec<(&str, &str)>) -> Result<PreparedRequest> {
+        self.prepare::<Value>(Method::GET, endpoint, params, None, CompressionMode::Auto)
+    }
+
+    /// 调用请求体为单个 JSON 字符串的 POST 接口，如语义联想和关键词提取接口
+    ///
+    /// ``text`` 会被编码为带引号的 JSON 字符串发送，响应解析为 ``D``，可以使用 `serde_json::Value`
+    pub fn post_text<D: DeserializeOwned>(&self, endpoint: &str, params: Vec<(&str, &str)>, text: &str) -> Result<D> {
+        self.post(endpoint, params, &text)
+    }
+
+    /// 检查 API Token 是否有效以及服务是否可以访问
+    ///
+    /// 请求 ``/application/rate_limit_status.json`` 接口，该接口只返回调用频率限制状态，不消耗调用次数。
+    /// Token 无效（401 或 403 响应）时返回 ``Ok(false)``，网络错误等其它错误返回 ``Err``
+    ///
+    /// # 使用示例
+    ///
+    /// ```no_run
+    /// extern crate bosonnlp;
+    ///
+    /// use bosonnlp::BosonNLP;
+    ///
+    /// fn main() {
+    ///     let nlp = BosonNLP::new(std::env::var("BOSON_API_TOKEN").unwrap());
+    ///     if !nlp.verify_token().unwrap() {
+    ///         eprintln!("invalid API token");
+    ///     }
+    /// }
+    /// ```
+    pub fn verify_token(&self) -> Result<bool> {
+        match self.get::<Value>("/application/rate_limit_status.json", vec![]) {
+            Ok(_) => Ok(true),
+            Err(Error::Api { code, .. }) if code == StatusCode::UNAUTHORIZED || code == StatusCode::FORBIDDEN => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// 查询各接口的调用额度使用情况
+    ///
+    /// 请求 ``/application/rate_limit_status.json`` 接口，不消耗调用次数，可用于批量任务中控制调用速度
+    ///
+    /// # 使用示例
+    ///
+    /// ```no_run
+    /// extern crate bosonnlp;
+    ///
+    /// use bosonnlp::BosonNLP;
+    ///
+    /// fn main() {
+    ///     let nlp = BosonNLP::new(std::env::var("BOSON_API_TOKEN").unwrap());
+    ///     let usage = nlp.usage().unwrap();
+    ///     if let Some(sentiment) = usage.get("sentiment") {
+    ///         println!("sentiment: {}/{} calls used", sentiment.calls_used(), sentiment.calls_limit);
+    ///     }
+    /// }
+    /// ```
+    pub fn usage(&self) -> Result<Usage> {
+        self.get("/application/rate_limit_status.json", vec![])
+    }
+
+    /// 未设置 ``allow_empty_contents`` 时检查 ``contents`` 中是否有空文本
+    fn check_contents<T: AsRef<str>>(&self, contents: &[T]) -> Result<()> {
+        if self.allow_empty_contents {
+            return Ok(());
+        }
+        validate_contents(contents)
+    }
+
+    /// 将 ``contents`` 按照 ``batch_size`` 拆分为多次请求发送，并按原顺序合并结果
+    pub(crate) fn post_batch<T, D>(&self, endpoint: &str, params: Vec<(&str, &str)>, contents: &[T]) -> Result<Vec<D>>
+    where
+        T: AsRef<str>,
+        D: DeserializeOwned,
+    {
+        self.check_contents(contents)?;
+        let mut result = Vec::with_capacity(contents.len());
+        for parts in contents.chunks(self.batch_size.max(1)) {
+            let data = parts.iter().map(|c| c.as_ref()).collect::<Vec<_>>();
+            let rs: Vec<D> = self.post(endpoint, params.clone(), &data)?;
+            result.extend(rs);
+        }
+        Ok(result)
+    }
+
+    /// 将 ``contents`` 按 ``batch_size`` 拆分后，使用最多 ``concurrency`` 个线程并发调用批量接口，
+    /// 按输入顺序合并结果。任一批次失败时不再发送剩余的批次，并返回按输入顺序第一个失败批次的错误
+    pub(crate) fn par_batch<T, D>(&self, endpoint: &str, params: Vec<(&str, &str)>, contents: &[T], concurrency: usize) -> Result<Vec<D>>
+    where
+        T: AsRef<str> + Sync,
+        D: DeserializeOwned + Send,
+    {
+        self.check_contents(contents)?;
+        let chunks: Vec<&[T]> = contents.chunks(self.batch_size.max(1)).collect();
+        let results = par_map(&chunks, concurrency, |chunk| {
+            let data = chunk.iter().map(|c| c.as_ref()).collect::<Vec<_>>();
+            self.post::<Vec<D>, _>(endpoint, params.clone(), &data)
+        })?;
+        Ok(results.into_iter().flatten().collect())
+    }
+
+    /// [情感分析接口](http://docs.bosonnlp.com/sentiment.html)
+    ///
+    /// ``contents``: 需要做情感分析的文本序列
+    ///
+    /// ``model``: 使用不同的语料训练的模型，可以传入 [`SentimentModel`](enum.SentimentModel.html) 或模型名称字符串
+    ///
+    /// # 使用示例
+    ///
+    /// ```
+    /// extern crate bosonnlp;
+    ///
+    /// use bosonnlp::{BosonNLP, SentimentLabel, SentimentModel};
+    ///
+    /// fn main() {
+    ///     let nlp = BosonNLP::new(env!("BOSON_API_TOKEN"));
+    ///     let rs = nlp.sentiment(&["这家味道还不错"], SentimentModel::Food).unwrap();
+    ///     assert_eq!(1, rs.len());
+    ///     assert_eq!(SentimentLabel::Positive, rs[0].label());
+    ///     let rs = nlp.sentiment(&["这家味道还不错"], "food").unwrap();
+    ///     assert_eq!(1, rs.len());
+    /// }
+    /// ```
+    ///
+    /// 批量接口会因为单个空文本拒绝整批请求，``contents`` 中包含空文本时直接返回
+    /// `Error::InvalidArgument` 并指出其位置，不会发送请求。
+    /// [`ner`](#method.ner)、[`tag`](#method.tag) 等其他批量接口同样如此
+    ///
+    /// ```
+    /// extern crate bosonnlp;
+    ///
+    /// use bosonnlp::{BosonNLP, Error};
+    ///
+    /// fn main() {
+    ///     // 本地没有监听 1 号端口，如果发送了请求会返回连接错误
+    ///     let nlp = BosonNLP::with_options("token", "http://127.0.0.1:1", false);
+    ///     match nlp.sentiment(&["这家味道还不错", "", "服务很好"], "food") {
+    ///         Err(Error::InvalidArgument { ref name, ref message }) => {
+    ///             assert_eq!("contents", name);
+    ///             assert!(message.contains("index 1"));
+    ///         }
+    ///         _ => panic!("expected an invalid argument error"),
+    ///     }
+    ///     assert!(matches!(nlp.ner(&["成都商报记者 姚永忠", "  "], 2, false), Err(Error::InvalidArgument { .. })));
+    /// }
+    /// ```
+    ///
+    /// 模型名称作为查询参数发送，如 ``/sentiment/analysis?food``，自定义模型的名称会经过 URL 编码
+    ///
+    /// ```no_run
+    /// extern crate bosonnlp;
+    ///
+    /// use bosonnlp::{BosonNLP, SentimentModel};
+    ///
+    /// fn main() {
+    ///     let nlp = BosonNLP::new(std::env::var("BOSON_API_TOKEN").unwrap());
+    ///     nlp.sentiment(&["这家味道还不错"], SentimentModel::Food).unwrap();
+    ///     // 自定义模型名称编码后作为查询参数发送
+    ///     nlp.sentiment(&["这家味道还不错"], "my model&x=1").unwrap();
+    /// }
+    /// ```
+    pub fn sentiment<T, M>(&self, contents: &[T], model: M) -> Result<Vec<Sentiment>>
+    where
+        T: AsRef<str>,
+        M: Into<SentimentModel>,
+    {
+        let model = model.into();
+        self.post_batch("/sentiment/analysis", vec![(model.as_ref(), "")], contents)
+    }
+
+    /// 对单个文本调用[情感分析接口](http://docs.bosonnlp.com/sentiment.html)
+    pub fn sentiment_one<T, M>(&self, content: T, model: M) -> Result<Sentiment>
+    where
+        T: AsRef<str>,
+        M: Into<SentimentModel>,
+    {
+        single(self.sentiment(&[content], model)?)
+    }
+
+    /// 组装 `sentiment` 将要发送的请求但不发送，``contents`` 不会按 ``batch_size`` 拆分
+    pub fn preview_sentiment<T, M>(&self, contents: &[T], model: M) -> Result<PreparedRequest>
+    where
+        T: AsRef<str>,
+        M: Into<SentimentModel>,
+    {
+        self.check_contents(contents)?;
+        let model = model.into();
+        let data = contents.iter().map(|c| c.as_ref()).collect::<Vec<_>>();
+        self.prepare(Method::POST, "/sentiment/analysis", vec![(model.as_ref(), "")], Some(&data), CompressionMode::Auto)
+    }
+
+    /// 使用多个线程并发调用[情感分析接口](http://docs.bosonnlp.com/sentiment.html)，适用于大量文本
+    ///
+    /// ``contents`` 会按 ``batch_size`` 拆分为多个请求，最多同时发送 ``concurrency`` 个请求，
+    /// 返回结果与输入顺序一致。任一请求失败时返回该错误
+    ///
+    /// # 使用示例
+    ///
+    /// ```no_run
+    /// extern crate bosonnlp;
+    ///
+    /// use bosonnlp::BosonNLP;
+    ///
+    /// fn main() {
+    ///     let nlp = BosonNLP::new(std::env::var("BOSON_API_TOKEN").unwrap()).with_batch_size(50);
+    ///     let contents: Vec<String> = (0..1000).map(|i| format!("第 {} 条评论", i)).collect();
+    ///     let rs = nlp.sentiment_parallel(&contents, "general", 4).unwrap();
+    ///     assert_eq!(1000, rs.len());
+    /// }
+    /// ```
+    pub fn sentiment_parallel<T, M>(&self, contents: &[T], model: M, concurrency: usize) -> Result<Vec<Sentiment>>
+    where
+        T: AsRef<str> + Sync,
+        M: Into<SentimentModel>,
+    {
+        let model = model.into();
+        self.par_batch("/sentiment/analysis", vec![(model.as_ref(), "")], contents, concurrency)
+    }
+
+    /// [时间转换接口](http://docs.bosonnlp.com/time.html)
+    ///
+    /// ``content``: 需要做时间转换的文本
+    ///
+    /// ``basetime``: 时间描述时的基准时间戳。如果为 ``None`` ，使用服务器当前的GMT+8时间
+    ///
+    /// # 使用示例
+    ///
+    /// ```
+    /// extern crate bosonnlp;
+    ///
+    /// use bosonnlp::BosonNLP;
+    ///
+    /// fn main() {
+    ///     let nlp = BosonNLP::new(env!("BOSON_API_TOKEN"));
+    ///     let time = nlp.convert_time("2013年二月二十八日下午四点三十分二十九秒", None).unwrap();
+    ///     assert_eq!("2013-02-28 16:30:29", &time.timestamp.unwrap());
+    ///     assert_eq!("timestamp", &time.format);
+    /// }
+    /// ```
+    pub fn convert_time<T: AsRef<str>>(&self, content: T, basetime: Option<T>) -> Result<ConvertedTime> {
+        if let Some(base) = basetime {
+            validate_basetime(base.as_ref())?;
+            let params = vec![("pattern", content.as_ref()), ("basetime", base.as_ref())];
+            self.post("/time/analysis", params, &Value::Null)
+        } else {
+            let params = vec![("pattern", content.as_ref())];
+            self.post("/time/analysis", params, &Value::Null)
+        }
+    }
+
+    /// 使用多个线程并发调用[时间转换接口](http://docs.bosonnlp.com/time.html)，转换多个时间描述
+    ///
+    /// 时间转换接口每次只处理一个时间描述，``contents`` 中的每一项会单独发送一个请求并使用同一个 ``basetime``，
+    /// 最多同时发送 ``concurrency`` 个请求，返回结果与输入顺序一致。任一请求失败时返回按输入顺序第一个失败的错误
+    ///
+    /// # 使用示例
+    ///
+    /// ```no_run
+    /// extern crate bosonnlp;
+    ///
+    /// use bosonnlp::BosonNLP;
+    ///
+    /// fn main() {
+    ///     let nlp = BosonNLP::new(std::env::var("BOSON_API_TOKEN").unwrap());
+    ///     let rs = nlp.convert_time_batch(&["今天", "明天下午三点", "下周一"], Some("2013-02-28 16:30:29"), 4).unwrap();
+    ///     assert_eq!(3, rs.len());
+    /// }
+    /// ```
+    pub fn convert_time_batch<T>(&self, contents: &[T], basetime: Option<&str>, concurrency: usize) -> Result<Vec<ConvertedTime>>
+    where
+        T: AsRef<str> + Sync,
+    {
+        self.check_contents(contents)?;
+        if let Some(base) = basetime {
+            validate_basetime(base)?;
+        }
+        par_map(contents, concurrency, |content| self.convert_time(content.as_ref(), basetime))
+    }
+
+    /// 以 ``basetime`` 为基准时间调用[时间转换接口](http://docs.bosonnlp.com/time.html)，需要启用 `chrono` feature
+    ///
+    /// ``basetime`` 会被转换为服务器使用的 GMT+8 时间
+    ///
+    /// # 使用示例
+    ///
+    /// ```no_run
+    /// extern crate bosonnlp;
+    /// extern crate chrono;
+    ///
+    /// use chrono::{TimeZone, Utc};
+    /// use bosonnlp::BosonNLP;
+    ///
+    /// fn main() {
+    ///     let nlp = BosonNLP::new(std::env::var("BOSON_API_TOKEN").unwrap());
+    ///     let basetime = Utc.with_ymd_and_hms(2013, 2, 28, 8, 30, 29).unwrap();
+    ///     let time = nlp.convert_time_at("明天下午三点", basetime).unwrap();
+    ///     assert_eq!("timestamp", time.format);
+    /// }
+    /// ```
+    #[cfg(feature = "chrono")]
+    pub fn convert_time_at<T, Tz>(&self, content: T, basetime: chrono::DateTime<Tz>) -> Result<ConvertedTime>
+    where
+        T: AsRef<str>,
+        Tz: chrono::TimeZone,
+    {
+        let basetime = format_basetime(&basetime);
+        self.convert_time(content.as_ref(), Some(basetime.as_str()))
+    }
+
+    /// 以 Unix 时间戳 ``timestamp``（秒）为基准时间调用[时间转换接口](http://docs.bosonnlp.com/time.html)，
+    /// 需要启用 `chrono` feature
+    #[cfg(feature = "chrono")]
+    pub fn convert_time_at_timestamp<T: AsRef<str>>(&self, content: T, timestamp: i64) -> Result<ConvertedTime> {
+        let basetime = format_basetime_timestamp(timestamp)?;
+        self.convert_time(content.as_ref(), Some(basetime.as_str()))
+    }
+
+    /// [新闻分类接口](http://docs.bosonnlp.com/classify.html)
+    ///
+    /// ``contents``: 需要做分类的新闻文本序列
+    ///
+    /// # 使用示例
+    ///
+    /// ```
+    /// extern crate bosonnlp;
+    ///
+    /// use bosonnlp::BosonNLP;
+    ///
+    /// fn main() {
+    ///     let nlp = BosonNLP::new(env!("BOSON_API_TOKEN"));
+    ///     let rs = nlp.classify(&["俄否决安理会谴责叙军战机空袭阿勒颇平民"]).unwrap();
+    ///     assert_eq!(vec![5usize], rs);
+    /// }
+    /// ```
+    pub fn classify<T: AsRef<str>>(&self, contents: &[T]) -> Result<Vec<usize>> {
+        self.post_batch("/classify/analysis", vec![], contents)
+    }
+
+    /// 对单个文本调用[新闻分类接口](http://docs.bosonnlp.com/classify.html)
+    pub fn classify_one<T: AsRef<str>>(&self, content: T) -> Result<usize> {
+        single(self.classify(&[content])?)
+    }
+
+    /// [新闻分类接口](http://docs.bosonnlp.com/classify.html)，返回 [`NewsCategory`](enum.NewsCategory.html) 分类
+    ///
+    /// ``contents``: 需要做分类的新闻文本序列
+    ///
+    /// # 使用示例
+    ///
+    /// ```no_run
+    /// extern crate bosonnlp;
+    ///
+    /// use bosonnlp::{BosonNLP, NewsCategory};
+    ///
+    /// fn main() {
+    ///     let nlp = BosonNLP::new(std::env::var("BOSON_API_TOKEN").unwrap());
+    ///     let rs = nlp.classify_labeled(&["俄否决安理会谴责叙军战机空袭阿勒颇平民"]).unwrap();
+    ///     assert_eq!(vec![NewsCategory::Military], rs);
+    /// }
+    /// ```
+    pub fn classify_labeled<T: AsRef<str>>(&self, contents: &[T]) -> Result<Vec<NewsCategory>> {
+        let rs = self.classify(contents)?;
+        Ok(rs.into_iter().map(NewsCategory::from).collect())
+    }
+
+    /// [语义联想接口](http://docs.bosonnlp.com/suggest.html)
+    ///
+    /// ``word``: 需要做语义联想的词
+    ///
+    /// ``top_k``: 返回结果的条数，1-100 有效
+    ///
+    /// # 使用示例
+    ///
+    /// ```
+    /// extern crate bosonnlp;
+    ///
+    /// use bosonnlp::BosonNLP;
+    ///
+    /// fn main() {
+    ///     let nlp = BosonNLP::new(env!("BOSON_API_TOKEN"));
+    ///     let rs = nlp.suggest("北京", 2).unwrap();
+    ///     assert_eq!(2, rs.len());
+    ///     assert!(!rs[0].word.is_empty());
+    /// }
+    /// ```
+    ///
+    /// ``top_k`` 超出范围时直接返回错误，不会发送请求:
+    ///
+    /// ```
+    /// extern crate bosonnlp;
+    ///
+    /// use bosonnlp::{BosonNLP, Error};
+    ///
+    /// fn main() {
+    ///     let nlp = BosonNLP::new("");
+    ///     match nlp.suggest("北京", 0) {
+    ///         Err(Error::InvalidArgument { name, .. }) => assert_eq!("top_k", name),
+    ///         _ => panic!("expected InvalidArgument"),
+    ///     }
+    ///     assert!(nlp.keywords("病毒式媒体网站：让新闻迅速蔓延", 101, false).is_err());
+    /// }
+    /// ```
+    pub fn suggest<T: AsRef<str>>(&self, word: T, top_k: usize) -> Result<Vec<ScoredWord>> {
+        validate_top_k(top_k)?;
+        self.post_text("/suggest/analysis", vec![("top_k", &top_k.to_string())], word.as_ref())
+    }
+
+    /// [关键词提取接口](http://docs.bosonnlp.com/keywords.html)
+    ///
+    /// ``text``: 需要做关键词提取的文本
+    ///
+    /// ``top_k``: 返回结果的条数，1-100 有效
+    ///
+    /// ``segmented``: `text` 是否已经进行了分词，若为 `true` 则不会再对内容进行分词处理
+    ///
+    /// # 使用示例
+    ///
+    /// ```
+    /// extern crate bosonnlp;
+    ///
+    /// use bosonnlp::BosonNLP;
+    ///
+    /// fn main() {
+    ///     let nlp = BosonNLP::new(env!("BOSON_API_TOKEN"));
+    ///     let rs = nlp.keywords("病毒式媒体网站：让新闻迅速蔓延", 2, false).unwrap();
+    ///     assert_eq!(2, rs.len());
+    ///     let (score, word): (f32, String) = rs[0].clone().into();
+    ///     assert_eq!(rs[0].score, score);
+    ///     assert_eq!(rs[0].word, word);
+    /// }
+    /// ```
+    pub fn keywords<T: AsRef<str>>(&self, text: T, top_k: usize, segmented: bool) -> Result<Vec<ScoredWord>> {
+        self.keywords_with(text, &KeywordsOptions::default().top_k(top_k).segmented(segmented))
+    }
+
+    /// 使用 [`KeywordsOptions`](struct.KeywordsOptions.html) 调用[关键词提取接口](http://docs.bosonnlp.com/keywords.html)
+    ///
+    /// 关键词提取接口不支持繁体转简体，开启 ``t2s`` 时先通过分词与词性标注接口转换，
+    /// 参见 [`ner_with`](#method.ner_with)
+    pub fn keywords_with<T: AsRef<str>>(&self, text: T, options: &KeywordsOptions) -> Result<Vec<ScoredWord>> {
+        validate_top_k(options.top_k)?;
+        self.check_contents(&[text.as_ref()])?;
+        let top_k_str = options.top_k.to_string();
+        let mut params = vec![("top_k", top_k_str.as_ref())];
+        if options.segmented || options.t2s {
+            params.push(("segmented", "1"));
+        }
+        if options.t2s {
+            let words = self.simplified_words(&[text])?;
+            return self.post_text("/keywords/analysis", params, &words[0]);
+        }
+        self.post_text("/keywords/analysis", params, text.as_ref())
+    }
+
+    /// 使用已分词的输入调用[关键词提取接口](http://docs.bosonnlp.com/keywords.html)
+    ///
+    /// ``words``: 分词结果，发送时以空格连接，词为空或包含空白字符时返回 `Error::InvalidArgument`
+    ///
+    /// ``top_k``: 返回结果的条数，1-100 有效
+    ///
+    /// # 使用示例
+    ///
+    /// ```no_run
+    /// extern crate bosonnlp;
+    ///
+    /// use bosonnlp::BosonNLP;
+    ///
+    /// fn main() {
+    ///     let nlp = BosonNLP::new(std::env::var("BOSON_API_TOKEN").unwrap());
+    ///     let rs = nlp.keywords_segmented(&["病毒式", "媒体", "网站"], 1).unwrap();
+    ///     assert_eq!(1, rs.len());
+    /// }
+    /// ```
+    pub fn keywords_segmented<W: AsRef<str>>(&self, words: &[W], top_k: usize) -> Result<Vec<ScoredWord>> {
+        let text = join_words(words)?;
+        self.keywords_with(text, &KeywordsOptions::default().top_k(top_k).segmented(true))
+    }
+
+    /// 使用分词与词性标注接口将繁体转换为简体并分词，返回以空格分隔的分词结果
+    fn simplified_words<T: AsRef<str>>(&self, contents: &[T]) -> Result<Vec<String>> {
+        let tags = self.tag_with(contents, &TagOptions::default().t2s(true))?;
+        Ok(tags.into_iter().map(|tag| tag.word.join(" ")).collect())
+    }
+
+    /// 使用多个线程并发调用[关键词提取接口](http://docs.bosonnlp.com/keywords.html)，提取多个文本的关键词
+    ///
+    /// 关键词提取接口每次只处理一个文本，``texts`` 中的每个文本会单独发送一个请求，
+    /// 最多同时发送 4 个请求，返回结果与输入顺序一致。任一请求失败时返回按输入顺序第一个失败的错误
+    ///
+    /// # 使用示例
+    ///
+    /// ```no_run
+    /// extern crate bosonnlp;
+    ///
+    /// use bosonnlp::BosonNLP;
+    ///
+    /// fn main() {
+    ///     let nlp = BosonNLP::new(std::env::var("BOSON_API_TOKEN").unwrap());
+    ///     let rs = nlp.keywords_batch(&["病毒式媒体网站", "新闻迅速蔓延"], 2, false).unwrap();
+    ///     assert_eq!(2, rs.len());
+    /// }
+    /// ```
+    pub fn keywords_batch<T: AsRef<str> + Sync>(&self, texts: &[T], top_k: usize, segmented: bool) -> Result<Vec<Vec<ScoredWord>>> {
+        validate_top_k(top_k)?;
+        self.check_contents(texts)?;
+        par_map(texts, 4, |text| self.keywords(text, top_k, segmented))
+    }
+
+    /// [依存文法分析接口](http://docs.bosonnlp.com/depparser.html)
+    ///
+    /// ``contents``: 需要做依存文法分析的文本序列
+    ///
+    /// # 使用示例
+    ///
+    /// ```
+    /// extern crate bosonnlp;
+    ///
+    /// use bosonnlp::BosonNLP;
+    ///
+    /// fn main() {
+    ///     let nlp = BosonNLP::new(env!("BOSON_API_TOKEN"));
+    ///     let rs = nlp.depparser(&["今天天气好"]).unwrap();
+    ///     assert_eq!(1, rs.len());
+    ///     let dep0 = &rs[0];
+    ///     assert_eq!(vec![2isize, 2isize, -1isize], dep0.head);
+    ///     let rs = nlp.depparser(&["今天天气好", "美好的世界"]).unwrap();
+    ///     assert_eq!(2, rs.len());
+    /// }
+    /// ```
+    pub fn depparser<T: AsRef<str>>(&self, contents: &[T]) -> Result<Vec<Dependency>> {
+        self.depparser_segmented(contents, false)
+    }
+
+    /// 对单个文本调用[依存文法分析接口](http://docs.bosonnlp.com/depparser.html)
+    pub fn depparser_one<T: AsRef<str>>(&self, content: T) -> Result<Dependency> {
+        single(self.depparser(&[content])?)
+    }
+
+    /// [依存文法分析接口](http://docs.bosonnlp.com/depparser.html)，支持已经分词的输入
+    ///
+    /// ``segmented``: 输入是否已经为分词结果，若为 `true` 则不会再对内容进行分词处理，词之间以空格分隔
+    ///
+    /// # 使用示例
+    ///
+    /// ```no_run
+    /// extern crate bosonnlp;
+    ///
+    /// use bosonnlp::BosonNLP;
+    ///
+    /// fn main() {
+    ///     let nlp = BosonNLP::new(std::env::var("BOSON_API_TOKEN").unwrap());
+    ///     let rs = nlp.depparser_segmented(&["今天 天气 好"], true).unwrap();
+    ///     assert_eq!(1, rs.len());
+    /// }
+    /// ```
+    pub fn depparser_segmented<T: AsRef<str>>(&self, contents: &[T], segmented: bool) -> Result<Vec<Dependency>> {
+        let params = if segmented { vec![("segmented", "1")] } else { vec![] };
+        let rs: Vec<Dependency> = self.post_batch("/depparser/analysis", params, contents)?;
+        for dep in &rs {
+            dep.validate()?;
+        }
+        Ok(rs)
+    }
+
+    /// [命名实体识别接口](http://docs.bosonnlp.com/ner.html)
+    ///
+    /// ``contents``: 需要做命名实体识别的文本序列
+    ///
+    /// ``sensitivity``: 准确率与召回率之间的平衡。
+    /// 设置成 1 能找到更多的实体，设置成 5 能以更高的精度寻找实体
+    /// 一般设置为 3
+    ///
+    /// ``segmented``: 输入是否已经为分词结果
+    ///
+    /// # 使用示例
+    ///
+    /// ```
+    /// extern crate bosonnlp;
+    ///
+    /// use bosonnlp::BosonNLP;
+    ///
+    /// fn main() {
+    ///     let nlp = BosonNLP::new(env!("BOSON_API_TOKEN"));
+    ///     let rs = nlp.ner(&["成都商报记者 姚永忠"], 2, false).unwrap();
+    ///     assert_eq!(1, rs.len());
+    ///     let rs = nlp.ner(&["成都商报记者 姚永忠", "微软XP操作系统今日正式退休"], 2, false).unwrap();
+    ///     assert_eq!(2, rs.len());
+    /// }
+    /// ```
+    pub fn ner<T: AsRef<str>>(&self, contents: &[T], sensitivity: usize, segmented: bool) -> Result<Vec<NamedEntity>> {
+        let options = NerOptions::default().sensitivity(sensitivity).segmented(segmented);
+        self.ner_with(contents, &options)
+    }
+
+    /// 对单个文本调用[命名实体识别接口](http://docs.bosonnlp.com/ner.html)
+    pub fn ner_one<T: AsRef<str>>(&self, content: T, sensitivity: usize, segmented: bool) -> Result<NamedEntity> {
+        single(self.ner(&[content], sensitivity, segmented)?)
+    }
+
+    /// 使用 [`NerOptions`](struct.NerOptions.html) 调用[命名实体识别接口](http://docs.bosonnlp.com/ner.html)
+    ///
+    /// 命名实体识别接口不支持繁体转简体，开启 ``t2s`` 时先使用开启了 ``t2s`` 的分词与词性标注接口
+    /// 将文本转换为简体并分词，再以已分词的方式发送
+    ///
+    /// # 使用示例
+    ///
+    /// ```no_run
+    /// extern crate bosonnlp;
+    ///
+    /// use bosonnlp::{BosonNLP, NerOptions};
+    ///
+    /// fn main() {
+    ///     let nlp = BosonNLP::new(std::env::var("BOSON_API_TOKEN").unwrap());
+    ///     let rs = nlp.ner_with(&["微軟作業系統今日正式退休"], &NerOptions::default().t2s(true)).unwrap();
+    ///     assert_eq!(1, rs.len());
+    /// }
+    /// ```
+    pub fn ner_with<T: AsRef<str>>(&self, contents: &[T], options: &NerOptions) -> Result<Vec<NamedEntity>> {
+        let sensitivity_str = options.sensitivity.to_string();
+        let mut params = vec![("sensitivity", sensitivity_str.as_ref())];
+        if options.segmented || options.t2s {
+            params.push(("segmented", "1"));
+        }
+        if options.t2s {
+            let contents = self.simplified_words(contents)?;
+            return self.post_batch("/ner/analysis", params, &contents);
+        }
+        self.post_batch("/ner/analysis", params, contents)
+    }
+
+    /// 使用已分词的输入调用[命名实体识别接口](http://docs.bosonnlp.com/ner.html)
+    ///
+    /// ``contents``: 每个文本的分词结果，发送时以空格连接，词为空或包含空白字符时返回 `Error::InvalidArgument`
+    ///
+    /// ``sensitivity``: 准确率与召回率之间的平衡，一般设置为 3
+    ///
+    /// # 使用示例
+    ///
+    /// ```no_run
+    /// extern crate bosonnlp;
+    ///
+    /// use bosonnlp::BosonNLP;
+    ///
+    /// fn main() {
+    ///     let nlp = BosonNLP::new(std::env::var("BOSON_API_TOKEN").unwrap());
+    ///     let words = vec![vec!["成都", "商报", "记者", "姚永忠"]];
+    ///     let rs = nlp.ner_segmented(&words, 3).unwrap();
+    ///     assert_eq!(1, rs.len());
+    /// }
+    /// ```
+    pub fn ner_segmented<W: AsRef<str>>(&self, contents: &[Vec<W>], sensitivity: usize) -> Result<Vec<NamedEntity>> {
+        let contents = contents.iter().map(|words| join_words(words)).collect::<Result<Vec<_>>>()?;
+        self.ner_with(&contents, &NerOptions::default().sensitivity(sensitivity).segmented(true))
+    }
+
+    /// [分词与词性标注接口](http://docs.bosonnlp.com/tag.html)
+    ///
+    /// ``contents``: 需要做分词与词性标注的文本序列
+    ///
+    /// ``space_mode``: 空格保留选项，0-3 有效
+    ///
+    /// ``oov_level``: 枚举强度选项，0-4 有效
+    ///
+    /// ``t2s``: 是否开启繁体转简体
+    ///
+    /// ``special_char_conv``: 是否转化特殊字符，针对回车、Tab 等特殊字符。
+    ///
+    /// # 使用示例
+    ///
+    /// ```
+    /// extern crate bosonnlp;
+    ///
+    /// use bosonnlp::BosonNLP;
+    ///
+    /// fn main() {
+    ///     let nlp = BosonNLP::new(env!("BOSON_API_TOKEN"));
+    ///     let rs = nlp.tag(&["成都商报记者 姚永忠"], 0, 3, false, false).unwrap();
+    ///     assert_eq!(1, rs.len());
+    /// }
+    /// ```
+    pub fn tag<T: AsRef<str>>(
+        &self,
+        contents: &[T],
+        space_mode: usize,
+        oov_level: usize,
+        t2s: bool,
+        special_char_conv: bool,
+    ) -> Result<Vec<Tag>> {
+        let options = TagOptions::default()
+            .space_mode(space_mode)
+            .oov_level(oov_level)
+            .t2s(t2s)
+            .special_char_conv(special_char_conv);
+        self.tag_with(contents, &options)
+    }
+
+    /// 对单个文本调用[分词与词性标注接口](http://docs.bosonnlp.com/tag.html)
+    pub fn tag_one<T: AsRef<str>>(
+        &self,
+        content: T,
+        space_mode: usize,
+        oov_level: usize,
+        t2s: bool,
+        special_char_conv: bool,
+    ) -> Result<Tag> {
+        single(self.tag(&[content], space_mode, oov_level, t2s, special_char_conv)?)
+    }
+
+    /// 使用 [`TagOptions`](struct.TagOptions.html) 调用[分词与词性标注接口](http://docs.bosonnlp.com/tag.html)
+    ///
+    /// ``space_mode`` 或 ``oov_level`` 超出有效范围时返回 `Error::InvalidArgument`，不会发送请求
+    ///
+    /// # 使用示例
+    ///
+    /// ```no_run
+    /// extern crate bosonnlp;
+    ///
+    /// use bosonnlp::{BosonNLP, TagOptions};
+    ///
+    /// fn main() {
+    ///     let nlp = BosonNLP::new(std::env::var("BOSON_API_TOKEN").unwrap());
+    ///     let options = TagOptions::default().oov_level(4).t2s(true);
+    ///     let rs = nlp.tag_with(&["成都商报记者 姚永忠"], &options).unwrap();
+    ///     assert_eq!(1, rs.len());
+    /// }
+    /// ```
+    pub fn tag_with<T: AsRef<str>>(&self, contents: &[T], options: &TagOptions) -> Result<Vec<Tag>> {
+        options.validate()?;
+        let params = options.params();
+        self.post_batch("/tag/analysis", params.iter().map(|(k, v)| (*k, v.as_str())).collect(), contents)
+    }
+
+    /// 组装 `tag_with` 将要发送的请求但不发送，``contents`` 不会按 ``batch_size`` 拆分
     ///
     /// # 使用示例
     ///
     /// ```
     /// extern crate bosonnlp;
     ///
-    /// use bosonnlp::BosonNLP;
+    /// use bosonnlp::{BosonNLP, TagOptions};
     ///
     /// fn main() {
-    ///     let nlp = BosonNLP::new(env!("BOSON_API_TOKEN"));
-    ///     let time = nlp.convert_time("2013年二月二十八日下午四点三十分二十九秒", None).unwrap();
-    ///     assert_eq!("2013-02-28 16:30:29", &time.timestamp.unwrap());
-    ///     assert_eq!("timestamp", &time.format);
+    ///     let nlp = BosonNLP::new("token");
+    ///     let req = nlp.preview_tag(&["成都商报记者 姚永忠"], &TagOptions::default().oov_level(4)).unwrap();
+    ///     assert_eq!("/tag/analysis", req.url.path());
+    ///     assert!(req.url.query().unwrap().contains("oov_level=4"));
     /// }
     /// ```
-    pub fn convert_time<T: AsRef<str>>(&self, content: T, basetime: Option<T>) -> Result<ConvertedTime> {
-        if let Some(base) = basetime {
-            let params = vec![("pattern", content.as_ref()), ("basetime", base.as_ref())];
-            return self.post("/time/analysis", params, &Value::Null);
-        } else {
-            let params = vec![("pattern", content.as_ref())];
-            return self.post("/time/analysis", params, &Value::Null);
-        };
+    pub fn preview_tag<T: AsRef<str>>(&self, contents: &[T], options: &TagOptions) -> Result<PreparedRequest> {
+        options.validate()?;
+        self.check_contents(contents)?;
+        let params = options.params();
+        let data = contents.iter().map(|c| c.as_ref()).collect::<Vec<_>>();
+        let params = params.iter().map(|(k, v)| (*k, v.as_str())).collect();
+        self.prepare(Method::POST, "/tag/analysis", params, Some(&data), CompressionMode::Auto)
     }
 
-    /// [新闻分类接口](http://docs.bosonnlp.com/classify.html)
+    /// [新闻摘要接口](http://docs.bosonnlp.com/summary.html)
     ///
-    /// ``contents``: 需要做分类的新闻文本序列
+    /// ``title``: 需要做摘要的新闻标题，如果没有则传入空字符串
+    ///
+    /// ``content``: 需要做摘要的新闻正文，最多 10000 字
+    ///
+    /// ``percentage``: 摘要长度占原文长度的比例，大于 0 且不超过 1 的小数，
+    /// 需要按字数限制时请使用 [`summary_by_words`](#method.summary_by_words)
+    ///
+    /// ``not_exceed``: 是否严格限制字数
     ///
     /// # 使用示例
     ///
@@ -208,149 +1886,212 @@ impl BosonNLP {
     ///
     /// fn main() {
     ///     let nlp = BosonNLP::new(env!("BOSON_API_TOKEN"));
-    ///     let rs = nlp.classify(&["俄否决安理会谴责叙军战机空袭阿勒颇平民"]).unwrap();
-    ///     assert_eq!(vec![5usize], rs);
+    ///     let title = "前优酷土豆技术副总裁黄冬加盟芒果TV任CTO";
+    ///     let content = "腾讯科技讯（刘亚澜）10月22日消息，前优酷土豆技术副总裁黄冬已于日前正式加盟芒果TV，出任CTO一职。";
+    ///     let rs = nlp.summary(title, content, 1.0, false);
+    ///     assert!(rs.is_ok());
     /// }
     /// ```
-    pub fn classify<T: AsRef<str>>(&self, contents: &[T]) -> Result<Vec<usize>> {
-        let data = contents.iter().map(|c| c.as_ref()).collect::<Vec<_>>();
-        self.post("/classify/analysis", vec![], &data)
-    }
-
-    /// [语义联想接口](http://docs.bosonnlp.com/suggest.html)
     ///
-    /// ``word``: 需要做语义联想的词
+    /// 正文超过 10000 字或 ``percentage`` 超出范围时返回 `Error::InvalidArgument`，不会发送请求：
     ///
-    /// ``top_k``: 返回结果的条数，最大值可设定为 100
+    /// ```
+    /// extern crate bosonnlp;
     ///
-    /// # 使用示例
+    /// use bosonnlp::{BosonNLP, Error};
     ///
+    /// fn main() {
+    ///     // 本地没有监听 1 号端口，参数检查通过时请求会失败
+    ///     let nlp = BosonNLP::with_options("token", "http://127.0.0.1:1", false);
+    ///     let content = "长".repeat(10001);
+    ///     match nlp.summary("", &content, 0.3, false) {
+    ///         Err(Error::InvalidArgument { ref name, .. }) => assert_eq!("content", name),
+    ///         _ => panic!("expected invalid content"),
+    ///     }
+    ///     match nlp.summary_by_words("", &content, 50, false) {
+    ///         Err(Error::InvalidArgument { ref name, .. }) => assert_eq!("content", name),
+    ///         _ => panic!("expected invalid content"),
+    ///     }
+    ///     for percentage in &[0.0, 1.5, -0.3] {
+    ///         match nlp.summary("", "今天天气好", *percentage, false) {
+    ///             Err(Error::InvalidArgument { ref name, .. }) => assert_eq!("percentage", name),
+    ///             _ => panic!("expected invalid percentage"),
+    ///         }
+    ///     }
+    ///     assert!(match nlp.summary("", "长".repeat(10000), 1.0, false) {
+    ///         Err(Error::InvalidArgument { .. }) => false,
+    ///         _ => true,
+    ///     });
+    /// }
     /// ```
+    ///
+    /// ``title`` 和 ``content`` 可以是不同的字符串类型：
+    ///
+    /// ```no_run
     /// extern crate bosonnlp;
     ///
     /// use bosonnlp::BosonNLP;
     ///
     /// fn main() {
-    ///     let nlp = BosonNLP::new(env!("BOSON_API_TOKEN"));
-    ///     let rs = nlp.suggest("北京", 2).unwrap();
-    ///     assert_eq!(2, rs.len());
+    ///     let nlp = BosonNLP::new(std::env::var("BOSON_API_TOKEN").unwrap());
+    ///     let content = format!("{}已于日前正式加盟芒果TV，出任CTO一职。", "前优酷土豆技术副总裁黄冬");
+    ///     let rs = nlp.summary("", content, 0.3, false).unwrap();
+    ///     assert!(!rs.is_empty());
     /// }
     /// ```
-    pub fn suggest<T: AsRef<str>>(&self, word: T, top_k: usize) -> Result<Vec<(f32, String)>> {
-        self.post(
-            "/suggest/analysis",
-            vec![("top_k", &top_k.to_string())],
-            &word.as_ref(),
-        )
+    pub fn summary<T: AsRef<str>, U: AsRef<str>>(&self, title: T, content: U, percentage: f32, not_exceed: bool) -> Result<String> {
+        validate_summary(content.as_ref(), Some(percentage))?;
+        let data = summary_data(title.as_ref(), content.as_ref(), json!(percentage), not_exceed);
+        self.post("/summary/analysis", vec![], &data)
     }
 
-    /// [关键词提取接口](http://docs.bosonnlp.com/keywords.html)
+    /// [新闻摘要接口](http://docs.bosonnlp.com/summary.html)，按字数限制摘要长度
     ///
-    /// ``text``: 需要做关键词提取的文本
+    /// ``title``: 需要做摘要的新闻标题，如果没有则传入空字符串
+    ///
+    /// ``content``: 需要做摘要的新闻正文
     ///
-    /// ``top_k``: 返回结果的条数，最大值可设定为 100
+    /// ``max_words``: 摘要的最大字数
     ///
-    /// ``segmented``: `text` 是否已经进行了分词，若为 `true` 则不会再对内容进行分词处理
+    /// ``not_exceed``: 是否严格限制字数
+    ///
+    /// 正文超过 10000 字时返回 `Error::InvalidArgument`，不会发送请求
     ///
     /// # 使用示例
     ///
-    /// ```
+    /// ```no_run
     /// extern crate bosonnlp;
     ///
     /// use bosonnlp::BosonNLP;
     ///
     /// fn main() {
-    ///     let nlp = BosonNLP::new(env!("BOSON_API_TOKEN"));
-    ///     let rs = nlp.keywords("病毒式媒体网站：让新闻迅速蔓延", 2, false).unwrap();
-    ///     assert_eq!(2, rs.len());
+    ///     let nlp = BosonNLP::new(std::env::var("BOSON_API_TOKEN").unwrap());
+    ///     let title = "前优酷土豆技术副总裁黄冬加盟芒果TV任CTO";
+    ///     let content = "腾讯科技讯（刘亚澜）10月22日消息，前优酷土豆技术副总裁黄冬已于日前正式加盟芒果TV，出任CTO一职。";
+    ///     let rs = nlp.summary_by_words(title, content, 50, true).unwrap();
+    ///     assert!(rs.chars().count() <= 50);
     /// }
     /// ```
-    pub fn keywords<T: AsRef<str>>(&self, text: T, top_k: usize, segmented: bool) -> Result<Vec<(f32, String)>> {
-        let top_k_str = top_k.to_string();
-        let params = if segmented {
-            vec![("top_k", top_k_str.as_ref()), ("segmented", "1")]
-        } else {
-            vec![("top_k", top_k_str.as_ref())]
-        };
-        self.post("/keywords/analysis", params, &text.as_ref())
+    pub fn summary_by_words<T: AsRef<str>, U: AsRef<str>>(&self, title: T, content: U, max_words: usize, not_exceed: bool) -> Result<String> {
+        validate_summary(content.as_ref(), None)?;
+        let data = summary_data(title.as_ref(), content.as_ref(), json!(max_words), not_exceed);
+        self.post("/summary/analysis", vec![], &data)
     }
 
-    /// [依存文法分析接口](http://docs.bosonnlp.com/depparser.html)
-    ///
-    /// ``contents``: 需要做依存文法分析的文本序列
+    /// 获取 ``task_id`` 对应的[文本聚类](http://docs.bosonnlp.com/cluster.html)任务句柄，用于分步执行任务
     ///
     /// # 使用示例
     ///
-    /// ```
+    /// ```no_run
     /// extern crate bosonnlp;
     ///
-    /// use bosonnlp::BosonNLP;
+    /// use bosonnlp::{BosonNLP, TaskStatus};
     ///
     /// fn main() {
-    ///     let nlp = BosonNLP::new(env!("BOSON_API_TOKEN"));
-    ///     let rs = nlp.depparser(&["今天天气好"]).unwrap();
+    ///     let nlp = BosonNLP::new(std::env::var("BOSON_API_TOKEN").unwrap());
+    ///     let mut task = nlp.cluster_task("mytask");
+    ///     task.push(&["今天天气好", "今天天气不错", "点点楼头细雨"]).unwrap();
+    ///     task.analysis(0.8, 0.45).unwrap();
+    ///     // 之后可以在其它进程中使用同一个 task_id 查询结果
+    ///     let task = nlp.cluster_task("mytask");
+    ///     task.wait(Some(10), None).unwrap();
+    ///     assert_eq!(TaskStatus::Done, task.status().unwrap());
+    ///     let rs = task.result().unwrap();
+    ///     task.clear().unwrap();
     ///     assert_eq!(1, rs.len());
-    ///     let dep0 = &rs[0];
-    ///     assert_eq!(vec![2isize, 2isize, -1isize], dep0.head);
-    ///     let rs = nlp.depparser(&["今天天气好", "美好的世界"]).unwrap();
-    ///     assert_eq!(2, rs.len());
     /// }
     /// ```
-    pub fn depparser<T: AsRef<str>>(&self, contents: &[T]) -> Result<Vec<Dependency>> {
-        let data = contents.iter().map(|c| c.as_ref()).collect::<Vec<_>>();
-        self.post("/depparser/analysis", vec![], &data)
+    pub fn cluster_task(&self, task_id: &str) -> ClusterTaskHandle<'_> {
+        ClusterTaskHandle::new(ClusterTask::new(self, task_id))
     }
 
-    /// [命名实体识别接口](http://docs.bosonnlp.com/ner.html)
+    /// 获取 ``task_id`` 对应的[典型意见](http://docs.bosonnlp.com/comments.html)任务句柄，用于分步执行任务
+    pub fn comments_task(&self, task_id: &str) -> CommentsTaskHandle<'_> {
+        CommentsTaskHandle::new(CommentsTask::new(self, task_id))
+    }
+
+    /// 清空 ``task_id`` 对应的文本聚类任务在服务器端缓存的文本和结果
     ///
-    /// ``contents``: 需要做命名实体识别的文本序列
+    /// # 使用示例
     ///
-    /// ``sensitivity``: 准确率与召回率之间的平衡。
-    /// 设置成 1 能找到更多的实体，设置成 5 能以更高的精度寻找实体
-    /// 一般设置为 3
+    /// ```no_run
+    /// extern crate bosonnlp;
     ///
-    /// ``segmented``: 输入是否已经为分词结果
+    /// use bosonnlp::{BosonNLP, ClusterOptions};
+    ///
+    /// fn main() {
+    ///     let nlp = BosonNLP::new(std::env::var("BOSON_API_TOKEN").unwrap());
+    ///     let options = ClusterOptions::default().task_id("keepme").keep_result(true);
+    ///     let rs = nlp.cluster_with(&["今天天气好", "今天天气不错", "点点楼头细雨"], &options).unwrap();
+    ///     // 任务数据仍然保留在服务器上，可以再次获取结果
+    ///     assert_eq!(rs.len(), nlp.cluster_task("keepme").result().unwrap().len());
+    ///     nlp.clear_cluster_task("keepme").unwrap();
+    /// }
+    /// ```
+    pub fn clear_cluster_task(&self, task_id: &str) -> Result<()> {
+        self.cluster_task(task_id).clear()
+    }
+
+    /// 清空 ``task_id`` 对应的典型意见任务在服务器端缓存的文本和结果
+    ///
+    /// 进程在任务完成前退出时，服务器上会残留已上传的文本，可以使用相同的 ``task_id`` 调用
+    /// [`clear_cluster_task`](#method.clear_cluster_task) 或该方法清空，请求失败时返回错误
     ///
     /// # 使用示例
     ///
-    /// ```
+    /// ```no_run
     /// extern crate bosonnlp;
     ///
     /// use bosonnlp::BosonNLP;
     ///
     /// fn main() {
-    ///     let nlp = BosonNLP::new(env!("BOSON_API_TOKEN"));
-    ///     let rs = nlp.ner(&["成都商报记者 姚永忠"], 2, false).unwrap();
-    ///     assert_eq!(1, rs.len());
-    ///     let rs = nlp.ner(&["成都商报记者 姚永忠", "微软XP操作系统今日正式退休"], 2, false).unwrap();
-    ///     assert_eq!(2, rs.len());
+    ///     let nlp = BosonNLP::new(std::env::var("BOSON_API_TOKEN").unwrap());
+    ///     nlp.clear_comments_task("crashed").unwrap();
     /// }
     /// ```
-    pub fn ner<T: AsRef<str>>(&self, contents: &[T], sensitivity: usize, segmented: bool) -> Result<Vec<NamedEntity>> {
-        let data = contents.iter().map(|c| c.as_ref()).collect::<Vec<_>>();
-        let sensitivity_str = sensitivity.to_string();
-        let params = if segmented {
-            vec![
-                ("sensitivity", sensitivity_str.as_ref()),
-                ("segmented", "1"),
-            ]
-        } else {
-            vec![("sensitivity", sensitivity_str.as_ref())]
-        };
-        self.post("/ner/analysis", params, &data)
+    pub fn clear_comments_task(&self, task_id: &str) -> Result<()> {
+        self.comments_task(task_id).clear()
     }
 
-    /// [分词与词性标注接口](http://docs.bosonnlp.com/tag.html)
+    /// 继续等待已提交的文本聚类任务完成，获取结果后清空服务器端的任务数据
     ///
-    /// ``contents``: 需要做分词与词性标注的文本序列
+    /// [`cluster`](#method.cluster) 等方法等待超时返回 `Error::Timeout(task_id)` 时服务器端的任务仍在运行，
+    /// 可以使用其中的 ``task_id`` 调用该方法继续等待，不会重新上传文本和启动分析
     ///
-    /// ``space_mode``: 空格保留选项，0-3 有效
+    /// ``timeout``: 等待任务完成的秒数，传入 ``None`` 则一直等待
+    pub fn resume_cluster(&self, task_id: &str, timeout: Option<u64>) -> Result<Vec<TextCluster>> {
+        let task = self.cluster_task(task_id);
+        task.wait(timeout, None)?;
+        let result = task.result()?;
+        if let Err(err) = task.clear() {
+            warn!("Failed to clear task {}: {}", task_id, err);
+        }
+        Ok(result)
+    }
+
+    /// 继续等待已提交的典型意见任务完成，获取结果后清空服务器端的任务数据，
+    /// 参见 [`resume_cluster`](#method.resume_cluster)
+    pub fn resume_comments(&self, task_id: &str, timeout: Option<u64>) -> Result<Vec<CommentsCluster>> {
+        let task = self.comments_task(task_id);
+        task.wait(timeout, None)?;
+        let result = task.result()?;
+        if let Err(err) = task.clear() {
+            warn!("Failed to clear task {}: {}", task_id, err);
+        }
+        Ok(result)
+    }
+
+    /// [文本聚类接口](http://docs.bosonnlp.com/cluster.html)
     ///
-    /// ``oov_level``: 枚举强度选项，0-4 有效
+    /// ``contents``: 需要聚类的文本，可以是 ``Vec``、切片或任意产生文本的迭代器
     ///
-    /// ``t2s``: 是否开启繁体转简体
+    /// ``task_id``: 唯一的 task_id，话题聚类任务的名字，可由字母和数字组成
     ///
-    /// ``special_char_conv``: 是否转化特殊字符，针对回车、Tab 等特殊字符。
+    /// ``alpha``: 聚类最大 cluster 大小，一般为 0.8
+    ///
+    /// ``beta``: 聚类平均 cluster 大小，一般为 0.45
+    ///
+    /// ``timeout``: 等待文本聚类任务完成的秒数，一般为 1800 秒
     ///
     /// # 使用示例
     ///
@@ -361,123 +2102,233 @@ impl BosonNLP {
     ///
     /// fn main() {
     ///     let nlp = BosonNLP::new(env!("BOSON_API_TOKEN"));
-    ///     let rs = nlp.tag(&["成都商报记者 姚永忠"], 0, 3, false, false).unwrap();
+    ///     let contents = vec![
+    ///         "今天天气好",
+    ///         "今天天气好",
+    ///         "今天天气不错",
+    ///         "点点楼头细雨",
+    ///         "重重江外平湖",
+    ///         "当年戏马会东徐",
+    ///         "今日凄凉南浦",
+    ///     ];
+    ///     let rs = nlp.cluster(&contents, None, 0.8, 0.45, Some(10)).unwrap();
     ///     assert_eq!(1, rs.len());
     /// }
     /// ```
-    pub fn tag<T: AsRef<str>>(
+    pub fn cluster<I, T>(
         &self,
-        contents: &[T],
-        space_mode: usize,
-        oov_level: usize,
-        t2s: bool,
-        special_char_conv: bool,
-    ) -> Result<Vec<Tag>> {
-        let data = contents.iter().map(|c| c.as_ref()).collect::<Vec<_>>();
-        let t2s_str = if t2s { "1" } else { "0" };
-        let special_char_conv_str = if special_char_conv { "1" } else { "0" };
-        let space_mode_str = space_mode.to_string();
-        let oov_level_str = oov_level.to_string();
-        let params = vec![
-            ("space_mode", space_mode_str.as_ref()),
-            ("oov_level", oov_level_str.as_ref()),
-            ("t2s", t2s_str),
-            ("special_char_conv", special_char_conv_str),
-        ];
-        self.post("/tag/analysis", params, &data)
+        contents: I,
+        task_id: Option<&str>,
+        alpha: f32,
+        beta: f32,
+        timeout: Option<u64>,
+    ) -> Result<Vec<TextCluster>>
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<str>,
+    {
+        let mut options = ClusterOptions::default().alpha(alpha).beta(beta).timeout(timeout);
+        options.task_id = task_id.map(|id| id.to_owned());
+        self.cluster_with(contents, &options)
     }
 
-    /// [新闻摘要接口](http://docs.bosonnlp.com/summary.html)
+    /// 使用 [`ClusterOptions`](struct.ClusterOptions.html) 调用[文本聚类接口](http://docs.bosonnlp.com/cluster.html)
     ///
-    /// ``title``: 需要做摘要的新闻标题，如果没有则传入空字符串
+    /// 获取结果后会清空服务器端的任务数据，清空失败不影响返回的结果，只会记录一条警告日志。
+    /// 设置 ``keep_result`` 后不会清空
     ///
-    /// ``content``: 需要做摘要的新闻正文
+    /// # 使用示例
+    ///
+    /// ```no_run
+    /// extern crate bosonnlp;
     ///
-    /// ``word_limit``: 摘要字数限制
+    /// use std::time::Duration;
+    /// use bosonnlp::{BosonNLP, ClusterOptions};
     ///
-    /// ``not_exceed``: 是否严格限制字数
+    /// fn main() {
+    ///     let nlp = BosonNLP::new(std::env::var("BOSON_API_TOKEN").unwrap());
+    ///     let contents = vec![
+    ///         "今天天气好",
+    ///         "今天天气好",
+    ///         "今天天气不错",
+    ///         "点点楼头细雨",
+    ///         "重重江外平湖",
+    ///         "当年戏马会东徐",
+    ///         "今日凄凉南浦",
+    ///     ];
+    ///     let options = ClusterOptions::default()
+    ///         .timeout(10)
+    ///         .poll_interval(Duration::from_millis(500));
+    ///     let rs = nlp.cluster_with(&contents, &options).unwrap();
+    ///     assert_eq!(1, rs.len());
+    /// }
+    /// ```
+    pub fn cluster_with<I, T>(&self, contents: I, options: &ClusterOptions) -> Result<Vec<TextCluster>>
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<str>,
+    {
+        let contents: Vec<ClusterContent> = contents.into_iter().map(|c| ClusterContent::from(c.as_ref())).collect();
+        self.run_task(&contents, options, ClusterTask::new)
+    }
+
+    /// 使用自定义的文档编号调用[文本聚类接口](http://docs.bosonnlp.com/cluster.html)
+    ///
+    /// ``contents``: ``(文档编号, 文本)`` 序列，结果中使用这里的文档编号表示文档
     ///
     /// # 使用示例
     ///
-    /// ```
+    /// ```no_run
     /// extern crate bosonnlp;
     ///
-    /// use bosonnlp::BosonNLP;
+    /// use bosonnlp::{BosonNLP, ClusterOptions};
     ///
     /// fn main() {
-    ///     let nlp = BosonNLP::new(env!("BOSON_API_TOKEN"));
-    ///     let title = "前优酷土豆技术副总裁黄冬加盟芒果TV任CTO";
-    ///     let content = "腾讯科技讯（刘亚澜）10月22日消息，前优酷土豆技术副总裁黄冬已于日前正式加盟芒果TV，出任CTO一职。";
-    ///     let rs = nlp.summary(title, content, 1.0, false);
-    ///     assert!(rs.is_ok());
+    ///     let nlp = BosonNLP::new(std::env::var("BOSON_API_TOKEN").unwrap());
+    ///     let docs = vec![("doc1", "今天天气好"), ("doc2", "今天天气不错")];
+    ///     let rs = nlp.cluster_with_ids(docs, &ClusterOptions::default()).unwrap();
+    ///     assert!(rs.iter().all(|cluster| cluster.list.iter().all(|id| id.starts_with("doc"))));
     /// }
     /// ```
-    pub fn summary<T: Into<String>>(&self, title: T, content: T, word_limit: f32, not_exceed: bool) -> Result<String> {
-        let not_exceed = if not_exceed { 1 } else { 0 };
-        let data = json!({
-            "title": title.into(),
-            "content": content.into(),
-            "percentage": word_limit,
-            "not_exceed": not_exceed
-        });
-        self.post("/summary/analysis", vec![], &data)
+    pub fn cluster_with_ids<I, K, V>(&self, contents: I, options: &ClusterOptions) -> Result<Vec<TextCluster>>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        let contents: Vec<ClusterContent> = contents
+            .into_iter()
+            .map(|(id, text)| ClusterContent::with_id(id, text))
+            .collect();
+        self.run_task(&contents, options, ClusterTask::new)
     }
 
-    /// [文本聚类接口](http://docs.bosonnlp.com/cluster.html)
+    /// 调用[文本聚类接口](http://docs.bosonnlp.com/cluster.html)，结果中的文档编号会被替换为对应的文本
     ///
-    /// ``task_id``: 唯一的 task_id，话题聚类任务的名字，可由字母和数字组成
+    /// # 使用示例
     ///
-    /// ``alpha``: 聚类最大 cluster 大小，一般为 0.8
+    /// ```no_run
+    /// extern crate bosonnlp;
     ///
-    /// ``beta``: 聚类平均 cluster 大小，一般为 0.45
+    /// use bosonnlp::{BosonNLP, ClusterOptions};
     ///
-    /// ``timeout``: 等待文本聚类任务完成的秒数，一般为 1800 秒
+    /// fn main() {
+    ///     let nlp = BosonNLP::new(std::env::var("BOSON_API_TOKEN").unwrap());
+    ///     let contents = vec!["今天天气好", "今天天气不错", "点点楼头细雨"];
+    ///     let rs = nlp.cluster_with_text(&contents, &ClusterOptions::default()).unwrap();
+    ///     assert!(contents.contains(&rs[0].representative.as_str()));
+    /// }
+    /// ```
+    pub fn cluster_with_text<I, T>(&self, contents: I, options: &ClusterOptions) -> Result<Vec<TextClusterWithText>>
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<str>,
+    {
+        let contents: Vec<ClusterContent> = contents.into_iter().map(|c| ClusterContent::from(c.as_ref())).collect();
+        self.run_task(&contents, options, ClusterTask::new)?
+            .iter()
+            .map(|cluster| cluster.with_text(&contents))
+            .collect()
+    }
+
+    /// 从 ``reader`` 中逐行读取文本调用[文本聚类接口](http://docs.bosonnlp.com/cluster.html)，
+    /// 每行为一篇文档，跳过空行
+    ///
+    /// 文本按 100 行一组边读取边上传，不会一次性将全部内容读入内存，
+    /// 其余参数与 [`cluster`](#method.cluster) 相同
     ///
     /// # 使用示例
     ///
-    /// ```
+    /// ```no_run
     /// extern crate bosonnlp;
     ///
+    /// use std::io::Cursor;
     /// use bosonnlp::BosonNLP;
     ///
     /// fn main() {
-    ///     let nlp = BosonNLP::new(env!("BOSON_API_TOKEN"));
-    ///     let contents = vec![
-    ///         "今天天气好",
-    ///         "今天天气好",
-    ///         "今天天气不错",
-    ///         "点点楼头细雨",
-    ///         "重重江外平湖",
-    ///         "当年戏马会东徐",
-    ///         "今日凄凉南浦",
-    ///     ];
-    ///     let rs = nlp.cluster(&contents, None, 0.8, 0.45, Some(10)).unwrap();
+    ///     let nlp = BosonNLP::new(std::env::var("BOSON_API_TOKEN").unwrap());
+    ///     let reader = Cursor::new("今天天气好\n今天天气好\n\n今天天气不错\n点点楼头细雨\n");
+    ///     let rs = nlp.cluster_from_reader(reader, None, 0.8, 0.45, Some(10)).unwrap();
     ///     assert_eq!(1, rs.len());
     /// }
     /// ```
-    pub fn cluster<T: AsRef<str>>(
+    pub fn cluster_from_reader<R: BufRead>(
         &self,
-        contents: &[T],
+        reader: R,
         task_id: Option<&str>,
         alpha: f32,
         beta: f32,
         timeout: Option<u64>,
     ) -> Result<Vec<TextCluster>> {
-        let mut task = match task_id {
-            Some(_id) => ClusterTask::new(self, _id),
-            None => {
-                let _id = Uuid::new_v4().to_simple_ref().to_string();
-                ClusterTask::new(self, _id)
+        let mut options = ClusterOptions::default().alpha(alpha).beta(beta).timeout(timeout);
+        options.task_id = task_id.map(|id| id.to_owned());
+        options.validate()?;
+        let mut task = self.new_task(&options, ClusterTask::new);
+        let chunk_size = options.chunk_size.max(1);
+        let mut chunk = Vec::with_capacity(chunk_size);
+        let mut total = 0;
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
             }
-        };
-        let tasks: Vec<ClusterContent> = Vec::from_iter(contents.iter().map(|c| c.into()));
-        if !task.push(&tasks)? {
+            chunk.push(ClusterContent::from(line));
+            if chunk.len() == chunk_size {
+                task.push(&chunk)?;
+                total += chunk.len();
+                chunk.clear();
+            }
+        }
+        if !chunk.is_empty() {
+            task.push(&chunk)?;
+            total += chunk.len();
+        }
+        if total == 0 {
+            return Ok(vec![]);
+        }
+        self.finish_task(&task, &options)
+    }
+
+    /// 按 ``options`` 创建文本聚类或典型意见任务，没有指定 ``task_id`` 时随机生成
+    fn new_task<'a, T, F>(&'a self, options: &ClusterOptions, new: F) -> T
+    where
+        T: Task,
+        F: FnOnce(&'a BosonNLP, String) -> T,
+    {
+        let task_id = options.task_id.clone().unwrap_or_else(|| Uuid::new_v4().to_simple_ref().to_string());
+        let mut task = new(self, task_id);
+        task.set_chunk_size(options.chunk_size);
+        self.task_submitted(T::KIND, &task.task_id());
+        task
+    }
+
+    /// 上传 ``contents`` 并执行任务
+    fn run_task<'a, T, F>(&'a self, contents: &[ClusterContent], options: &ClusterOptions, new: F) -> Result<Vec<T::Item>>
+    where
+        T: Task,
+        F: FnOnce(&'a BosonNLP, String) -> T,
+    {
+        self.check_contents(&contents.iter().map(|c| c.text.as_str()).collect::<Vec<_>>())?;
+        options.validate()?;
+        let mut task = self.new_task(options, new);
+        task.push(contents)?;
+        if contents.is_empty() {
             return Ok(vec![]);
         }
-        task.analysis(alpha, beta)?;
-        task.wait(timeout)?;
+        self.finish_task(&task, options)
+    }
+
+    /// 启动已上传文本的任务，等待完成后获取结果并按 ``keep_result`` 清空任务数据
+    fn finish_task<T: Task>(&self, task: &T, options: &ClusterOptions) -> Result<Vec<T::Item>> {
+        task.analysis(options.alpha, options.beta)?;
+        task.wait_with(options)?;
         let result = task.result()?;
-        task.clear()?;
+        if options.keep_result {
+            return Ok(result);
+        }
+        if let Err(err) = task.clear() {
+            warn!("Failed to clear task {}: {}", task.task_id(), err);
+        }
         Ok(result)
     }
 
@@ -520,29 +2371,92 @@ impl BosonNLP {
     ///     assert_eq!(4, rs.len());
     /// }
     /// ```
-    pub fn comments<T: AsRef<str>>(
+    pub fn comments<I, T>(
         &self,
-        contents: &[T],
+        contents: I,
         task_id: Option<&str>,
         alpha: f32,
         beta: f32,
         timeout: Option<u64>,
-    ) -> Result<Vec<CommentsCluster>> {
-        let mut task = match task_id {
-            Some(_id) => CommentsTask::new(self, _id),
-            None => {
-                let _id = Uuid::new_v4().to_simple_ref().to_string();
-                CommentsTask::new(self, _id)
-            }
-        };
-        let tasks: Vec<ClusterContent> = Vec::from_iter(contents.iter().map(|c| c.into()));
-        if !task.push(&tasks)? {
-            return Ok(vec![]);
+    ) -> Result<Vec<CommentsCluster>>
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<str>,
+    {
+        let mut options = ClusterOptions::default().alpha(alpha).beta(beta).timeout(timeout);
+        options.task_id = task_id.map(|id| id.to_owned());
+        self.comments_with(contents, &options)
+    }
+
+    /// 使用 [`ClusterOptions`](struct.ClusterOptions.html) 调用[典型意见接口](http://docs.bosonnlp.com/comments.html)
+    ///
+    /// 获取结果后会清空服务器端的任务数据，清空失败不影响返回的结果，只会记录一条警告日志。
+    /// 设置 ``keep_result`` 后不会清空
+    pub fn comments_with<I, T>(&self, contents: I, options: &ClusterOptions) -> Result<Vec<CommentsCluster>>
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<str>,
+    {
+        let contents: Vec<ClusterContent> = contents.into_iter().map(|c| ClusterContent::from(c.as_ref())).collect();
+        self.run_task(&contents, options, CommentsTask::new)
+    }
+
+    /// 使用自定义的文档编号调用[典型意见接口](http://docs.bosonnlp.com/comments.html)
+    ///
+    /// ``contents``: ``(文档编号, 文本)`` 序列，结果中使用这里的文档编号表示文档
+    pub fn comments_with_ids<I, K, V>(&self, contents: I, options: &ClusterOptions) -> Result<Vec<CommentsCluster>>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        let contents: Vec<ClusterContent> = contents
+            .into_iter()
+            .map(|(id, text)| ClusterContent::with_id(id, text))
+            .collect();
+        self.run_task(&contents, options, CommentsTask::new)
+    }
+}
+
+/// 使用最多 ``concurrency`` 个线程对 ``items`` 中的每一项调用 ``f``，按输入顺序返回结果。
+/// 任一调用失败时不再处理之后的项，排在失败项之前的项仍会处理完，并返回按输入顺序第一个失败的错误
+fn par_map<T, R, F>(items: &[T], concurrency: usize, f: F) -> Result<Vec<R>>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> Result<R> + Sync,
+{
+    let results: Vec<Mutex<Option<Result<R>>>> = items.iter().map(|_| Mutex::new(None)).collect();
+    let next = AtomicUsize::new(0);
+    // 按输入顺序第一个失败的项，之后的项不再处理，之前的项仍需全部处理完
+    let failed = AtomicUsize::new(usize::MAX);
+    thread::scope(|scope| {
+        for _ in 0..concurrency.max(1).min(items.len()) {
+            scope.spawn(|| {
+                let claim = |idx: usize| {
+                    if idx < items.len() && idx < failed.load(Ordering::SeqCst) {
+                        Some(idx + 1)
+                    } else {
+                        None
+                    }
+                };
+                while let Ok(idx) = next.fetch_update(Ordering::SeqCst, Ordering::SeqCst, claim) {
+                    let rs = f(&items[idx]);
+                    if rs.is_err() {
+                        failed.fetch_min(idx, Ordering::SeqCst);
+                    }
+                    *results[idx].lock().unwrap() = Some(rs);
+                }
+            });
+        }
+    });
+    let mut result = Vec::with_capacity(items.len());
+    for rs in results {
+        // 第一个失败项之前的项都已处理，遇到失败项时直接返回
+        match rs.into_inner().unwrap() {
+            Some(rs) => result.push(rs?),
+            None => unreachable!("item skipped before any failure"),
         }
-        task.analysis(alpha, beta)?;
-        task.wait(timeout)?;
-        let result = task.result()?;
-        task.clear()?;
-        Ok(result)
     }
+    Ok(result)
 }
@@ -1,33 +1,349 @@
+use std::fmt;
 use std::io;
+use std::time::Duration;
 
+use failure::Fail;
 use reqwest::{self, StatusCode};
-use serde_json;
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use serde::de::{Deserialize, Deserializer};
+use serde_json::Value;
 
-#[derive(Debug, Fail)]
+/// `BosonNLP` API 错误响应中的结构化字段，各字段在响应中不存在时为 `None`
+///
+/// # 使用示例
+///
+/// ```
+/// extern crate bosonnlp;
+/// extern crate serde_json;
+///
+/// use bosonnlp::BosonApiError;
+///
+/// fn main() {
+///     let err: BosonApiError = serde_json::from_str(r#"{"message":"invalid token","code":"AUTH_FAILED","rcode":1001}"#).unwrap();
+///     assert_eq!(Some("invalid token".to_owned()), err.message);
+///     assert_eq!(Some("AUTH_FAILED".to_owned()), err.code);
+///     assert_eq!(Some(1001), err.rcode);
+///
+///     let err: BosonApiError = serde_json::from_str(r#"{"message":"count limit exceeded","code":429}"#).unwrap();
+///     assert_eq!(Some("429".to_owned()), err.code);
+///     assert_eq!(None, err.rcode);
+///
+///     let err: BosonApiError = serde_json::from_str(r#"{"status":500}"#).unwrap();
+///     assert_eq!(BosonApiError::default(), err);
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[non_exhaustive]
+pub struct BosonApiError {
+    /// 错误信息
+    #[serde(default)]
+    pub message: Option<String>,
+    /// 错误代码，数字代码会转换为字符串
+    #[serde(default, deserialize_with = "deserialize_code")]
+    pub code: Option<String>,
+    /// 返回码
+    #[serde(default)]
+    pub rcode: Option<i64>,
+}
+
+/// 错误代码可能是字符串或数字
+fn deserialize_code<'de, D: Deserializer<'de>>(deserializer: D) -> ::std::result::Result<Option<String>, D::Error> {
+    Ok(match Option::<Value>::deserialize(deserializer)? {
+        Some(Value::String(code)) => Some(code),
+        Some(Value::Number(code)) => Some(code.to_string()),
+        _ => None,
+    })
+}
+
+/// 调用 `BosonNLP` API 可能出现的错误
+///
+/// 该枚举标记为 `#[non_exhaustive]`，之后新增错误类型不属于破坏性变更，
+/// 因此在 crate 外部 `match` 时必须包含通配分支:
+///
+/// ```compile_fail,E0004
+/// extern crate bosonnlp;
+///
+/// use bosonnlp::Error;
+///
+/// fn describe(err: &Error) -> &'static str {
+///     match *err {
+///         Error::Api { .. } => "api",
+///         Error::InvalidArgument { .. } => "invalid argument",
+///         Error::RateLimited { .. } => "rate limited",
+///         Error::InvalidResponse(..) => "invalid response",
+///         Error::TaskNotFound(..) => "task not found",
+///         Error::Timeout(..) => "timeout",
+///         Error::Cancelled(..) => "cancelled",
+///         Error::RequestTimeout(..) => "request timeout",
+///         Error::Io(..) => "io",
+///         Error::Http(..) => "http",
+///         Error::Json(..) => "json",
+///     }
+/// }
+///
+/// fn main() {}
+/// ```
+///
+/// 加上通配分支即可:
+///
+/// ```
+/// extern crate bosonnlp;
+///
+/// use bosonnlp::Error;
+///
+/// fn describe(err: &Error) -> &'static str {
+///     match *err {
+///         Error::RateLimited { .. } => "rate limited",
+///         Error::Timeout(..) => "timeout",
+///         _ => "other",
+///     }
+/// }
+///
+/// fn main() {
+///     assert_eq!("rate limited", describe(&Error::RateLimited { retry_after: None }));
+///     assert_eq!("other", describe(&Error::TaskNotFound("task".to_owned())));
+/// }
+/// ```
+#[derive(Debug)]
+#[non_exhaustive]
 pub enum Error {
     /// API 错误
-    #[fail(display = "API error, code {}, reason {}", code, reason)]
     Api {
         code: StatusCode,
-        reason: String
+        reason: String,
+        /// 响应头中的请求 ID，向 `BosonNLP` 反馈问题时需要提供
+        request_id: Option<String>,
+        /// 响应内容为 JSON 对象时解析出的结构化错误信息
+        detail: Option<BosonApiError>,
+    },
+
+    /// 参数不合法，请求未发送
+    InvalidArgument {
+        name: String,
+        message: String
+    },
+
+    /// 请求过于频繁，``retry_after`` 为服务器建议的重试等待时间
+    RateLimited {
+        retry_after: Option<Duration>
     },
 
+    /// API 返回的数据不合法，如依存文法分析结果的数组长度不一致
+    InvalidResponse(String),
+
     /// 聚类任务未找到
-    #[fail(display = "Cluster task {} not found", _0)]
     TaskNotFound(String),
 
-    /// 聚类任务超时
-    #[fail(display = "Cluster task {} timed out", _0)]
+    /// 聚类任务超时，即等待任务完成的时间超过了 ``timeout``
     Timeout(String),
 
-    #[fail(display = "I/O error: {}", _0)]
-    Io(#[cause] io::Error),
+    /// 等待聚类任务完成时取消标志被设置
+    Cancelled(String),
+
+    /// 单次 HTTP 请求超时，即超过了 Client 设置的请求超时时间
+    RequestTimeout(reqwest::Error),
+
+    Io(io::Error),
+
+    Http(reqwest::Error),
+
+    Json(serde_json::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Api { ref code, ref reason, ref request_id, .. } => {
+                write!(f, "API error, code {}, reason {}", code, reason)?;
+                if let Some(ref request_id) = *request_id {
+                    write!(f, ", request id {}", request_id)?;
+                }
+                Ok(())
+            }
+            Error::InvalidArgument { ref name, ref message } => write!(f, "Invalid argument {}: {}", name, message),
+            Error::RateLimited { ref retry_after } => write!(f, "Rate limited, retry after {:?}", retry_after),
+            Error::InvalidResponse(ref message) => write!(f, "Invalid response: {}", message),
+            Error::TaskNotFound(ref task_id) => write!(f, "Cluster task {} not found", task_id),
+            Error::Timeout(ref task_id) => write!(f, "Cluster task {} timed out", task_id),
+            Error::Cancelled(ref task_id) => write!(f, "Cluster task {} cancelled", task_id),
+            Error::RequestTimeout(ref err) => write!(f, "Http request timed out: {}", err),
+            Error::Io(ref err) => write!(f, "I/O error: {}", err),
+            Error::Http(ref err) => write!(f, "Http error: {}", err),
+            Error::Json(ref err) => write!(f, "Json error: {}", err),
+        }
+    }
+}
+
+impl Fail for Error {
+    fn cause(&self) -> Option<&dyn Fail> {
+        match *self {
+            Error::RequestTimeout(ref err) | Error::Http(ref err) => Some(err),
+            Error::Io(ref err) => Some(err),
+            Error::Json(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl Error {
+    /// 该错误是否是临时性的，重试请求可能成功
+    ///
+    /// 连接错误、请求超时、5xx 响应和 429 响应返回 true，
+    /// 4xx 响应、参数错误和 JSON 解析错误等返回 false
+    ///
+    /// # 使用示例
+    ///
+    /// ```
+    /// extern crate bosonnlp;
+    /// extern crate reqwest;
+    /// extern crate serde_json;
+    ///
+    /// use bosonnlp::Error;
+    /// use reqwest::StatusCode;
+    ///
+    /// fn main() {
+    ///     let api_error = |code| Error::Api { code, reason: "".to_owned(), request_id: None, detail: None };
+    ///     assert!(api_error(StatusCode::INTERNAL_SERVER_ERROR).is_retryable());
+    ///     assert!(api_error(StatusCode::SERVICE_UNAVAILABLE).is_retryable());
+    ///     assert!(!api_error(StatusCode::BAD_REQUEST).is_retryable());
+    ///     assert!(!api_error(StatusCode::FORBIDDEN).is_retryable());
+    ///     assert_eq!(Some(StatusCode::FORBIDDEN), api_error(StatusCode::FORBIDDEN).status_code());
+    ///
+    ///     let rate_limited = Error::RateLimited { retry_after: None };
+    ///     assert!(rate_limited.is_retryable());
+    ///     assert_eq!(Some(StatusCode::TOO_MANY_REQUESTS), rate_limited.status_code());
+    ///
+    ///     // 本地没有监听 1 号端口，连接会被拒绝
+    ///     let connect_error = Error::from(reqwest::blocking::get("http://127.0.0.1:1").unwrap_err());
+    ///     assert!(connect_error.is_retryable());
+    ///     assert_eq!(None, connect_error.status_code());
+    ///
+    ///     let json_error = Error::from(serde_json::from_str::<u32>("oops").unwrap_err());
+    ///     assert!(!json_error.is_retryable());
+    ///     assert_eq!(None, json_error.status_code());
+    ///
+    ///     let invalid = Error::InvalidArgument { name: "top_k".to_owned(), message: "".to_owned() };
+    ///     assert!(!invalid.is_retryable());
+    /// }
+    /// ```
+    pub fn is_retryable(&self) -> bool {
+        match *self {
+            #[cfg(not(target_arch = "wasm32"))]
+            Error::Http(ref e) => e.is_connect() || e.is_timeout() || e.is_request(),
+            #[cfg(target_arch = "wasm32")]
+            Error::Http(ref e) => e.is_timeout() || e.is_request(),
+            Error::Api { code, .. } => code.is_server_error(),
+            Error::RateLimited { .. } | Error::RequestTimeout(..) => true,
+            _ => false,
+        }
+    }
+
+    /// 根据 API 返回的非成功响应构造错误
+    ///
+    /// 429 响应返回 `Error::RateLimited`，其它响应返回 `Error::Api`。
+    /// 响应内容为带有 ``message`` 字段的 JSON 时使用该字段作为错误原因，否则使用原始响应内容
+    ///
+    /// # 使用示例
+    ///
+    /// ```
+    /// extern crate bosonnlp;
+    /// extern crate reqwest;
+    ///
+    /// use std::time::Duration;
+    /// use bosonnlp::Error;
+    /// use reqwest::StatusCode;
+    /// use reqwest::header::{HeaderMap, HeaderValue};
+    ///
+    /// fn main() {
+    ///     let mut headers = HeaderMap::new();
+    ///     headers.insert("X-Request-Id", HeaderValue::from_static("abc123"));
+    ///     match Error::from_response(StatusCode::BAD_REQUEST, &headers, r#"{"message":"invalid token"}"#.to_owned()) {
+    ///         Error::Api { code, reason, request_id, detail } => {
+    ///             assert_eq!(StatusCode::BAD_REQUEST, code);
+    ///             assert_eq!("invalid token", reason);
+    ///             assert_eq!(Some("abc123".to_owned()), request_id);
+    ///             assert_eq!(Some("invalid token".to_owned()), detail.unwrap().message);
+    ///         }
+    ///         err => panic!("unexpected error {:?}", err),
+    ///     }
+    ///
+    ///     let empty = HeaderMap::new();
+    ///     match Error::from_response(StatusCode::BAD_GATEWAY, &empty, "<html>Bad Gateway</html>".to_owned()) {
+    ///         Error::Api { reason, request_id, detail, .. } => {
+    ///             assert_eq!("<html>Bad Gateway</html>", reason);
+    ///             assert_eq!(None, request_id);
+    ///             assert_eq!(None, detail);
+    ///         }
+    ///         err => panic!("unexpected error {:?}", err),
+    ///     }
+    ///
+    ///     match Error::from_response(StatusCode::INTERNAL_SERVER_ERROR, &empty, "".to_owned()) {
+    ///         Error::Api { code, reason, .. } => {
+    ///             assert_eq!(500, code.as_u16());
+    ///             assert_eq!("", reason);
+    ///         }
+    ///         err => panic!("unexpected error {:?}", err),
+    ///     }
+    ///
+    ///     let mut headers = HeaderMap::new();
+    ///     headers.insert("Retry-After", HeaderValue::from_static("30"));
+    ///     match Error::from_response(StatusCode::TOO_MANY_REQUESTS, &headers, "".to_owned()) {
+    ///         Error::RateLimited { retry_after } => assert_eq!(Some(Duration::from_secs(30)), retry_after),
+    ///         err => panic!("unexpected error {:?}", err),
+    ///     }
+    /// }
+    /// ```
+    pub fn from_response(status: StatusCode, headers: &HeaderMap, body: String) -> Error {
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            return Error::RateLimited {
+                retry_after: parse_retry_after(headers),
+            };
+        }
+        let (reason, detail) = match serde_json::from_str::<Value>(&body) {
+            Ok(result) => {
+                let reason = match result.get("message") {
+                    Some(msg) => msg.as_str().unwrap_or("").to_owned(),
+                    None => body,
+                };
+                (reason, serde_json::from_value::<BosonApiError>(result).ok())
+            }
+            Err(..) => (body, None),
+        };
+        Error::Api {
+            code: status,
+            reason,
+            request_id: parse_request_id(headers),
+            detail,
+        }
+    }
 
-    #[fail(display = "Http error: {}", _0)]
-    Http(#[cause] reqwest::Error),
+    /// API 响应的 HTTP 状态码，非 API 响应导致的错误返回 `None`
+    pub fn status_code(&self) -> Option<StatusCode> {
+        match *self {
+            Error::Api { code, .. } => Some(code),
+            Error::RateLimited { .. } => Some(StatusCode::TOO_MANY_REQUESTS),
+            _ => None,
+        }
+    }
+}
+
+/// 解析 `Retry-After` 响应头中的秒数
+fn parse_retry_after(headers: &HeaderMap) -> Option<Duration> {
+    headers
+        .get(RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .map(Duration::from_secs)
+}
 
-    #[fail(display = "Json error: {}", _0)]
-    Json(#[cause] serde_json::Error),
+/// 获取响应头中的请求 ID
+fn parse_request_id(headers: &HeaderMap) -> Option<String> {
+    ["X-Request-Id", "X-Trace-Id"]
+        .iter()
+        .filter_map(|name| headers.get(*name))
+        .filter_map(|value| value.to_str().ok())
+        .map(|value| value.to_owned())
+        .next()
 }
 
 pub type Result<T> = ::std::result::Result<T, Error>;
@@ -40,7 +356,11 @@ impl From<io::Error> for Error {
 
 impl From<reqwest::Error> for Error {
     fn from(err: reqwest::Error) -> Self {
-        Error::Http(err)
+        if err.is_timeout() {
+            Error::RequestTimeout(err)
+        } else {
+            Error::Http(err)
+        }
     }
 }
 
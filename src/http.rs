@@ -0,0 +1,314 @@
+use std::cmp::{max, min};
+use std::convert::TryFrom;
+use std::io::{self, Read};
+#[cfg(feature = "compression")]
+use std::io::Write;
+use std::time::Duration;
+
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use url::Url;
+#[cfg(feature = "compression")]
+use flate2::Compression;
+#[cfg(feature = "compression")]
+use flate2::read::GzDecoder;
+#[cfg(feature = "compression")]
+use flate2::write::GzEncoder;
+use reqwest::{Method, StatusCode};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, CONTENT_ENCODING};
+
+use crate::errors::*;
+use crate::validate::validate_task_id;
+
+
+/// 默认的 `BosonNLP` API 服务器地址
+pub(crate) const DEFAULT_BOSONNLP_URL: &str = "https://api.bosonnlp.com";
+
+/// 默认的 gzip 压缩级别
+pub(crate) const DEFAULT_COMPRESS_LEVEL: u32 = 6;
+
+/// 最高的 gzip 压缩级别，更大的值按该级别处理
+pub(crate) const MAX_COMPRESS_LEVEL: u32 = 9;
+
+/// 默认的响应内容大小上限，50 MB
+pub(crate) const DEFAULT_MAX_RESPONSE_BYTES: usize = 50 * 1024 * 1024;
+
+/// 指数退避时两次重试之间最长的等待时间
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(64);
+
+/// User-Agent，设置了 ``app`` 时将其添加在默认值之前，如 ``myapp/2.0 bosonnlp-rs/0.11.0``
+pub(crate) fn user_agent(app: Option<&str>) -> String {
+    match app {
+        Some(app) => format!("{} bosonnlp-rs/{}", app, env!("CARGO_PKG_VERSION")),
+        None => format!("bosonnlp-rs/{}", env!("CARGO_PKG_VERSION")),
+    }
+}
+
+/// 组装好但没有发送的请求，参见
+/// [`BosonNLP::preview_post`](struct.BosonNLP.html#method.preview_post)
+#[derive(Debug, Clone)]
+pub struct PreparedRequest {
+    /// 请求方法
+    pub method: Method,
+    /// 包含查询参数的完整 URL
+    pub url: Url,
+    /// 请求头，不包含 API Token
+    pub headers: HeaderMap,
+    /// 压缩前的 JSON 请求体，GET 请求为 None
+    pub body: Option<String>,
+    /// 请求体是否会经过 gzip 压缩
+    pub compressed: bool,
+}
+
+/// 解析自定义 HTTP 头，名称或值不合法时返回 `Error::InvalidArgument`
+pub(crate) fn parse_header(name: &str, value: &str) -> Result<(HeaderName, HeaderValue)> {
+    let header_name = HeaderName::from_bytes(name.as_bytes()).map_err(|_| Error::InvalidArgument {
+        name: "header".to_owned(),
+        message: format!("invalid header name {:?}", name),
+    })?;
+    let header_value = HeaderValue::from_str(value).map_err(|_| Error::InvalidArgument {
+        name: "header".to_owned(),
+        message: format!("invalid value for header {}", name),
+    })?;
+    Ok((header_name, header_value))
+}
+
+/// 日志中最多输出的请求体和响应体字节数
+const LOG_BODY_LIMIT: usize = 1024;
+
+/// 截断过长的请求体或响应体以便输出到日志
+pub(crate) fn truncate_for_log(body: &[u8]) -> String {
+    if body.len() <= LOG_BODY_LIMIT {
+        return String::from_utf8_lossy(body).into_owned();
+    }
+    format!("{}... ({} bytes)", String::from_utf8_lossy(&body[..LOG_BODY_LIMIT]), body.len())
+}
+
+/// 用于日志输出的自定义请求头，隐藏取值以免泄露鉴权信息
+pub(crate) fn redact_headers(headers: &HeaderMap) -> String {
+    headers.keys().map(|name| format!("{}: ***", name)).collect::<Vec<_>>().join(", ")
+}
+
+/// 使用非本机的 ``http://`` 地址时警告 API Token 会以明文传输
+pub(crate) fn warn_insecure_url(bosonnlp_url: &str) {
+    let url = match Url::parse(bosonnlp_url) {
+        Ok(url) => url,
+        Err(..) => return,
+    };
+    if url.scheme() != "http" {
+        return;
+    }
+    let local = match url.host() {
+        Some(url::Host::Domain(domain)) => domain.eq_ignore_ascii_case("localhost"),
+        Some(url::Host::Ipv4(ip)) => ip.is_loopback(),
+        Some(url::Host::Ipv6(ip)) => ip.is_loopback(),
+        None => false,
+    };
+    if !local {
+        warn!("{} is not using HTTPS, the API token will be sent in cleartext", bosonnlp_url);
+    }
+}
+
+/// 拼接 API 地址和查询参数
+///
+/// 值为空字符串的参数只输出参数名，如情感分析接口通过 ``?food`` 指定模型
+pub(crate) fn build_url(bosonnlp_url: &str, api_prefix: Option<&str>, endpoint: &str, params: Vec<(&str, &str)>) -> Url {
+    let prefix = api_prefix.map(|prefix| prefix.trim_matches('/')).unwrap_or("");
+    let url_string = if prefix.is_empty() {
+        format!("{}{}", bosonnlp_url, endpoint)
+    } else {
+        format!("{}/{}{}", bosonnlp_url, prefix, endpoint)
+    };
+    let mut url = Url::parse(&url_string).unwrap();
+    {
+        let mut query = url.query_pairs_mut();
+        for (key, value) in params {
+            if value.is_empty() {
+                query.append_key_only(key);
+            } else {
+                query.append_pair(key, value);
+            }
+        }
+    }
+    url
+}
+
+/// 使用 gzip 压缩请求体，返回请求体及其是否经过压缩
+#[cfg(feature = "compression")]
+pub(crate) fn compress_body(body: Vec<u8>, level: u32) -> Result<(Vec<u8>, bool)> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::new(level.min(MAX_COMPRESS_LEVEL)));
+    encoder.write_all(&body)?;
+    Ok((encoder.finish()?, true))
+}
+
+/// 未启用 `compression` feature 时请求体总是不压缩
+#[cfg(not(feature = "compression"))]
+pub(crate) fn compress_body(body: Vec<u8>, _level: u32) -> Result<(Vec<u8>, bool)> {
+    debug!("The `compression` feature is disabled, sending {} bytes uncompressed", body.len());
+    Ok((body, false))
+}
+
+/// 响应内容是否经过 gzip 压缩
+pub(crate) fn is_gzipped(headers: &HeaderMap) -> bool {
+    headers
+        .get(CONTENT_ENCODING)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.eq_ignore_ascii_case("gzip"))
+}
+
+/// 读取响应内容，``gzipped`` 为 true 时先进行解压
+///
+/// ``capacity`` 为预分配的缓冲区大小，对于压缩的响应仅作为下限。
+/// 解压后的内容超过 ``limit`` 字节时返回 `Error::InvalidResponse`，超出的部分不会被读入内存
+pub(crate) fn read_body<R: Read>(reader: R, gzipped: bool, capacity: usize, limit: usize) -> Result<String> {
+    let mut body = Vec::with_capacity(capacity.min(limit));
+    let max = limit as u64 + 1;
+    if gzipped {
+        #[cfg(feature = "compression")]
+        GzDecoder::new(reader).take(max).read_to_end(&mut body)?;
+        #[cfg(not(feature = "compression"))]
+        return Err(Error::InvalidResponse(
+            "received a gzip response but the `compression` feature is disabled".to_owned(),
+        ));
+    } else {
+        reader.take(max).read_to_end(&mut body)?;
+    }
+    check_body_size(body.len(), limit)?;
+    String::from_utf8(body).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err).into())
+}
+
+/// 响应内容超过 ``limit`` 字节时返回 `Error::InvalidResponse`
+pub(crate) fn check_body_size(len: usize, limit: usize) -> Result<()> {
+    if len > limit {
+        return Err(Error::InvalidResponse(format!("response body exceeds {} bytes", limit)));
+    }
+    Ok(())
+}
+
+/// 以流的方式读取响应内容，累计读取超过 ``limit`` 字节时返回错误，
+/// 该错误可以通过 `body_size_error` 转换为 `Error::InvalidResponse`
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) struct LimitedReader<R> {
+    inner: R,
+    read: usize,
+    limit: usize,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl<R: Read> LimitedReader<R> {
+    pub fn new(inner: R, limit: usize) -> LimitedReader<R> {
+        LimitedReader { inner, read: 0, limit }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl<R: Read> Read for LimitedReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.read += n;
+        if self.read > self.limit {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, BodyTooLarge(self.limit)));
+        }
+        Ok(n)
+    }
+}
+
+/// `LimitedReader` 读取的内容超过上限
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug)]
+struct BodyTooLarge(usize);
+
+#[cfg(not(target_arch = "wasm32"))]
+impl std::fmt::Display for BodyTooLarge {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "response body exceeds {} bytes", self.0)
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl std::error::Error for BodyTooLarge {}
+
+/// 将 `LimitedReader` 超过上限时产生的读取错误转换为 `Error::InvalidResponse`，其它错误原样返回
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn body_size_error(err: Error) -> Error {
+    let limit = match err {
+        Error::Io(ref err) => err.get_ref().and_then(|err| err.downcast_ref::<BodyTooLarge>()).map(|err| err.0),
+        Error::Json(ref err) => std::error::Error::source(err)
+            .and_then(|err| err.downcast_ref::<io::Error>())
+            .and_then(|err| err.get_ref())
+            .and_then(|err| err.downcast_ref::<BodyTooLarge>())
+            .map(|err| err.0),
+        _ => None,
+    };
+    match limit {
+        Some(limit) => Error::InvalidResponse(format!("response body exceeds {} bytes", limit)),
+        None => err,
+    }
+}
+
+/// 检查响应状态码并解析响应内容
+pub(crate) fn handle_response<D: DeserializeOwned>(status: StatusCode, headers: &HeaderMap, body: String) -> Result<D> {
+    if !status.is_success() {
+        return Err(Error::from_response(status, headers, body));
+    }
+    Ok(serde_json::from_str::<D>(&body)?)
+}
+
+/// 新闻摘要接口的请求体，``percentage`` 为小数时表示比例，为整数时表示字数
+pub(crate) fn summary_data(title: &str, content: &str, percentage: Value, not_exceed: bool) -> Value {
+    json!({
+        "title": title,
+        "content": content,
+        "percentage": percentage,
+        "not_exceed": if not_exceed { 1 } else { 0 }
+    })
+}
+
+/// 将基准时间转换为 GMT+8 时间并格式化为时间转换接口要求的 ``%Y-%m-%d %H:%M:%S`` 格式
+#[cfg(feature = "chrono")]
+pub(crate) fn format_basetime<Tz: chrono::TimeZone>(basetime: &chrono::DateTime<Tz>) -> String {
+    let gmt8 = chrono::FixedOffset::east_opt(8 * 3600).unwrap();
+    basetime.with_timezone(&gmt8).format("%Y-%m-%d %H:%M:%S").to_string()
+}
+
+/// 将 Unix 时间戳格式化为时间转换接口要求的基准时间，时间戳超出范围时返回 `Error::InvalidArgument`
+#[cfg(feature = "chrono")]
+pub(crate) fn format_basetime_timestamp(timestamp: i64) -> Result<String> {
+    match chrono::DateTime::from_timestamp(timestamp, 0) {
+        Some(basetime) => Ok(format_basetime(&basetime)),
+        None => Err(Error::InvalidArgument {
+            name: "timestamp".to_owned(),
+            message: format!("timestamp {} out of range", timestamp),
+        }),
+    }
+}
+
+/// 请求是否可以安全地重复发送
+pub(crate) fn is_idempotent(method: &Method, endpoint: &str) -> bool {
+    let path = endpoint.split('?').next().unwrap_or(endpoint);
+    *method == Method::GET || path.ends_with("/analysis")
+}
+
+/// 第 ``retries`` 次重试前需要等待的时间，优先使用服务器建议的等待时间
+///
+/// 指数退避的等待时间最长为 64 秒，``backoff`` 本身更长时不再翻倍
+pub(crate) fn retry_delay(err: &Error, backoff: Duration, retries: usize) -> Duration {
+    match *err {
+        Error::RateLimited { retry_after: Some(retry_after) } => retry_after,
+        _ => {
+            let max_delay = max(backoff, MAX_RETRY_DELAY);
+            u32::try_from(retries)
+                .ok()
+                .and_then(|retries| 2u32.checked_pow(retries))
+                .and_then(|factor| backoff.checked_mul(factor))
+                .map(|delay| min(delay, max_delay))
+                .unwrap_or(max_delay)
+        }
+    }
+}
+
+/// 聚类任务接口的路径，如 ``/cluster/push/{task_id}``
+pub(crate) fn task_endpoint(kind: &str, action: &str, task_id: &str) -> Result<String> {
+    validate_task_id(task_id)?;
+    Ok(format!("/{}/{}/{}", kind, action, task_id))
+}
@@ -7,7 +7,7 @@
 //!
 //! ```toml
 //! [dependencies]
-//! bosonnlp = "0.10"
+//! bosonnlp = "0.11"
 //! ```
 //!
 //! ## 使用教程
@@ -26,6 +26,123 @@
 //! }
 //! ```
 //!
+//! ## 异步接口
+//!
+//! 启用 `async` feature 后可以使用基于 `reqwest` 异步 Client 的 `AsyncBosonNLP`，
+//! 其接口与 `BosonNLP` 一致，但所有方法均为 `async fn`:
+//!
+//! ```toml
+//! [dependencies]
+//! bosonnlp = { version = "0.11", features = ["async"] }
+//! ```
+//!
+//! ## WebAssembly
+//!
+//! 编译到 `wasm32-unknown-unknown` 时只提供异步的 `AsyncBosonNLP`，需要启用 `wasm` feature，
+//! 请求通过浏览器的 `fetch` 发送，不支持设置代理和请求超时时间:
+//!
+//! ```toml
+//! [dependencies]
+//! bosonnlp = { version = "0.11", features = ["wasm"] }
+//! ```
+//!
+//! ## TLS
+//!
+//! 默认通过 `native-tls` feature 使用系统的 TLS 实现（Linux 上为 OpenSSL）。
+//! 静态链接 musl 或交叉编译时可以关闭默认 feature 并启用 `rustls`，使用纯 Rust 实现的 TLS:
+//!
+//! ```toml
+//! [dependencies]
+//! bosonnlp = { version = "0.11", default-features = false, features = ["rustls", "compression"] }
+//! ```
+//!
+//! 使用 `BosonNLP::with_client` 或 `BosonNLPBuilder::client` 传入自行构建的 reqwest Client 时，以该 Client 的配置为准
+//!
+//! ## 压缩
+//!
+//! 默认启用的 `compression` feature 提供请求体和响应的 gzip 压缩支持。
+//! 关闭后不再依赖 `flate2`，请求体总是不压缩发送，`compress` 设置不生效:
+//!
+//! ```toml
+//! [dependencies]
+//! bosonnlp = { version = "0.11", default-features = false, features = ["native-tls"] }
+//! ```
+//!
+//! ## 多线程
+//!
+//! `BosonNLP` 实现了 `Send` 和 `Sync`，内部的 reqwest Client 维护连接池，
+//! 推荐创建一个实例并通过 `Arc` 在线程间共享，而不是每个线程各自创建:
+//!
+//! ```
+//! extern crate bosonnlp;
+//!
+//! use std::sync::Arc;
+//! use std::thread;
+//! use bosonnlp::BosonNLP;
+//!
+//! fn _assert_send_sync<T: Send + Sync>() {}
+//!
+//! fn main() {
+//!     _assert_send_sync::<BosonNLP>();
+//!     // 本地没有监听 1 号端口，请求会失败
+//!     let nlp = Arc::new(BosonNLP::with_options("token", "http://127.0.0.1:1", false));
+//!     let handles: Vec<_> = (0..4)
+//!         .map(|_| {
+//!             let nlp = Arc::clone(&nlp);
+//!             thread::spawn(move || nlp.classify(&["今天天气好"]).is_err())
+//!         })
+//!         .collect();
+//!     for handle in handles {
+//!         assert!(handle.join().unwrap());
+//!     }
+//! }
+//! ```
+//!
+//! ## 日志
+//!
+//! 请求和响应会通过 `log` 输出日志：`debug` 级别输出请求方法、URL、请求体大小和响应状态码，
+//! `trace` 级别额外输出截断后的请求体和响应体。日志中不会包含 API Token，自定义请求头的值也会被隐藏
+//!
+//! ```
+//! extern crate bosonnlp;
+//! extern crate log;
+//!
+//! use std::sync::Mutex;
+//! use log::{LevelFilter, Log, Metadata, Record};
+//! use bosonnlp::BosonNLP;
+//!
+//! static LOGS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+//!
+//! struct Logger;
+//!
+//! impl Log for Logger {
+//!     fn enabled(&self, _: &Metadata) -> bool { true }
+//!     fn log(&self, record: &Record) {
+//!         if record.target().starts_with("bosonnlp") {
+//!             LOGS.lock().unwrap().push(record.args().to_string());
+//!         }
+//!     }
+//!     fn flush(&self) {}
+//! }
+//!
+//! fn main() {
+//!     log::set_logger(&Logger).unwrap();
+//!     log::set_max_level(LevelFilter::Trace);
+//!     // 本地没有监听 1 号端口，请求会失败，但仍然会输出请求日志
+//!     let nlp = BosonNLP::builder()
+//!         .token("secret-token")
+//!         .url("http://127.0.0.1:1")
+//!         .header("X-Api-Gateway-Key", "secret-key")
+//!         .build()
+//!         .unwrap();
+//!     assert!(nlp.classify(&["今天天气好"]).is_err());
+//!     let logs = LOGS.lock().unwrap();
+//!     assert!(logs.iter().any(|l| l.contains("POST http://127.0.0.1:1/classify/analysis")));
+//!     assert!(logs.iter().any(|l| l.contains("今天天气好")));
+//!     assert!(logs.iter().all(|l| !l.contains("secret")));
+//! }
+//! ```
+//!
 //! 可以在 [`BosonNLP` 文档网站](http://docs.bosonnlp.com) 阅读详细的 `BosonNLP` REST API 文档。
 #![recursion_limit = "1024"]
 
@@ -34,6 +151,7 @@ extern crate log;
 extern crate url;
 extern crate uuid;
 extern crate reqwest;
+#[cfg(feature = "compression")]
 extern crate flate2;
 extern crate serde;
 #[macro_use]
@@ -41,14 +159,41 @@ extern crate serde_derive;
 #[macro_use]
 extern crate serde_json;
 extern crate failure;
-#[macro_use]
-extern crate failure_derive;
+#[cfg(all(feature = "async", not(target_arch = "wasm32")))]
+extern crate tokio;
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+extern crate gloo_timers;
+#[cfg(feature = "chrono")]
+extern crate chrono;
+
+#[cfg(all(feature = "async", not(feature = "wasm"), target_arch = "wasm32"))]
+compile_error!("the `wasm` feature is required to use `AsyncBosonNLP` on wasm32");
 
 mod rep;
+#[cfg(any(feature = "async", not(target_arch = "wasm32")))]
+mod http;
+#[cfg(any(feature = "async", not(target_arch = "wasm32")))]
+mod backoff;
+mod validate;
+#[cfg(not(target_arch = "wasm32"))]
 mod client;
+#[cfg(not(target_arch = "wasm32"))]
 mod task;
 mod errors;
+mod options;
+mod util;
+#[cfg(feature = "async")]
+mod async_client;
 
-pub use self::client::BosonNLP;
+#[cfg(not(target_arch = "wasm32"))]
+pub use self::client::{BosonNLP, BosonNLPBuilder, RequestMetrics, Sleeper, ThreadSleeper};
+#[cfg(not(target_arch = "wasm32"))]
+pub use self::task::{ClusterTaskHandle, CommentsTaskHandle, ResultIter};
+#[cfg(feature = "async")]
+pub use self::async_client::AsyncBosonNLP;
 pub use self::errors::*;
+#[cfg(any(feature = "async", not(target_arch = "wasm32")))]
+pub use self::http::PreparedRequest;
+pub use self::options::*;
 pub use self::rep::*;
+pub use self::util::split_sentences;
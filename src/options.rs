@@ -0,0 +1,403 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use crate::errors::*;
+use crate::validate::{validate_alpha_beta, validate_poll_interval};
+
+/// 取消标志，两个标志指向同一个 `AtomicBool` 时视为相等
+#[derive(Debug, Clone)]
+pub(crate) struct CancelFlag(pub(crate) Arc<AtomicBool>);
+
+impl CancelFlag {
+    pub(crate) fn is_set(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+impl PartialEq for CancelFlag {
+    fn eq(&self, other: &CancelFlag) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+/// 文本聚类和典型意见任务的参数
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClusterOptions {
+    pub(crate) task_id: Option<String>,
+    pub(crate) alpha: f32,
+    pub(crate) beta: f32,
+    pub(crate) timeout: Option<u64>,
+    pub(crate) poll_interval: Option<Duration>,
+    pub(crate) keep_result: bool,
+    pub(crate) chunk_size: usize,
+    pub(crate) cancel: Option<CancelFlag>,
+    pub(crate) check_alpha_beta: bool,
+}
+
+impl Default for ClusterOptions {
+    fn default() -> ClusterOptions {
+        ClusterOptions {
+            task_id: None,
+            alpha: 0.8,
+            beta: 0.45,
+            timeout: Some(1800),
+            poll_interval: None,
+            keep_result: false,
+            chunk_size: 100,
+            cancel: None,
+            check_alpha_beta: true,
+        }
+    }
+}
+
+impl ClusterOptions {
+    /// 唯一的 task_id，任务的名字，只能由字母和数字组成。默认随机生成
+    ///
+    /// 包含其它字符时，提交任务前会返回 `Error::InvalidArgument`
+    ///
+    /// # 使用示例
+    ///
+    /// ```
+    /// extern crate bosonnlp;
+    ///
+    /// use bosonnlp::{BosonNLP, ClusterOptions, Error};
+    ///
+    /// fn main() {
+    ///     let nlp = BosonNLP::new("");
+    ///     let contents: Vec<&str> = vec![];
+    ///     let options = ClusterOptions::default().task_id("task2017");
+    ///     assert!(nlp.cluster_with(&contents, &options).unwrap().is_empty());
+    ///     for task_id in &["task/2017", ""] {
+    ///         let options = ClusterOptions::default().task_id(*task_id);
+    ///         match nlp.cluster_with(&["今天天气好"], &options) {
+    ///             Err(Error::InvalidArgument { ref name, .. }) => assert_eq!("task_id", name),
+    ///             _ => panic!("expected invalid task_id"),
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn task_id<T: Into<String>>(mut self, task_id: T) -> ClusterOptions {
+        self.task_id = Some(task_id.into());
+        self
+    }
+
+    /// 聚类最大 cluster 大小，默认为 0.8
+    ///
+    /// ``alpha`` 和 ``beta`` 都需要在 (0, 1) 之间，并且 ``alpha`` 大于 ``beta``，
+    /// 否则提交任务前会返回 `Error::InvalidArgument`，参见 [`check_alpha_beta`](#method.check_alpha_beta)
+    ///
+    /// # 使用示例
+    ///
+    /// ```
+    /// extern crate bosonnlp;
+    ///
+    /// use bosonnlp::{BosonNLP, ClusterOptions, Error};
+    ///
+    /// fn main() {
+    ///     let nlp = BosonNLP::new("token");
+    ///     for &(alpha, beta) in &[(5.0, 0.45), (0.8, 0.0), (0.4, 0.45)] {
+    ///         let options = ClusterOptions::default().alpha(alpha).beta(beta);
+    ///         match nlp.cluster_with(["今天天气好"], &options) {
+    ///             Err(Error::InvalidArgument { ref name, .. }) => assert!(name == "alpha" || name == "beta"),
+    ///             rs => panic!("unexpected result {:?}", rs),
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn alpha(mut self, alpha: f32) -> ClusterOptions {
+        self.alpha = alpha;
+        self
+    }
+
+    /// 聚类平均 cluster 大小，默认为 0.45
+    pub fn beta(mut self, beta: f32) -> ClusterOptions {
+        self.beta = beta;
+        self
+    }
+
+    /// 等待任务完成的秒数，默认为 1800 秒，传入 ``None`` 则一直等待
+    pub fn timeout<T: Into<Option<u64>>>(mut self, timeout: T) -> ClusterOptions {
+        self.timeout = timeout.into();
+        self
+    }
+
+    /// 首次查询任务状态前等待的时间，之后每查询 3 次等待时间翻倍。默认为 1 秒，
+    /// 设置为 0 时等待任务会返回 `Error::InvalidArgument`
+    ///
+    /// 文档较少的任务通常几秒内即可完成，可以设置较短的间隔；
+    /// 文档较多的任务可以设置较长的间隔以减少 API 调用次数
+    pub fn poll_interval(mut self, poll_interval: Duration) -> ClusterOptions {
+        self.poll_interval = Some(poll_interval);
+        self
+    }
+
+    /// 获取结果后是否保留服务器端的任务数据，默认为 false 即自动清空
+    ///
+    /// 保留后可以通过任务句柄再次获取结果，但调用者需要负责在不再需要时
+    /// 调用 `clear_cluster_task` 或 `clear_comments_task` 清空任务
+    pub fn keep_result(mut self, keep_result: bool) -> ClusterOptions {
+        self.keep_result = keep_result;
+        self
+    }
+
+    /// 上传文本时单次请求最多发送的文本数量，默认为 100
+    ///
+    /// 文本较长时可以调小以避免单次请求过大，文本较短时可以调大以减少请求次数
+    ///
+    /// # 使用示例
+    ///
+    /// ```
+    /// extern crate bosonnlp;
+    ///
+    /// use bosonnlp::ClusterOptions;
+    ///
+    /// fn main() {
+    ///     // 每次请求最多上传 20 个文本
+    ///     let options = ClusterOptions::default().chunk_size(20);
+    /// }
+    /// ```
+    pub fn chunk_size(mut self, chunk_size: usize) -> ClusterOptions {
+        self.chunk_size = chunk_size;
+        self
+    }
+
+    /// 等待任务完成时的取消标志，默认不设置
+    ///
+    /// 每次查询任务状态前都会检查该标志，被设置为 true 时停止等待并返回 `Error::Cancelled`，
+    /// 服务器端的任务数据不会被清空。标志在两次查询之间的等待期间被设置时，等待结束后才会生效
+    ///
+    /// # 使用示例
+    ///
+    /// ```
+    /// extern crate bosonnlp;
+    ///
+    /// use std::sync::Arc;
+    /// use std::sync::atomic::{AtomicBool, Ordering};
+    ///
+    /// use bosonnlp::ClusterOptions;
+    ///
+    /// fn main() {
+    ///     let cancelled = Arc::new(AtomicBool::new(false));
+    ///     let options = ClusterOptions::default().cancel_flag(cancelled.clone());
+    ///     // 在其它线程中设置标志后，使用 options 等待任务的 cluster_with 会返回 Error::Cancelled
+    ///     cancelled.store(true, Ordering::SeqCst);
+    /// }
+    /// ```
+    pub fn cancel_flag(mut self, cancel: Arc<AtomicBool>) -> ClusterOptions {
+        self.cancel = Some(CancelFlag(cancel));
+        self
+    }
+
+    /// 提交任务前是否检查 ``alpha`` 和 ``beta`` 的取值范围，默认为 true
+    ///
+    /// 需要尝试文档范围以外的参数时可以关闭，此时参数会原样发送给服务器
+    pub fn check_alpha_beta(mut self, check: bool) -> ClusterOptions {
+        self.check_alpha_beta = check;
+        self
+    }
+
+    /// 检查轮询间隔，并按 ``check_alpha_beta`` 检查聚类参数
+    pub(crate) fn validate(&self) -> Result<()> {
+        validate_poll_interval(self.poll_interval)?;
+        if self.check_alpha_beta {
+            validate_alpha_beta(self.alpha, self.beta)?;
+        }
+        Ok(())
+    }
+}
+
+/// 分词与词性标注接口的参数
+///
+/// # 使用示例
+///
+/// ```
+/// extern crate bosonnlp;
+///
+/// use bosonnlp::TagOptions;
+///
+/// fn main() {
+///     let options = TagOptions::default()
+///         .space_mode(1)
+///         .oov_level(4)
+///         .t2s(true);
+///     assert_eq!(TagOptions::default().space_mode(1).oov_level(4).t2s(true), options);
+/// }
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct TagOptions {
+    pub(crate) space_mode: usize,
+    pub(crate) oov_level: usize,
+    pub(crate) t2s: bool,
+    pub(crate) special_char_conv: bool,
+}
+
+impl Default for TagOptions {
+    fn default() -> TagOptions {
+        TagOptions {
+            space_mode: 0,
+            oov_level: 3,
+            t2s: false,
+            special_char_conv: false,
+        }
+    }
+}
+
+impl TagOptions {
+    /// 空格保留选项，0-3 有效，默认为 0
+    pub fn space_mode(mut self, space_mode: usize) -> TagOptions {
+        self.space_mode = space_mode;
+        self
+    }
+
+    /// 枚举强度选项，0-4 有效，默认为 3
+    pub fn oov_level(mut self, oov_level: usize) -> TagOptions {
+        self.oov_level = oov_level;
+        self
+    }
+
+    /// 是否开启繁体转简体，默认为 false
+    pub fn t2s(mut self, t2s: bool) -> TagOptions {
+        self.t2s = t2s;
+        self
+    }
+
+    /// 是否转化特殊字符，针对回车、Tab 等特殊字符，默认为 false
+    pub fn special_char_conv(mut self, special_char_conv: bool) -> TagOptions {
+        self.special_char_conv = special_char_conv;
+        self
+    }
+
+    /// 检查参数是否在有效范围内
+    pub(crate) fn validate(&self) -> Result<()> {
+        if self.space_mode > 3 {
+            return Err(Error::InvalidArgument {
+                name: "space_mode".to_owned(),
+                message: format!("expected 0-3, got {}", self.space_mode),
+            });
+        }
+        if self.oov_level > 4 {
+            return Err(Error::InvalidArgument {
+                name: "oov_level".to_owned(),
+                message: format!("expected 0-4, got {}", self.oov_level),
+            });
+        }
+        Ok(())
+    }
+
+    /// 分词与词性标注接口的查询参数
+    pub(crate) fn params(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("space_mode", self.space_mode.to_string()),
+            ("oov_level", self.oov_level.to_string()),
+            ("t2s", if self.t2s { "1" } else { "0" }.to_owned()),
+            ("special_char_conv", if self.special_char_conv { "1" } else { "0" }.to_owned()),
+        ]
+    }
+}
+
+/// 命名实体识别接口的参数
+///
+/// 命名实体识别接口不支持繁体转简体，开启 ``t2s`` 后会先调用[分词与词性标注接口](http://docs.bosonnlp.com/tag.html)
+/// 将繁体转换为简体并分词，再将分词结果作为已分词的输入进行命名实体识别，每批文本会多发送一次请求
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct NerOptions {
+    pub(crate) sensitivity: usize,
+    pub(crate) segmented: bool,
+    pub(crate) t2s: bool,
+}
+
+impl Default for NerOptions {
+    fn default() -> NerOptions {
+        NerOptions {
+            sensitivity: 3,
+            segmented: false,
+            t2s: false,
+        }
+    }
+}
+
+impl NerOptions {
+    /// 准确率与召回率之间的平衡，设置成 1 能找到更多的实体，设置成 5 能以更高的精度寻找实体，默认为 3
+    pub fn sensitivity(mut self, sensitivity: usize) -> NerOptions {
+        self.sensitivity = sensitivity;
+        self
+    }
+
+    /// 输入是否已经为分词结果，默认为 false
+    pub fn segmented(mut self, segmented: bool) -> NerOptions {
+        self.segmented = segmented;
+        self
+    }
+
+    /// 是否开启繁体转简体，默认为 false。开启后使用分词接口的分词结果，``segmented`` 无效
+    pub fn t2s(mut self, t2s: bool) -> NerOptions {
+        self.t2s = t2s;
+        self
+    }
+}
+
+/// 关键词提取接口的参数
+///
+/// 与 [`NerOptions`](struct.NerOptions.html) 相同，开启 ``t2s`` 后会先调用分词与词性标注接口转换为简体并分词
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct KeywordsOptions {
+    pub(crate) top_k: usize,
+    pub(crate) segmented: bool,
+    pub(crate) t2s: bool,
+}
+
+impl Default for KeywordsOptions {
+    fn default() -> KeywordsOptions {
+        KeywordsOptions {
+            top_k: 100,
+            segmented: false,
+            t2s: false,
+        }
+    }
+}
+
+impl KeywordsOptions {
+    /// 返回结果的条数，1-100 有效，默认为 100
+    pub fn top_k(mut self, top_k: usize) -> KeywordsOptions {
+        self.top_k = top_k;
+        self
+    }
+
+    /// 输入是否已经进行了分词，默认为 false
+    pub fn segmented(mut self, segmented: bool) -> KeywordsOptions {
+        self.segmented = segmented;
+        self
+    }
+
+    /// 是否开启繁体转简体，默认为 false。开启后使用分词接口的分词结果，``segmented`` 无效
+    pub fn t2s(mut self, t2s: bool) -> KeywordsOptions {
+        self.t2s = t2s;
+        self
+    }
+}
+
+/// 单次请求的请求体压缩方式
+///
+/// 未启用 `compression` feature 时请求体总是不压缩
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub enum CompressionMode {
+    /// 按实例的 ``compress`` 和 ``compress_threshold`` 设置决定，默认值
+    #[default]
+    Auto,
+    /// 总是压缩请求体
+    Always,
+    /// 不压缩请求体，适用于已知很小或难以压缩的请求
+    Never,
+}
+
+impl CompressionMode {
+    /// 长度为 ``len`` 的请求体是否需要压缩
+    pub(crate) fn should_compress(self, compress: bool, threshold: usize, len: usize) -> bool {
+        match self {
+            CompressionMode::Auto => compress && len > threshold,
+            CompressionMode::Always => true,
+            CompressionMode::Never => false,
+        }
+    }
+}
@@ -1,7 +1,800 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::io::Write;
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+use serde::Serialize;
+use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
 use uuid::Uuid;
 
+use crate::errors::*;
+
+/// 情感分析模型
+///
+/// # 使用示例
+///
+/// ```
+/// extern crate bosonnlp;
+///
+/// use bosonnlp::SentimentModel;
+///
+/// fn main() {
+///     assert_eq!("general", SentimentModel::General.as_ref());
+///     assert_eq!("weibo", SentimentModel::Weibo.to_string());
+///     assert_eq!(SentimentModel::Food, SentimentModel::from("food"));
+///     assert_eq!(SentimentModel::Custom("mymodel".to_owned()), SentimentModel::from("mymodel"));
+/// }
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum SentimentModel {
+    /// 通用
+    General,
+    /// 汽车
+    Auto,
+    /// 电商
+    Ecommerce,
+    /// 新闻
+    News,
+    /// 微博
+    Weibo,
+    /// 餐饮
+    Food,
+    /// 酒店
+    Hotel,
+    /// 厨具
+    Kitchen,
+    /// 自定义模型
+    Custom(String),
+}
+
+impl SentimentModel {
+    /// `BosonNLP` 提供的所有情感分析模型，不包括自定义模型
+    ///
+    /// # 使用示例
+    ///
+    /// ```
+    /// extern crate bosonnlp;
+    ///
+    /// use bosonnlp::{sentiment_models, SentimentModel};
+    ///
+    /// fn main() {
+    ///     assert!(SentimentModel::all().contains(&SentimentModel::General));
+    ///     assert!(!sentiment_models().is_empty());
+    ///     assert!(sentiment_models().contains(&"general"));
+    ///     let names: Vec<&str> = SentimentModel::all().iter().map(|m| m.as_ref()).collect();
+    ///     assert_eq!(sentiment_models(), &names[..]);
+    /// }
+    /// ```
+    pub fn all() -> &'static [SentimentModel] {
+        const ALL: &[SentimentModel] = &[
+            SentimentModel::General,
+            SentimentModel::Auto,
+            SentimentModel::Ecommerce,
+            SentimentModel::News,
+            SentimentModel::Weibo,
+            SentimentModel::Food,
+            SentimentModel::Hotel,
+            SentimentModel::Kitchen,
+        ];
+        ALL
+    }
+}
+
+/// `BosonNLP` 提供的所有情感分析模型名称，来自[情感分析接口文档](http://docs.bosonnlp.com/sentiment.html)
+pub const fn sentiment_models() -> &'static [&'static str] {
+    &["general", "auto", "ecommerce", "news", "weibo", "food", "hotel", "kitchen"]
+}
+
+impl AsRef<str> for SentimentModel {
+    fn as_ref(&self) -> &str {
+        match *self {
+            SentimentModel::General => "general",
+            SentimentModel::Auto => "auto",
+            SentimentModel::Ecommerce => "ecommerce",
+            SentimentModel::News => "news",
+            SentimentModel::Weibo => "weibo",
+            SentimentModel::Food => "food",
+            SentimentModel::Hotel => "hotel",
+            SentimentModel::Kitchen => "kitchen",
+            SentimentModel::Custom(ref model) => model,
+        }
+    }
+}
+
+impl fmt::Display for SentimentModel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_ref())
+    }
+}
+
+impl<'a> From<&'a str> for SentimentModel {
+    fn from(model: &'a str) -> SentimentModel {
+        match model {
+            "general" => SentimentModel::General,
+            "auto" => SentimentModel::Auto,
+            "ecommerce" => SentimentModel::Ecommerce,
+            "news" => SentimentModel::News,
+            "weibo" => SentimentModel::Weibo,
+            "food" => SentimentModel::Food,
+            "hotel" => SentimentModel::Hotel,
+            "kitchen" => SentimentModel::Kitchen,
+            _ => SentimentModel::Custom(model.to_owned()),
+        }
+    }
+}
+
+impl From<String> for SentimentModel {
+    fn from(model: String) -> SentimentModel {
+        SentimentModel::from(model.as_str())
+    }
+}
+
+/// 解析并检查模型名称，自定义模型名称只能由字母、数字、``_``、``-`` 和 ``.`` 组成，
+/// 否则返回 `Error::InvalidArgument`
+///
+/// 已有的 `From<&str>` 不做检查，因此无法再实现 `TryFrom<&str>`，需要检查时使用 ``parse``
+///
+/// # 使用示例
+///
+/// ```
+/// extern crate bosonnlp;
+///
+/// use bosonnlp::{Error, SentimentModel};
+///
+/// fn main() {
+///     assert_eq!(SentimentModel::Food, "food".parse().unwrap());
+///     assert_eq!(SentimentModel::Custom("my_model-2".to_owned()), "my_model-2".parse().unwrap());
+///     match "my model".parse::<SentimentModel>() {
+///         Err(Error::InvalidArgument { ref name, .. }) => assert_eq!("model", name),
+///         rs => panic!("unexpected result {:?}", rs),
+///     }
+///     assert!("".parse::<SentimentModel>().is_err());
+///     assert!("food&x=1".parse::<SentimentModel>().is_err());
+/// }
+/// ```
+impl FromStr for SentimentModel {
+    type Err = Error;
+
+    fn from_str(model: &str) -> Result<SentimentModel> {
+        let valid = !model.is_empty()
+            && model.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-' || c == '.');
+        if !valid {
+            return Err(Error::InvalidArgument {
+                name: "model".to_owned(),
+                message: format!("invalid sentiment model name {:?}", model),
+            });
+        }
+        Ok(SentimentModel::from(model))
+    }
+}
+
+/// 情感倾向
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum SentimentLabel {
+    /// 正面
+    Positive,
+    /// 负面
+    Negative,
+    /// 中性
+    Neutral,
+}
+
+/// 情感分析结果
+///
+/// API 返回的 ``[positive, negative]`` 数组会按顺序映射到对应字段，序列化时也输出为数组。
+/// 部分模型会返回 ``[positive, negative, neutral]`` 三个概率，第三个概率保存在 ``neutral`` 中，
+/// 之后多余的元素会被忽略
+///
+/// # 使用示例
+///
+/// ```
+/// extern crate bosonnlp;
+/// extern crate serde_json;
+///
+/// use bosonnlp::{Sentiment, SentimentLabel};
+///
+/// fn main() {
+///     let sentiment: Sentiment = serde_json::from_str("[0.7, 0.3]").unwrap();
+///     assert_eq!(Sentiment::new(0.7, 0.3), sentiment);
+///     assert_eq!(None, sentiment.neutral);
+///     assert_eq!(vec![0.7, 0.3], sentiment.probabilities());
+///
+///     let sentiment: Sentiment = serde_json::from_str("[0.2, 0.1, 0.7]").unwrap();
+///     assert_eq!(Some(0.7), sentiment.neutral);
+///     assert_eq!(vec![0.2, 0.1, 0.7], sentiment.probabilities());
+///     assert_eq!(SentimentLabel::Neutral, sentiment.label());
+///     assert_eq!("[0.2,0.1,0.7]", serde_json::to_string(&sentiment).unwrap());
+/// }
+/// ```
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(into = "Vec<f32>")]
+pub struct Sentiment {
+    /// 正面情感的概率
+    pub positive: f32,
+    /// 负面情感的概率
+    pub negative: f32,
+    /// 中性情感的概率，只有三分类模型会返回
+    pub neutral: Option<f32>,
+}
+
+impl<'de> Deserialize<'de> for Sentiment {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> ::std::result::Result<Sentiment, D::Error> {
+        deserializer.deserialize_seq(SentimentVisitor)
+    }
+}
+
+/// 读取正面、负面和可选的中性概率，忽略之后多余的元素
+struct SentimentVisitor;
+
+impl<'de> Visitor<'de> for SentimentVisitor {
+    type Value = Sentiment;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("an array with at least 2 probabilities")
+    }
+
+    fn visit_seq<S: SeqAccess<'de>>(self, mut seq: S) -> ::std::result::Result<Sentiment, S::Error> {
+        let positive = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let negative = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(1, &self))?;
+        let neutral = seq.next_element::<serde_json::Value>()?.and_then(|v| v.as_f64()).map(|v| v as f32);
+        while seq.next_element::<de::IgnoredAny>()?.is_some() {}
+        Ok(Sentiment { positive, negative, neutral })
+    }
+}
+
+impl From<Sentiment> for (f32, f32) {
+    fn from(sentiment: Sentiment) -> (f32, f32) {
+        (sentiment.positive, sentiment.negative)
+    }
+}
+
+impl From<Sentiment> for Vec<f32> {
+    fn from(sentiment: Sentiment) -> Vec<f32> {
+        sentiment.probabilities()
+    }
+}
+
+impl Sentiment {
+    /// 使用正面和负面概率创建二分类的情感分析结果
+    pub fn new(positive: f32, negative: f32) -> Sentiment {
+        Sentiment { positive, negative, neutral: None }
+    }
+
+    /// 按 API 返回顺序排列的所有概率
+    pub fn probabilities(&self) -> Vec<f32> {
+        let mut probabilities = vec![self.positive, self.negative];
+        probabilities.extend(self.neutral);
+        probabilities
+    }
+
+    /// 根据概率较大的一方判断情感倾向，两者之差小于 0.1 时视为中性。
+    /// 三分类结果中中性概率最大时同样视为中性
+    ///
+    /// # 使用示例
+    ///
+    /// ```
+    /// extern crate bosonnlp;
+    ///
+    /// use bosonnlp::{Sentiment, SentimentLabel};
+    ///
+    /// fn main() {
+    ///     assert_eq!(SentimentLabel::Positive, Sentiment::new(0.9, 0.1).label());
+    ///     assert_eq!(SentimentLabel::Neutral, Sentiment::new(0.52, 0.48).label());
+    ///     let sentiment = Sentiment { positive: 0.1, negative: 0.3, neutral: Some(0.6) };
+    ///     assert_eq!(SentimentLabel::Neutral, sentiment.label());
+    /// }
+    /// ```
+    pub fn label(&self) -> SentimentLabel {
+        if let Some(neutral) = self.neutral {
+            if neutral > self.positive && neutral > self.negative {
+                return SentimentLabel::Neutral;
+            }
+        }
+        let diff = self.positive - self.negative;
+        if diff.abs() < 0.1 {
+            SentimentLabel::Neutral
+        } else if diff > 0.0 {
+            SentimentLabel::Positive
+        } else {
+            SentimentLabel::Negative
+        }
+    }
+
+    /// 各项概率的误差均不超过 ``epsilon`` 时视为相等，用于比较浮点数结果
+    ///
+    /// # 使用示例
+    ///
+    /// ```
+    /// extern crate bosonnlp;
+    ///
+    /// use bosonnlp::Sentiment;
+    ///
+    /// fn main() {
+    ///     let sentiment = Sentiment::new(0.7 + 0.2, 0.1);
+    ///     assert!(sentiment.approx_eq(&Sentiment::new(0.9, 0.1), 1e-6));
+    ///     assert!(!sentiment.approx_eq(&Sentiment::new(0.8, 0.2), 1e-6));
+    ///     let neutral = Sentiment { neutral: Some(0.0), ..sentiment.clone() };
+    ///     assert!(!sentiment.approx_eq(&neutral, 1e-6));
+    /// }
+    /// ```
+    pub fn approx_eq(&self, other: &Sentiment, epsilon: f32) -> bool {
+        let neutral_eq = match (self.neutral, other.neutral) {
+            (Some(a), Some(b)) => (a - b).abs() <= epsilon,
+            (None, None) => true,
+            _ => false,
+        };
+        (self.positive - other.positive).abs() <= epsilon && (self.negative - other.negative).abs() <= epsilon && neutral_eq
+    }
+}
+
+/// 读取数组的前两个元素，忽略多余的元素，API 在数组末尾增加新的元素时仍然可以解析
+struct PairVisitor<A, B>(PhantomData<(A, B)>);
+
+impl<'de, A: Deserialize<'de>, B: Deserialize<'de>> Visitor<'de> for PairVisitor<A, B> {
+    type Value = (A, B);
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("an array with at least 2 elements")
+    }
+
+    fn visit_seq<S: SeqAccess<'de>>(self, mut seq: S) -> ::std::result::Result<(A, B), S::Error> {
+        let first = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let second = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(1, &self))?;
+        while seq.next_element::<de::IgnoredAny>()?.is_some() {}
+        Ok((first, second))
+    }
+}
+
+/// 定义词性标注枚举及其与标注代码之间的转换
+macro_rules! pos_tags {
+    ($($(#[$attr:meta])* $variant:ident => $code:expr,)*) => {
+        /// 词性标注
+        ///
+        /// 参见 [`BosonNLP` 词性标注说明](http://docs.bosonnlp.com/tag_rule.html)
+        ///
+        /// # 使用示例
+        ///
+        /// ```
+        /// extern crate bosonnlp;
+        ///
+        /// use bosonnlp::PosTag;
+        ///
+        /// fn main() {
+        ///     assert_eq!(PosTag::PlaceName, PosTag::from_code("ns"));
+        ///     assert_eq!("ns", PosTag::PlaceName.code());
+        ///     assert_eq!(PosTag::Other("xyz".to_owned()), PosTag::from_code("xyz"));
+        /// }
+        /// ```
+        #[derive(Debug, Clone, Eq, PartialEq, Hash)]
+        pub enum PosTag {
+            $($(#[$attr])* $variant,)*
+            /// 未知的词性标注
+            Other(String),
+        }
+
+        impl PosTag {
+            /// 根据标注代码获取对应的词性，未知的代码返回 `PosTag::Other`
+            pub fn from_code(code: &str) -> PosTag {
+                match code {
+                    $($code => PosTag::$variant,)*
+                    _ => PosTag::Other(code.to_owned()),
+                }
+            }
+
+            /// 词性对应的标注代码
+            pub fn code(&self) -> &str {
+                match *self {
+                    $(PosTag::$variant => $code,)*
+                    PosTag::Other(ref code) => code,
+                }
+            }
+        }
+    };
+}
+
+pos_tags! {
+    /// 名词
+    Noun => "n",
+    /// 人名
+    PersonName => "nr",
+    /// 汉语姓氏
+    Surname => "nr1",
+    /// 音译人名
+    ForeignPersonName => "nrf",
+    /// 地名
+    PlaceName => "ns",
+    /// 音译地名
+    ForeignPlaceName => "nsf",
+    /// 组织机构名
+    Organization => "nt",
+    /// 其它专名
+    OtherProperNoun => "nz",
+    /// 名词性惯用语
+    NounIdiom => "nl",
+    /// 名词性语素
+    NounMorpheme => "ng",
+    /// 时间词
+    Time => "t",
+    /// 时间词性语素
+    TimeMorpheme => "tg",
+    /// 处所词
+    Place => "s",
+    /// 方位词
+    Direction => "f",
+    /// 动词
+    Verb => "v",
+    /// 副动词
+    AdverbialVerb => "vd",
+    /// 动词“是”
+    VerbShi => "vshi",
+    /// 动词“有”
+    VerbYou => "vyou",
+    /// 不及物动词
+    IntransitiveVerb => "vi",
+    /// 动词性惯用语
+    VerbIdiom => "vl",
+    /// 动词性语素
+    VerbMorpheme => "vg",
+    /// 形容词
+    Adjective => "a",
+    /// 副形词
+    AdverbialAdjective => "ad",
+    /// 名形词
+    NominalAdjective => "an",
+    /// 形容词性语素
+    AdjectiveMorpheme => "ag",
+    /// 形容词性惯用语
+    AdjectiveIdiom => "al",
+    /// 区别词
+    Distinguishing => "b",
+    /// 区别词性惯用语
+    DistinguishingIdiom => "bl",
+    /// 状态词
+    Status => "z",
+    /// 代词
+    Pronoun => "r",
+    /// 人称代词
+    PersonalPronoun => "rr",
+    /// 指示代词
+    DemonstrativePronoun => "rz",
+    /// 谓词性指示代词
+    PredicativeDemonstrativePronoun => "rzv",
+    /// 疑问代词
+    InterrogativePronoun => "ry",
+    /// 数词
+    Numeral => "m",
+    /// 量词
+    Quantifier => "q",
+    /// 副词
+    Adverb => "d",
+    /// 副词性惯用语
+    AdverbIdiom => "dl",
+    /// 介词
+    Preposition => "p",
+    /// 介词“把”
+    PrepositionBa => "pba",
+    /// 介词“被”
+    PrepositionBei => "pbei",
+    /// 连词
+    Conjunction => "c",
+    /// 助词
+    Auxiliary => "u",
+    /// 助词“着”
+    AuxiliaryZhe => "uzhe",
+    /// 助词“了”、“喽”
+    AuxiliaryLe => "ule",
+    /// 助词“过”
+    AuxiliaryGuo => "uguo",
+    /// 助词“的”、“地”、“得”
+    AuxiliaryDe => "ude",
+    /// 助词“所”
+    AuxiliarySuo => "usuo",
+    /// 助词“等”、“等等”
+    AuxiliaryDeng => "udeng",
+    /// 助词“一样”、“似的”
+    AuxiliaryYiyang => "uyy",
+    /// 助词“的话”
+    AuxiliaryDehua => "udh",
+    /// 助词“之”
+    AuxiliaryZhi => "uzhi",
+    /// 助词“连”
+    AuxiliaryLian => "ulian",
+    /// 叹词
+    Interjection => "e",
+    /// 语气词
+    Modal => "y",
+    /// 拟声词
+    Onomatopoeia => "o",
+    /// 前缀
+    Prefix => "h",
+    /// 后缀
+    Suffix => "k",
+    /// 字符串
+    String => "nx",
+    /// 标点符号
+    Punctuation => "w",
+    /// 左括号
+    LeftParenthesis => "wkz",
+    /// 右括号
+    RightParenthesis => "wky",
+    /// 左引号
+    LeftQuote => "wyz",
+    /// 右引号
+    RightQuote => "wyy",
+    /// 句号
+    Period => "wj",
+    /// 问号
+    QuestionMark => "ww",
+    /// 叹号
+    ExclamationMark => "wt",
+    /// 逗号
+    Comma => "wd",
+    /// 分号
+    Semicolon => "wf",
+    /// 顿号
+    EnumerationComma => "wn",
+    /// 冒号
+    Colon => "wm",
+    /// 省略号
+    Ellipsis => "ws",
+    /// 破折号
+    Dash => "wp",
+    /// 百分号千分号
+    Percent => "wb",
+    /// 单位符号
+    Unit => "wh",
+    /// 电子邮件
+    Email => "email",
+    /// 电话号码
+    Telephone => "tel",
+    /// 身份证号
+    IdNumber => "id",
+    /// IP 地址
+    IpAddress => "ip",
+    /// 网址
+    Url => "url",
+}
+
+impl fmt::Display for PosTag {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+/// 定义依存关系枚举及其与关系代码之间的转换
+macro_rules! dep_roles {
+    ($($(#[$attr:meta])* $variant:ident => $code:expr,)*) => {
+        /// 依存文法分析结果中的依存关系
+        ///
+        /// 参见 [`BosonNLP` 依存文法分析说明](http://docs.bosonnlp.com/depparser.html)
+        ///
+        /// # 使用示例
+        ///
+        /// ```
+        /// extern crate bosonnlp;
+        ///
+        /// use bosonnlp::DepRole;
+        ///
+        /// fn main() {
+        ///     assert_eq!(DepRole::Subject, DepRole::from_code("SBJ"));
+        ///     assert_eq!("SBJ", DepRole::Subject.code());
+        ///     assert_eq!(DepRole::Other("XYZ".to_owned()), DepRole::from_code("XYZ"));
+        /// }
+        /// ```
+        #[derive(Debug, Clone, Eq, PartialEq, Hash)]
+        pub enum DepRole {
+            $($(#[$attr])* $variant,)*
+            /// 未知的依存关系
+            Other(String),
+        }
+
+        impl DepRole {
+            /// 根据关系代码获取对应的依存关系，未知的代码返回 `DepRole::Other`
+            pub fn from_code(code: &str) -> DepRole {
+                match code {
+                    $($code => DepRole::$variant,)*
+                    _ => DepRole::Other(code.to_owned()),
+                }
+            }
+
+            /// 依存关系对应的关系代码
+            pub fn code(&self) -> &str {
+                match *self {
+                    $(DepRole::$variant => $code,)*
+                    DepRole::Other(ref code) => code,
+                }
+            }
+        }
+    };
+}
+
+dep_roles! {
+    /// 核心词
+    Root => "ROOT",
+    /// 主语成分
+    Subject => "SBJ",
+    /// 宾语成分
+    Object => "OBJ",
+    /// 标点符号
+    Punctuation => "PU",
+    /// 时间成分
+    Time => "TMP",
+    /// 位置成分
+    Location => "LOC",
+    /// 方式成分
+    Manner => "MNR",
+    /// 介宾成分
+    PrepositionObject => "POBJ",
+    /// 状语
+    Adverbial => "ADV",
+    /// 补语
+    Complement => "COMP",
+    /// 名词修饰语
+    NounModifier => "NMOD",
+    /// 形容词修饰语
+    AdjectiveModifier => "AMOD",
+    /// 动词修饰语
+    VerbModifier => "VMOD",
+    /// 时间修饰语
+    TimeModifier => "TMOD",
+    /// 限定词修饰语
+    DeterminerModifier => "DMOD",
+    /// 关系从句
+    RelativeClause => "RELC",
+    /// 并列成分
+    Conjunct => "CJT",
+}
+
+impl fmt::Display for DepRole {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+/// 新闻分类
+///
+/// # 使用示例
+///
+/// ```
+/// extern crate bosonnlp;
+///
+/// use bosonnlp::NewsCategory;
+///
+/// fn main() {
+///     assert_eq!(NewsCategory::Sports, NewsCategory::from(0));
+///     assert_eq!(NewsCategory::Military, NewsCategory::from(5));
+///     assert_eq!("军事", NewsCategory::Military.to_string());
+///     assert_eq!(NewsCategory::Unknown(42), NewsCategory::from(42));
+/// }
+/// ```
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum NewsCategory {
+    /// 体育
+    Sports,
+    /// 教育
+    Education,
+    /// 财经
+    Finance,
+    /// 社会
+    Society,
+    /// 娱乐
+    Entertainment,
+    /// 军事
+    Military,
+    /// 国内
+    Domestic,
+    /// 科技
+    Technology,
+    /// 互联网
+    Internet,
+    /// 房产
+    RealEstate,
+    /// 国际
+    International,
+    /// 女人
+    Women,
+    /// 汽车
+    Auto,
+    /// 游戏
+    Game,
+    /// 未知分类
+    Unknown(usize),
+}
+
+impl From<usize> for NewsCategory {
+    fn from(index: usize) -> NewsCategory {
+        match index {
+            0 => NewsCategory::Sports,
+            1 => NewsCategory::Education,
+            2 => NewsCategory::Finance,
+            3 => NewsCategory::Society,
+            4 => NewsCategory::Entertainment,
+            5 => NewsCategory::Military,
+            6 => NewsCategory::Domestic,
+            7 => NewsCategory::Technology,
+            8 => NewsCategory::Internet,
+            9 => NewsCategory::RealEstate,
+            10 => NewsCategory::International,
+            11 => NewsCategory::Women,
+            12 => NewsCategory::Auto,
+            13 => NewsCategory::Game,
+            _ => NewsCategory::Unknown(index),
+        }
+    }
+}
+
+impl fmt::Display for NewsCategory {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            NewsCategory::Sports => f.write_str("体育"),
+            NewsCategory::Education => f.write_str("教育"),
+            NewsCategory::Finance => f.write_str("财经"),
+            NewsCategory::Society => f.write_str("社会"),
+            NewsCategory::Entertainment => f.write_str("娱乐"),
+            NewsCategory::Military => f.write_str("军事"),
+            NewsCategory::Domestic => f.write_str("国内"),
+            NewsCategory::Technology => f.write_str("科技"),
+            NewsCategory::Internet => f.write_str("互联网"),
+            NewsCategory::RealEstate => f.write_str("房产"),
+            NewsCategory::International => f.write_str("国际"),
+            NewsCategory::Women => f.write_str("女人"),
+            NewsCategory::Auto => f.write_str("汽车"),
+            NewsCategory::Game => f.write_str("游戏"),
+            NewsCategory::Unknown(index) => write!(f, "未知分类 {}", index),
+        }
+    }
+}
+
+/// 关键词提取和语义联想结果
+///
+/// API 返回的 ``[score, word]`` 数组会按顺序映射到对应字段，序列化时也输出为数组，
+/// 数组中多余的元素会被忽略
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(into = "(f32, String)")]
+pub struct ScoredWord {
+    /// 权重或相似度
+    pub score: f32,
+    /// 词
+    pub word: String,
+}
+
+impl<'de> Deserialize<'de> for ScoredWord {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> ::std::result::Result<ScoredWord, D::Error> {
+        let (score, word) = deserializer.deserialize_seq(PairVisitor(PhantomData))?;
+        Ok(ScoredWord { score, word })
+    }
+}
+
+impl From<ScoredWord> for (f32, String) {
+    fn from(word: ScoredWord) -> (f32, String) {
+        (word.score, word.word)
+    }
+}
+
+impl ScoredWord {
+    /// 词相同且权重的误差不超过 ``epsilon`` 时视为相等，用于比较浮点数结果
+    ///
+    /// # 使用示例
+    ///
+    /// ```
+    /// extern crate bosonnlp;
+    ///
+    /// use bosonnlp::ScoredWord;
+    ///
+    /// fn main() {
+    ///     let word = ScoredWord { score: 0.1 + 0.2, word: "天气".to_owned() };
+    ///     assert!(word.approx_eq(&ScoredWord { score: 0.3, word: "天气".to_owned() }, 1e-6));
+    ///     assert!(!word.approx_eq(&ScoredWord { score: 0.3, word: "天空".to_owned() }, 1e-6));
+    /// }
+    /// ```
+    pub fn approx_eq(&self, other: &ScoredWord, epsilon: f32) -> bool {
+        self.word == other.word && (self.score - other.score).abs() <= epsilon
+    }
+}
+
 /// 依存文法
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub struct Dependency {
     pub head: Vec<isize>,
     pub role: Vec<String>,
@@ -9,8 +802,216 @@ pub struct Dependency {
     pub word: Vec<String>,
 }
 
+impl Dependency {
+    /// 将 ``role`` 中的关系代码解析为 `DepRole`，原始的关系代码仍保留在 ``role`` 中
+    ///
+    /// # 使用示例
+    ///
+    /// ```
+    /// extern crate bosonnlp;
+    /// extern crate serde_json;
+    ///
+    /// use bosonnlp::{Dependency, DepRole};
+    ///
+    /// fn main() {
+    ///     let dep: Dependency = serde_json::from_str(
+    ///         r#"{"head":[2,2,-1,2],"role":["TMOD","SBJ","ROOT","PU"],"tag":["NT","NN","VA","PU"],"word":["今天","天气","好","。"]}"#
+    ///     ).unwrap();
+    ///     assert_eq!(
+    ///         vec![DepRole::TimeModifier, DepRole::Subject, DepRole::Root, DepRole::Punctuation],
+    ///         dep.roles()
+    ///     );
+    ///     assert_eq!("TMOD", dep.role[0]);
+    /// }
+    /// ```
+    pub fn roles(&self) -> Vec<DepRole> {
+        self.role.iter().map(|role| DepRole::from_code(role)).collect()
+    }
+
+    /// 根节点的下标，即 ``head`` 为 -1 的词
+    pub fn root(&self) -> Option<usize> {
+        self.head.iter().position(|&h| h == -1)
+    }
+
+    /// 以第 ``idx`` 个词为父节点的所有词的下标
+    pub fn children(&self, idx: usize) -> Vec<usize> {
+        self.head
+            .iter()
+            .enumerate()
+            .filter(|&(_, &h)| h >= 0 && h as usize == idx)
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// 检查 ``head``、``role``、``tag`` 和 ``word`` 长度一致，且 ``head`` 均为 -1 或有效的下标，
+    /// 否则返回 `Error::InvalidResponse`
+    ///
+    /// ``depparser`` 系列接口在返回结果前会进行该检查
+    ///
+    /// # 使用示例
+    ///
+    /// ```
+    /// extern crate bosonnlp;
+    ///
+    /// use bosonnlp::Dependency;
+    ///
+    /// fn main() {
+    ///     let dep = Dependency {
+    ///         head: vec![2, 2, -1],
+    ///         role: vec!["TMP".to_owned(), "SBJ".to_owned(), "ROOT".to_owned()],
+    ///         tag: vec!["NT".to_owned(), "NN".to_owned(), "VA".to_owned()],
+    ///         word: vec!["今天".to_owned(), "天气".to_owned(), "好".to_owned()],
+    ///     };
+    ///     assert!(dep.validate().is_ok());
+    ///
+    ///     let mut truncated = dep.clone();
+    ///     truncated.tag.pop();
+    ///     assert!(truncated.validate().is_err());
+    ///
+    ///     let out_of_range = Dependency { head: vec![2, 3, -1], ..dep };
+    ///     assert!(out_of_range.validate().is_err());
+    /// }
+    /// ```
+    pub fn validate(&self) -> Result<()> {
+        let len = self.word.len();
+        if self.head.len() != len || self.role.len() != len || self.tag.len() != len {
+            return Err(Error::InvalidResponse(format!(
+                "dependency arrays have different lengths: head {}, role {}, tag {}, word {}",
+                self.head.len(), self.role.len(), self.tag.len(), len
+            )));
+        }
+        if let Some(&h) = self.head.iter().find(|&&h| h < -1 || h >= len as isize) {
+            return Err(Error::InvalidResponse(format!("dependency head {} out of range", h)));
+        }
+        Ok(())
+    }
+
+    /// 将依存文法分析结果转换为树
+    ///
+    /// 数组长度不一致、``head`` 下标越界或结果不是一棵树时返回 `Error::InvalidResponse`
+    ///
+    /// # 使用示例
+    ///
+    /// ```
+    /// extern crate bosonnlp;
+    ///
+    /// use bosonnlp::Dependency;
+    ///
+    /// fn main() {
+    ///     let dep = Dependency {
+    ///         head: vec![2, 2, -1],
+    ///         role: vec!["TMP".to_owned(), "SBJ".to_owned(), "ROOT".to_owned()],
+    ///         tag: vec!["NT".to_owned(), "NN".to_owned(), "VA".to_owned()],
+    ///         word: vec!["今天".to_owned(), "天气".to_owned(), "好".to_owned()],
+    ///     };
+    ///     assert_eq!(Some(2), dep.root());
+    ///     assert_eq!(vec![0, 1], dep.children(2));
+    ///     assert!(dep.children(0).is_empty());
+    ///
+    ///     let tree = dep.to_tree().unwrap();
+    ///     assert_eq!("好", tree.word);
+    ///     assert_eq!("ROOT", tree.role);
+    ///     let children: Vec<&str> = tree.children.iter().map(|c| c.word.as_str()).collect();
+    ///     assert_eq!(vec!["今天", "天气"], children);
+    ///     assert_eq!("SBJ", tree.children[1].role);
+    ///
+    ///     let broken = Dependency { head: vec![2, 5, -1], ..dep };
+    ///     assert!(broken.to_tree().is_err());
+    /// }
+    /// ```
+    pub fn to_tree(&self) -> Result<DepNode> {
+        self.validate()?;
+        let len = self.word.len();
+        let roots = self.head.iter().filter(|&&h| h == -1).count();
+        if roots != 1 {
+            return Err(Error::InvalidResponse(format!("expected 1 dependency root, got {}", roots)));
+        }
+        let root = self.build_node(self.root().unwrap());
+        if root.size() != len {
+            return Err(Error::InvalidResponse("dependency heads contain a cycle".to_owned()));
+        }
+        Ok(root)
+    }
+
+    fn build_node(&self, idx: usize) -> DepNode {
+        DepNode {
+            index: idx,
+            word: self.word[idx].clone(),
+            role: self.role[idx].clone(),
+            tag: self.tag[idx].clone(),
+            children: self.children(idx).into_iter().map(|i| self.build_node(i)).collect(),
+        }
+    }
+}
+
+/// 依存文法树的节点
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DepNode {
+    /// 在分词结果中的下标
+    pub index: usize,
+    pub word: String,
+    pub role: String,
+    pub tag: String,
+    pub children: Vec<DepNode>,
+}
+
+impl DepNode {
+    /// 以该节点为根的子树中的节点数量
+    fn size(&self) -> usize {
+        1 + self.children.iter().map(DepNode::size).sum::<usize>()
+    }
+}
+
+impl fmt::Display for DepNode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}/{}", self.word, self.role)?;
+        if !self.children.is_empty() {
+            f.write_str("(")?;
+            for (i, child) in self.children.iter().enumerate() {
+                if i > 0 {
+                    f.write_str(" ")?;
+                }
+                write!(f, "{}", child)?;
+            }
+            f.write_str(")")?;
+        }
+        Ok(())
+    }
+}
+
+/// 输出为 ``词/依存关系(子节点 ...)`` 形式的依存文法树，无法构建依存文法树时依次输出 ``词/依存关系``
+///
+/// # 使用示例
+///
+/// ```
+/// extern crate bosonnlp;
+///
+/// use bosonnlp::Dependency;
+///
+/// fn main() {
+///     let dep = Dependency {
+///         head: vec![2, 2, -1],
+///         role: vec!["TMOD", "SBJ", "ROOT"].into_iter().map(|r| r.to_owned()).collect(),
+///         tag: vec!["NT", "NN", "VA"].into_iter().map(|t| t.to_owned()).collect(),
+///         word: vec!["今天", "天气", "好"].into_iter().map(|w| w.to_owned()).collect(),
+///     };
+///     assert_eq!("好/ROOT(今天/TMOD 天气/SBJ)", dep.to_string());
+///
+///     let broken = Dependency { head: vec![2, 5, -1], ..dep };
+///     assert_eq!("今天/TMOD 天气/SBJ 好/ROOT", broken.to_string());
+/// }
+/// ```
+impl fmt::Display for Dependency {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Ok(tree) = self.to_tree() {
+            return write!(f, "{}", tree);
+        }
+        write_pairs(f, self.word.iter().zip(self.role.iter()))
+    }
+}
+
 /// 命名实体
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub struct NamedEntity {
     /// 命名实体结果
     pub entity: Vec<(usize, usize, String)>,
@@ -20,8 +1021,147 @@ pub struct NamedEntity {
     pub word: Vec<String>,
 }
 
+impl NamedEntity {
+    /// 将分词结果与解析后的词性一一对应
+    pub fn tagged_words(&self) -> Vec<(&str, PosTag)> {
+        tagged_words(&self.word, &self.tag)
+    }
+
+    /// 每个命名实体对应的原文及实体类型
+    ///
+    /// 实体的原文由 ``word[start..end]`` 拼接而成，超出分词结果范围的下标会被截断，
+    /// 截断后为空的实体会被跳过
+    ///
+    /// # 使用示例
+    ///
+    /// ```
+    /// extern crate bosonnlp;
+    ///
+    /// use bosonnlp::NamedEntity;
+    ///
+    /// fn main() {
+    ///     let words = ["微软", "XP", "操作系统", "今日", "正式", "退休"];
+    ///     let ner = NamedEntity {
+    ///         entity: vec![(0, 2, "product_name".to_owned()), (3, 4, "time".to_owned()), (5, 9, "time".to_owned()), (7, 9, "time".to_owned())],
+    ///         tag: vec!["nz", "nx", "n", "t", "ad", "v"].into_iter().map(|t| t.to_owned()).collect(),
+    ///         word: words.iter().map(|w| w.to_string()).collect(),
+    ///     };
+    ///     let texts = ner.entity_texts();
+    ///     assert_eq!(3, texts.len());
+    ///     assert_eq!(("微软XP".to_owned(), "product_name".to_owned()), texts[0]);
+    ///     assert_eq!(("今日".to_owned(), "time".to_owned()), texts[1]);
+    ///     assert_eq!(("退休".to_owned(), "time".to_owned()), texts[2]);
+    /// }
+    /// ```
+    pub fn entity_texts(&self) -> Vec<(String, String)> {
+        self.entity
+            .iter()
+            .filter_map(|&(start, end, ref entity_type)| {
+                let end = end.min(self.word.len());
+                if start >= end {
+                    return None;
+                }
+                Some((self.word[start..end].concat(), entity_type.clone()))
+            })
+            .collect()
+    }
+
+    /// 将分词、词性和命名实体结果按词对齐，属于命名实体的词带有实体类型
+    ///
+    /// 超出分词结果范围的实体会被截断，多个实体重叠时使用先出现的实体
+    ///
+    /// # 使用示例
+    ///
+    /// ```
+    /// extern crate bosonnlp;
+    ///
+    /// use bosonnlp::{NamedEntity, PosTag};
+    ///
+    /// fn main() {
+    ///     let ner = NamedEntity {
+    ///         entity: vec![(0, 2, "product_name".to_owned()), (1, 3, "org_name".to_owned()), (5, 9, "time".to_owned())],
+    ///         tag: vec!["nz", "nx", "n", "t", "ad", "v"].into_iter().map(|t| t.to_owned()).collect(),
+    ///         word: vec!["微软", "XP", "操作系统", "今日", "正式", "退休"].into_iter().map(|w| w.to_owned()).collect(),
+    ///     };
+    ///     let tokens = ner.annotated_tokens();
+    ///     assert_eq!(6, tokens.len());
+    ///     assert_eq!("微软", tokens[0].word);
+    ///     assert_eq!(PosTag::from_code("nz"), tokens[0].pos);
+    ///     assert_eq!(Some("product_name"), tokens[0].entity_type.as_deref());
+    ///     assert_eq!(Some("product_name"), tokens[1].entity_type.as_deref());
+    ///     assert_eq!(Some("org_name"), tokens[2].entity_type.as_deref());
+    ///     assert_eq!(None, tokens[3].entity_type);
+    ///     assert!(!tokens[4].is_entity());
+    ///     assert_eq!(Some("time"), tokens[5].entity_type.as_deref());
+    /// }
+    /// ```
+    pub fn annotated_tokens(&self) -> Vec<AnnotatedToken> {
+        let mut entity_types: Vec<Option<&String>> = vec![None; self.word.len()];
+        for &(start, end, ref entity_type) in &self.entity {
+            let end = end.min(self.word.len());
+            for slot in entity_types.iter_mut().take(end).skip(start) {
+                if slot.is_none() {
+                    *slot = Some(entity_type);
+                }
+            }
+        }
+        self.tagged_words()
+            .into_iter()
+            .zip(entity_types)
+            .map(|((word, pos), entity_type)| AnnotatedToken {
+                word: word.to_owned(),
+                pos,
+                entity_type: entity_type.cloned(),
+            })
+            .collect()
+    }
+}
+
+/// 带有词性和命名实体类型的词
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnnotatedToken {
+    /// 词
+    pub word: String,
+    /// 词性
+    pub pos: PosTag,
+    /// 所属命名实体的类型，不属于命名实体时为 `None`
+    pub entity_type: Option<String>,
+}
+
+impl AnnotatedToken {
+    /// 是否属于命名实体
+    pub fn is_entity(&self) -> bool {
+        self.entity_type.is_some()
+    }
+}
+
+/// 输出为 ``实体/类型`` 的序列
+///
+/// # 使用示例
+///
+/// ```
+/// extern crate bosonnlp;
+///
+/// use bosonnlp::NamedEntity;
+///
+/// fn main() {
+///     let ner = NamedEntity {
+///         entity: vec![(0, 2, "product_name".to_owned()), (3, 4, "time".to_owned())],
+///         tag: vec!["nz", "nx", "n", "t", "ad", "v"].into_iter().map(|t| t.to_owned()).collect(),
+///         word: vec!["微软", "XP", "操作系统", "今日", "正式", "退休"].into_iter().map(|w| w.to_owned()).collect(),
+///     };
+///     assert_eq!("微软XP/product_name 今日/time", ner.to_string());
+/// }
+/// ```
+impl fmt::Display for NamedEntity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let texts = self.entity_texts();
+        write_pairs(f, texts.iter().map(|(text, entity_type)| (text, entity_type)))
+    }
+}
+
 /// 词性标注
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub struct Tag {
     /// 词性标注结果
     pub tag: Vec<String>,
@@ -29,8 +1169,101 @@ pub struct Tag {
     pub word: Vec<String>,
 }
 
+impl Tag {
+    /// 将分词结果与解析后的词性一一对应
+    ///
+    /// # 使用示例
+    ///
+    /// ```
+    /// extern crate bosonnlp;
+    ///
+    /// use bosonnlp::{PosTag, Tag};
+    ///
+    /// fn main() {
+    ///     let tag = Tag {
+    ///         word: vec!["成都".to_owned(), "商报".to_owned(), "记者".to_owned(), "姚永忠".to_owned()],
+    ///         tag: vec!["ns".to_owned(), "n".to_owned(), "n".to_owned(), "nr".to_owned()],
+    ///     };
+    ///     let words = tag.tagged_words();
+    ///     assert_eq!(("成都", PosTag::PlaceName), words[0]);
+    ///     assert_eq!(("记者", PosTag::Noun), words[2]);
+    ///     assert_eq!(("姚永忠", PosTag::PersonName), words[3]);
+    /// }
+    /// ```
+    pub fn tagged_words(&self) -> Vec<(&str, PosTag)> {
+        tagged_words(&self.word, &self.tag)
+    }
+}
+
+/// 输出为 ``词/词性`` 的序列
+///
+/// # 使用示例
+///
+/// ```
+/// extern crate bosonnlp;
+///
+/// use bosonnlp::Tag;
+///
+/// fn main() {
+///     let tag = Tag {
+///         tag: vec!["ns", "n", "a"].into_iter().map(|t| t.to_owned()).collect(),
+///         word: vec!["成都", "天气", "好"].into_iter().map(|w| w.to_owned()).collect(),
+///     };
+///     assert_eq!("成都/ns 天气/n 好/a", tag.to_string());
+/// }
+/// ```
+impl fmt::Display for Tag {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_pairs(f, self.word.iter().zip(self.tag.iter()))
+    }
+}
+
+/// 输出以空格分隔的 ``a/b`` 序列
+fn write_pairs<'a, I>(f: &mut fmt::Formatter, pairs: I) -> fmt::Result
+where
+    I: Iterator<Item = (&'a String, &'a String)>,
+{
+    for (i, (a, b)) in pairs.enumerate() {
+        if i > 0 {
+            f.write_str(" ")?;
+        }
+        write!(f, "{}/{}", a, b)?;
+    }
+    Ok(())
+}
+
+/// 将分词结果与解析后的词性一一对应
+fn tagged_words<'a>(words: &'a [String], tags: &[String]) -> Vec<(&'a str, PosTag)> {
+    words
+        .iter()
+        .zip(tags.iter())
+        .map(|(word, tag)| (word.as_str(), PosTag::from_code(tag)))
+        .collect()
+}
+
 /// 时间转换结果
-#[derive(Debug, Deserialize, Clone)]
+///
+/// 序列化后的 JSON 与 API 返回的格式一致，可用于缓存结果
+///
+/// # 使用示例
+///
+/// ```
+/// extern crate bosonnlp;
+/// extern crate serde_json;
+///
+/// use bosonnlp::{ConvertedTime, Sentiment};
+///
+/// fn main() {
+///     let json = r#"{"timestamp":"2013-02-28 16:30:29","timedelta":null,"timespan":null,"type":"timestamp"}"#;
+///     let time: ConvertedTime = serde_json::from_str(json).unwrap();
+///     assert_eq!("timestamp", time.format);
+///     assert_eq!(json, serde_json::to_string(&time).unwrap());
+///
+///     let sentiment: Sentiment = serde_json::from_str("[0.75,0.25]").unwrap();
+///     assert_eq!("[0.75,0.25]", serde_json::to_string(&sentiment).unwrap());
+/// }
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub struct ConvertedTime {
     /// 时间点，ISO8601 格式的时间字符串
     pub timestamp: Option<String>,
@@ -44,10 +1277,301 @@ pub struct ConvertedTime {
     pub format: String,
 }
 
+/// 时间区间的组成方式
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum TimeSpanUnit {
+    /// 由时间点组成，对应 ``timespan_0``
+    Timestamp,
+    /// 由时间量组成，对应 ``timespan_1``
+    Timedelta,
+}
+
+/// 按 ``format`` 解析后的时间转换结果，参见 [`ConvertedTime::kind`](struct.ConvertedTime.html#method.kind)
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum TimeKind {
+    /// 时间点
+    Timestamp(String),
+    /// 时间量
+    Delta(String),
+    /// 时间区间
+    Span {
+        /// 起始时间
+        start: String,
+        /// 结束时间
+        end: String,
+        /// 区间的组成方式
+        unit: TimeSpanUnit,
+    },
+    /// ``format`` 无法识别或与实际返回的字段不一致
+    Unknown,
+}
+
+impl ConvertedTime {
+    /// 根据 ``format`` 取出对应字段的结果
+    ///
+    /// ``format`` 无法识别或对应的字段为空时返回 `TimeKind::Unknown`
+    ///
+    /// # 使用示例
+    ///
+    /// ```
+    /// extern crate bosonnlp;
+    /// extern crate serde_json;
+    ///
+    /// use bosonnlp::{ConvertedTime, TimeKind, TimeSpanUnit};
+    ///
+    /// fn main() {
+    ///     let time: ConvertedTime = serde_json::from_str(
+    ///         r#"{"timestamp":"2013-02-28 16:30:29","timedelta":null,"timespan":null,"type":"timestamp"}"#
+    ///     ).unwrap();
+    ///     assert_eq!(TimeKind::Timestamp("2013-02-28 16:30:29".to_owned()), time.kind());
+    ///
+    ///     let time: ConvertedTime = serde_json::from_str(
+    ///         r#"{"timestamp":null,"timedelta":"3 days, 0:00:00","timespan":null,"type":"timedelta"}"#
+    ///     ).unwrap();
+    ///     assert_eq!(TimeKind::Delta("3 days, 0:00:00".to_owned()), time.kind());
+    ///
+    ///     let time: ConvertedTime = serde_json::from_str(
+    ///         r#"{"timestamp":null,"timedelta":null,"timespan":["2013-02-28 00:00:00","2013-03-01 00:00:00"],"type":"timespan_0"}"#
+    ///     ).unwrap();
+    ///     assert_eq!(TimeKind::Span {
+    ///         start: "2013-02-28 00:00:00".to_owned(),
+    ///         end: "2013-03-01 00:00:00".to_owned(),
+    ///         unit: TimeSpanUnit::Timestamp,
+    ///     }, time.kind());
+    ///
+    ///     let time: ConvertedTime = serde_json::from_str(
+    ///         r#"{"timestamp":null,"timedelta":null,"timespan":["1:00:00","2:00:00"],"type":"timespan_1"}"#
+    ///     ).unwrap();
+    ///     assert_eq!(TimeKind::Span {
+    ///         start: "1:00:00".to_owned(),
+    ///         end: "2:00:00".to_owned(),
+    ///         unit: TimeSpanUnit::Timedelta,
+    ///     }, time.kind());
+    ///
+    ///     // format 与实际返回的字段不一致
+    ///     let time: ConvertedTime = serde_json::from_str(
+    ///         r#"{"timestamp":"2013-02-28 16:30:29","timedelta":null,"timespan":null,"type":"timedelta"}"#
+    ///     ).unwrap();
+    ///     assert_eq!(TimeKind::Unknown, time.kind());
+    /// }
+    /// ```
+    pub fn kind(&self) -> TimeKind {
+        let unit = match self.format.as_str() {
+            "timestamp" => {
+                return self.timestamp.clone().map_or(TimeKind::Unknown, TimeKind::Timestamp);
+            }
+            "timedelta" => {
+                return self.timedelta.clone().map_or(TimeKind::Unknown, TimeKind::Delta);
+            }
+            "timespan_0" => TimeSpanUnit::Timestamp,
+            "timespan_1" => TimeSpanUnit::Timedelta,
+            _ => return TimeKind::Unknown,
+        };
+        match self.timespan {
+            Some((ref start, ref end)) => TimeKind::Span {
+                start: start.clone(),
+                end: end.clone(),
+                unit,
+            },
+            None => TimeKind::Unknown,
+        }
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl ConvertedTime {
+    /// 将 ``timestamp`` 解析为 `chrono::NaiveDateTime`，需要启用 `chrono` feature
+    ///
+    /// # 使用示例
+    ///
+    /// ```
+    /// extern crate bosonnlp;
+    /// extern crate chrono;
+    ///
+    /// use chrono::NaiveDate;
+    /// use bosonnlp::ConvertedTime;
+    ///
+    /// fn main() {
+    ///     let time = ConvertedTime {
+    ///         timestamp: Some("2013-02-28 16:30:29".to_owned()),
+    ///         timedelta: None,
+    ///         timespan: None,
+    ///         format: "timestamp".to_owned(),
+    ///     };
+    ///     let expected = NaiveDate::from_ymd_opt(2013, 2, 28).unwrap().and_hms_opt(16, 30, 29).unwrap();
+    ///     assert_eq!(Some(expected), time.parsed_timestamp());
+    /// }
+    /// ```
+    pub fn parsed_timestamp(&self) -> Option<chrono::NaiveDateTime> {
+        self.timestamp.as_ref().and_then(|ts| parse_timestamp(ts))
+    }
+
+    /// 将 ``timedelta`` 解析为 `chrono::Duration`，支持 ``"xday,HH:MM:SS"`` 和 ``"HH:MM:SS"`` 两种格式，
+    /// 需要启用 `chrono` feature
+    ///
+    /// # 使用示例
+    ///
+    /// ```
+    /// extern crate bosonnlp;
+    /// extern crate chrono;
+    ///
+    /// use chrono::Duration;
+    /// use bosonnlp::ConvertedTime;
+    ///
+    /// fn main() {
+    ///     let mut time = ConvertedTime {
+    ///         timestamp: None,
+    ///         timedelta: Some("3 day,02:00:00".to_owned()),
+    ///         timespan: None,
+    ///         format: "timedelta".to_owned(),
+    ///     };
+    ///     assert_eq!(Some(Duration::days(3) + Duration::hours(2)), time.parsed_timedelta());
+    ///     time.timedelta = Some("00:30:15".to_owned());
+    ///     assert_eq!(Some(Duration::minutes(30) + Duration::seconds(15)), time.parsed_timedelta());
+    /// }
+    /// ```
+    pub fn parsed_timedelta(&self) -> Option<chrono::Duration> {
+        self.timedelta.as_ref().and_then(|td| parse_timedelta(td))
+    }
+
+    /// 将 ``timespan_0`` 格式的 ``timespan`` 解析为起始和结束的 `chrono::NaiveDateTime`，
+    /// 需要启用 `chrono` feature
+    ///
+    /// # 使用示例
+    ///
+    /// ```
+    /// extern crate bosonnlp;
+    /// extern crate chrono;
+    ///
+    /// use chrono::NaiveDate;
+    /// use bosonnlp::ConvertedTime;
+    ///
+    /// fn main() {
+    ///     let time = ConvertedTime {
+    ///         timestamp: None,
+    ///         timedelta: None,
+    ///         timespan: Some(("2015-01-01 00:00:00".to_owned(), "2015-01-31 23:59:59".to_owned())),
+    ///         format: "timespan_0".to_owned(),
+    ///     };
+    ///     let start = NaiveDate::from_ymd_opt(2015, 1, 1).unwrap().and_hms_opt(0, 0, 0).unwrap();
+    ///     let end = NaiveDate::from_ymd_opt(2015, 1, 31).unwrap().and_hms_opt(23, 59, 59).unwrap();
+    ///     assert_eq!(Some((start, end)), time.parsed_timespan());
+    /// }
+    /// ```
+    pub fn parsed_timespan(&self) -> Option<(chrono::NaiveDateTime, chrono::NaiveDateTime)> {
+        self.timespan.as_ref().and_then(|(start, end)| {
+            Some((parse_timestamp(start)?, parse_timestamp(end)?))
+        })
+    }
+
+    /// 将 ``timespan_1`` 格式的 ``timespan`` 解析为起始和结束的 `chrono::Duration`，
+    /// 需要启用 `chrono` feature
+    ///
+    /// # 使用示例
+    ///
+    /// ```
+    /// extern crate bosonnlp;
+    /// extern crate chrono;
+    ///
+    /// use chrono::Duration;
+    /// use bosonnlp::ConvertedTime;
+    ///
+    /// fn main() {
+    ///     let time = ConvertedTime {
+    ///         timestamp: None,
+    ///         timedelta: None,
+    ///         timespan: Some(("1 day,00:00:00".to_owned(), "3 day,00:00:00".to_owned())),
+    ///         format: "timespan_1".to_owned(),
+    ///     };
+    ///     assert_eq!(Some((Duration::days(1), Duration::days(3))), time.parsed_timedelta_span());
+    ///     assert_eq!(None, time.parsed_timespan());
+    /// }
+    /// ```
+    pub fn parsed_timedelta_span(&self) -> Option<(chrono::Duration, chrono::Duration)> {
+        self.timespan.as_ref().and_then(|(start, end)| {
+            Some((parse_timedelta(start)?, parse_timedelta(end)?))
+        })
+    }
+}
+
+/// 输出时间点、时间量或 ``开始 ~ 结束`` 形式的时间区间，依次取第一个有值的字段
+///
+/// # 使用示例
+///
+/// ```
+/// extern crate bosonnlp;
+///
+/// use bosonnlp::ConvertedTime;
+///
+/// fn main() {
+///     let time = ConvertedTime {
+///         timestamp: Some("2013-02-28 16:30:29".to_owned()),
+///         timedelta: None,
+///         timespan: None,
+///         format: "timestamp".to_owned(),
+///     };
+///     assert_eq!("2013-02-28 16:30:29", time.to_string());
+///
+///     let time = ConvertedTime {
+///         timestamp: None,
+///         timedelta: None,
+///         timespan: Some(("2013-02-28 00:00:00".to_owned(), "2013-03-01 00:00:00".to_owned())),
+///         format: "timespan_0".to_owned(),
+///     };
+///     assert_eq!("2013-02-28 00:00:00 ~ 2013-03-01 00:00:00", time.to_string());
+/// }
+/// ```
+impl fmt::Display for ConvertedTime {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(ref timestamp) = self.timestamp {
+            f.write_str(timestamp)
+        } else if let Some(ref timedelta) = self.timedelta {
+            f.write_str(timedelta)
+        } else if let Some((ref start, ref end)) = self.timespan {
+            write!(f, "{} ~ {}", start, end)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// 解析 ``2013-02-28 16:30:29`` 或 ISO8601 格式的时间字符串
+#[cfg(feature = "chrono")]
+fn parse_timestamp(timestamp: &str) -> Option<chrono::NaiveDateTime> {
+    let timestamp = timestamp.trim();
+    chrono::NaiveDateTime::parse_from_str(timestamp, "%Y-%m-%d %H:%M:%S")
+        .or_else(|_| chrono::NaiveDateTime::parse_from_str(timestamp, "%Y-%m-%dT%H:%M:%S"))
+        .ok()
+}
+
+/// 解析 ``xday,HH:MM:SS`` 或 ``HH:MM:SS`` 格式的时间量
+#[cfg(feature = "chrono")]
+fn parse_timedelta(timedelta: &str) -> Option<chrono::Duration> {
+    let (days, time) = match timedelta.find(',') {
+        Some(pos) => {
+            let days = timedelta[..pos].trim().trim_end_matches('s').trim_end_matches("day").trim();
+            (days.parse::<i64>().ok()?, &timedelta[pos + 1..])
+        }
+        None => (0, timedelta),
+    };
+    let mut parts = time.trim().split(':');
+    let hours = parts.next()?.parse::<i64>().ok()?;
+    let minutes = parts.next()?.parse::<i64>().ok()?;
+    let seconds = parts.next()?.parse::<i64>().ok()?;
+    if parts.next().is_some() {
+        return None;
+    }
+    Some(
+        chrono::Duration::days(days) + chrono::Duration::hours(hours) + chrono::Duration::minutes(minutes) +
+            chrono::Duration::seconds(seconds),
+    )
+}
+
 /// 文本聚类
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub struct TextCluster {
     /// 该 cluster 最具代表性的文档
+    #[serde(rename = "_id")]
     pub _id: String,
     /// 所有属于该 cluster 的文档 ``_id``
     pub list: Vec<String>,
@@ -55,22 +1579,191 @@ pub struct TextCluster {
     pub num: usize,
 }
 
+impl TextCluster {
+    /// 根据提交的输入文档，将文档编号替换为对应的文本
+    ///
+    /// ``contents`` 中找不到结果里的文档编号时返回 `Error::InvalidResponse`
+    ///
+    /// # 使用示例
+    ///
+    /// ```
+    /// extern crate bosonnlp;
+    ///
+    /// use bosonnlp::{ClusterContent, TextCluster};
+    ///
+    /// fn main() {
+    ///     let contents = vec![
+    ///         ClusterContent::with_id("1", "今天天气好"),
+    ///         ClusterContent::with_id("2", "今天天气不错"),
+    ///     ];
+    ///     let cluster = TextCluster {
+    ///         _id: "2".to_owned(),
+    ///         list: vec!["1".to_owned(), "2".to_owned()],
+    ///         num: 2,
+    ///     };
+    ///     let cluster = cluster.with_text(&contents).unwrap();
+    ///     assert_eq!("今天天气不错", cluster.representative);
+    ///     assert_eq!(vec!["今天天气好", "今天天气不错"], cluster.members);
+    ///     assert_eq!(2, cluster.num);
+    /// }
+    /// ```
+    pub fn with_text(&self, contents: &[ClusterContent]) -> Result<TextClusterWithText> {
+        let texts: HashMap<&str, &str> = contents.iter().map(|c| (c._id.as_str(), c.text.as_str())).collect();
+        let text_of = |id: &str| {
+            texts
+                .get(id)
+                .map(|text| text.to_string())
+                .ok_or_else(|| Error::InvalidResponse(format!("unknown document id {} in cluster result", id)))
+        };
+        Ok(TextClusterWithText {
+            representative: text_of(&self._id)?,
+            members: self.list.iter().map(|id| text_of(id)).collect::<Result<_>>()?,
+            num: self.num,
+        })
+    }
+
+    /// 对 ``list`` 中的文档编号排序，API 返回的顺序不固定，排序后便于比较结果
+    ///
+    /// # 使用示例
+    ///
+    /// ```
+    /// extern crate bosonnlp;
+    ///
+    /// use bosonnlp::TextCluster;
+    ///
+    /// fn main() {
+    ///     let mut cluster = TextCluster {
+    ///         _id: "2".to_owned(),
+    ///         list: vec!["3".to_owned(), "1".to_owned(), "2".to_owned()],
+    ///         num: 3,
+    ///     };
+    ///     cluster.sort_list();
+    ///     let expected = TextCluster {
+    ///         _id: "2".to_owned(),
+    ///         list: vec!["1".to_owned(), "2".to_owned(), "3".to_owned()],
+    ///         num: 3,
+    ///     };
+    ///     assert_eq!(expected, cluster);
+    /// }
+    /// ```
+    pub fn sort_list(&mut self) {
+        self.list.sort();
+    }
+}
+
+/// 包含文档文本的文本聚类结果
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct TextClusterWithText {
+    /// 该 cluster 最具代表性的文档
+    pub representative: String,
+    /// 所有属于该 cluster 的文档
+    pub members: Vec<String>,
+    /// 该 cluster 包含的文档数目
+    pub num: usize,
+}
+
+/// 属于某个典型意见的评论
+///
+/// API 返回的 ``[text, _id]`` 数组会按顺序映射到对应字段，序列化时也输出为数组，
+/// 数组中多余的元素会被忽略
+#[derive(Debug, Serialize, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(into = "(String, String)")]
+pub struct CommentItem {
+    /// 评论中表达该意见的文本片段
+    pub text: String,
+    /// 评论所在文档的 ``_id``
+    pub id: String,
+}
+
+impl<'de> Deserialize<'de> for CommentItem {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> ::std::result::Result<CommentItem, D::Error> {
+        let (text, id) = deserializer.deserialize_seq(PairVisitor(PhantomData))?;
+        Ok(CommentItem { text, id })
+    }
+}
+
+impl From<CommentItem> for (String, String) {
+    fn from(item: CommentItem) -> (String, String) {
+        (item.text, item.id)
+    }
+}
+
+impl CommentItem {
+    /// 使用评论文本片段和文档 ``_id`` 创建评论
+    pub fn new<T: Into<String>, I: Into<String>>(text: T, id: I) -> CommentItem {
+        CommentItem {
+            text: text.into(),
+            id: id.into(),
+        }
+    }
+}
+
 /// 典型意见
-#[derive(Debug, Deserialize, Clone)]
+///
+/// # 使用示例
+///
+/// ```
+/// extern crate bosonnlp;
+/// extern crate serde_json;
+///
+/// use bosonnlp::{CommentItem, CommentsCluster};
+///
+/// fn main() {
+///     let cluster: CommentsCluster = serde_json::from_str(
+///         r#"{"_id":0,"list":[["味道不错","1"],["味道很不错","3"]],"num":2,"opinion":"味道不错"}"#,
+///     ).unwrap();
+///     assert_eq!(CommentItem::new("味道不错", "1"), cluster.list[0]);
+///     assert_eq!(vec!["味道不错", "味道很不错"], cluster.opinion_texts());
+/// }
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub struct CommentsCluster {
     /// 该典型意见的标示
+    #[serde(rename = "_id")]
     pub _id: usize,
     /// 所有属于该典型意见的评论
-    pub list: Vec<(String, String)>,
+    pub list: Vec<CommentItem>,
     /// 该典型意见类似的意见个数
     pub num: usize,
     /// 典型意见文本
     pub opinion: String,
 }
 
+impl CommentsCluster {
+    /// 对 ``list`` 中的评论排序，API 返回的顺序不固定，排序后便于比较结果
+    pub fn sort_list(&mut self) {
+        self.list.sort();
+    }
+
+    /// 所有属于该典型意见的评论文本片段
+    pub fn opinion_texts(&self) -> Vec<&str> {
+        self.list.iter().map(|item| item.text.as_str()).collect()
+    }
+}
+
 /// 聚类任务状态
+///
+/// 从 API 返回的状态字符串反序列化，不区分大小写
+///
+/// # 使用示例
+///
+/// ```
+/// extern crate bosonnlp;
+/// extern crate serde_json;
+///
+/// use bosonnlp::TaskStatus;
+///
+/// fn main() {
+///     assert_eq!(TaskStatus::Received, serde_json::from_str::<TaskStatus>(r#""RECEIVED""#).unwrap());
+///     assert_eq!(TaskStatus::Running, serde_json::from_str::<TaskStatus>(r#""running""#).unwrap());
+///     assert_eq!(TaskStatus::Done, serde_json::from_str::<TaskStatus>(r#""DONE""#).unwrap());
+///     assert_eq!(TaskStatus::Error, serde_json::from_str::<TaskStatus>(r#""error""#).unwrap());
+///     assert_eq!(TaskStatus::NotFound, serde_json::from_str::<TaskStatus>(r#""NOT FOUND""#).unwrap());
+///     assert!(serde_json::from_str::<TaskStatus>(r#""paused""#).is_err());
+/// }
+/// ```
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
-pub(crate) enum TaskStatus {
+pub enum TaskStatus {
     /// 成功接收到分析请求
     Received,
     /// 数据分析正在进行中
@@ -79,32 +1772,225 @@ pub(crate) enum TaskStatus {
     Done,
     /// 分析遇到错误退出
     Error,
+    /// 服务器上不存在该任务，查询任务状态时会转换为 `Error::TaskNotFound`
+    NotFound,
+}
+
+impl<'de> Deserialize<'de> for TaskStatus {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> ::std::result::Result<TaskStatus, D::Error> {
+        struct TaskStatusVisitor;
+
+        impl<'de> Visitor<'de> for TaskStatusVisitor {
+            type Value = TaskStatus;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a cluster task status string")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> ::std::result::Result<TaskStatus, E> {
+                match value.to_lowercase().as_ref() {
+                    "received" => Ok(TaskStatus::Received),
+                    "running" => Ok(TaskStatus::Running),
+                    "done" => Ok(TaskStatus::Done),
+                    "error" => Ok(TaskStatus::Error),
+                    "not found" => Ok(TaskStatus::NotFound),
+                    _ => Err(E::unknown_variant(value, &["received", "running", "done", "error", "not found"])),
+                }
+            }
+        }
+
+        deserializer.deserialize_str(TaskStatusVisitor)
+    }
+}
+
+/// API 调用额度使用情况，按接口分别统计
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub struct Usage {
+    /// 各接口的调用额度，键为接口名，如 ``sentiment``、``cluster``
+    #[serde(default)]
+    pub limits: HashMap<String, EndpointUsage>,
+}
+
+impl Usage {
+    /// 获取 ``endpoint`` 接口的调用额度
+    pub fn get(&self, endpoint: &str) -> Option<&EndpointUsage> {
+        self.limits.get(endpoint)
+    }
+}
+
+/// 单个接口的调用额度
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub struct EndpointUsage {
+    /// 调用次数上限
+    #[serde(rename = "count-limit", default)]
+    pub calls_limit: u64,
+    /// 剩余调用次数
+    #[serde(rename = "count-remaining", default)]
+    pub calls_remaining: u64,
+    /// 调用频率上限
+    #[serde(rename = "frequency-limit", default)]
+    pub frequency_limit: u64,
+    /// 当前剩余的调用频率
+    #[serde(rename = "frequency-remaining", default)]
+    pub frequency_remaining: u64,
+}
+
+impl EndpointUsage {
+    /// 已使用的调用次数
+    pub fn calls_used(&self) -> u64 {
+        self.calls_limit.saturating_sub(self.calls_remaining)
+    }
+}
+
+/// 文本聚类或典型意见结果的统计信息
+#[derive(Debug, Default, Serialize, Clone, Copy, PartialEq, Eq)]
+pub struct ClusterSummary {
+    /// cluster 数目
+    pub cluster_count: usize,
+    /// 最大的 cluster 包含的文档数目
+    pub max_cluster_size: usize,
+    /// 只包含一篇文档的 cluster 数目
+    pub singleton_count: usize,
+    /// 所有 cluster 包含的文档总数
+    pub total_documents: usize,
+}
+
+impl ClusterSummary {
+    fn from_sizes<I: IntoIterator<Item = usize>>(sizes: I) -> ClusterSummary {
+        sizes.into_iter().fold(ClusterSummary::default(), |mut summary, num| {
+            summary.cluster_count += 1;
+            summary.max_cluster_size = summary.max_cluster_size.max(num);
+            if num == 1 {
+                summary.singleton_count += 1;
+            }
+            summary.total_documents += num;
+            summary
+        })
+    }
+}
+
+impl<'a> From<&'a [TextCluster]> for ClusterSummary {
+    fn from(clusters: &'a [TextCluster]) -> ClusterSummary {
+        ClusterSummary::from_sizes(clusters.iter().map(|c| c.num))
+    }
+}
+
+impl<'a> From<&'a [CommentsCluster]> for ClusterSummary {
+    fn from(clusters: &'a [CommentsCluster]) -> ClusterSummary {
+        ClusterSummary::from_sizes(clusters.iter().map(|c| c.num))
+    }
 }
 
-/// 聚类任务提交响应
+/// 统计文本聚类或典型意见结果的 cluster 数目、最大 cluster 大小、单文档 cluster 数目和文档总数
+///
+/// # 使用示例
+///
+/// ```
+/// extern crate bosonnlp;
+///
+/// use bosonnlp::{summarize, CommentItem, CommentsCluster, TextCluster};
+///
+/// fn main() {
+///     let cluster = |id: &str, list: &[&str]| TextCluster {
+///         _id: id.to_owned(),
+///         list: list.iter().map(|s| s.to_string()).collect(),
+///         num: list.len(),
+///     };
+///     let clusters = vec![
+///         cluster("1", &["1", "2", "3"]),
+///         cluster("4", &["4"]),
+///         cluster("5", &["5", "6"]),
+///         cluster("7", &["7"]),
+///     ];
+///     let summary = summarize(&clusters);
+///     assert_eq!(4, summary.cluster_count);
+///     assert_eq!(3, summary.max_cluster_size);
+///     assert_eq!(2, summary.singleton_count);
+///     assert_eq!(7, summary.total_documents);
+///
+///     let comments = vec![CommentsCluster {
+///         _id: 0,
+///         list: vec![CommentItem::new("好吃", "1"), CommentItem::new("很好吃", "2")],
+///         num: 2,
+///         opinion: "好吃".to_owned(),
+///     }];
+///     let summary = summarize(&comments);
+///     assert_eq!((1, 2, 0, 2), (summary.cluster_count, summary.max_cluster_size, summary.singleton_count, summary.total_documents));
+///
+///     let empty: Vec<TextCluster> = vec![];
+///     assert_eq!(0, summarize(&empty).cluster_count);
+/// }
+/// ```
+pub fn summarize<'a, C>(clusters: &'a [C]) -> ClusterSummary
+where
+    ClusterSummary: From<&'a [C]>,
+{
+    ClusterSummary::from(clusters)
+}
+
+/// 将分析结果写入 ``writer``，每行一个 JSON，即 NDJSON 格式，便于交给其它数据处理工具
+///
+/// ``writer`` 不会被自动缓冲，写入文件时建议使用 `BufWriter`
+///
+/// # 使用示例
+///
+/// ```
+/// extern crate bosonnlp;
+///
+/// use bosonnlp::{write_ndjson, Sentiment};
+///
+/// fn main() {
+///     let mut buf = Vec::new();
+///     write_ndjson(&mut buf, &[Sentiment::new(0.7, 0.3), Sentiment::new(0.2, 0.8)]).unwrap();
+///     assert_eq!("[0.7,0.3]\n[0.2,0.8]\n", String::from_utf8(buf).unwrap());
+/// }
+/// ```
+pub fn write_ndjson<W: Write, T: Serialize>(mut writer: W, items: &[T]) -> Result<()> {
+    for item in items {
+        serde_json::to_writer(&mut writer, item)?;
+        writer.write_all(b"\n")?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// 聚类任务提交响应，只解析用到的字段
 #[derive(Debug, Deserialize, Clone)]
 pub(crate) struct TaskPushResp {
-    pub task_id: String,
     pub count: usize,
 }
 
-/// 聚类任务状态响应
+/// 聚类任务状态响应，只解析用到的字段
 #[derive(Debug, Deserialize, Clone)]
 pub(crate) struct TaskStatusResp {
-    pub _id: String,
-    pub status: String,
-    pub count: usize,
+    pub status: TaskStatus,
 }
 
-/// 聚类任务单个输入内容
+/// 文本聚类和典型意见任务的单个输入文档
+///
+/// 由字符串转换而来时会随机生成文档编号，需要将聚类结果对应到自己的数据时
+/// 可以使用 [`with_id`](#method.with_id) 指定文档编号
 #[derive(Debug, Clone, Serialize, PartialEq, Eq, Hash)]
-pub(crate) struct ClusterContent {
+pub struct ClusterContent {
     /// 文档编号
+    #[serde(rename = "_id")]
     pub _id: String,
     /// 文档内容
     pub text: String,
 }
 
+impl ClusterContent {
+    /// 使用指定的文档编号创建输入文档，聚类结果中会使用该编号表示文档
+    pub fn with_id<I: Into<String>, T: Into<String>>(id: I, text: T) -> ClusterContent {
+        ClusterContent {
+            _id: id.into(),
+            text: text.into(),
+        }
+    }
+}
+
 impl From<String> for ClusterContent {
     fn from(content: String) -> ClusterContent {
         ClusterContent {
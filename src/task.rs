@@ -1,57 +1,76 @@
+use std::io::{BufRead, BufReader, Read};
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
 use std::time::Duration;
-use std::cmp::min;
-use std::thread;
+
+use serde::de::DeserializeOwned;
+use serde_json::Value;
 
 use super::BosonNLP;
-use rep::{TextCluster, CommentsCluster, TaskStatus, ClusterContent, TaskPushResp, TaskStatusResp};
-use errors::*;
+use crate::client::Sleeper;
+use crate::rep::{TextCluster, CommentsCluster, TaskStatus, ClusterContent, TaskPushResp, TaskStatusResp};
+use crate::errors::*;
+use crate::http::{body_size_error, task_endpoint};
+use crate::backoff::Backoff;
+use crate::validate::validate_poll_interval;
+use crate::options::{CancelFlag, ClusterOptions};
 
 /// 聚类任务属性
 pub(crate) trait TaskProperty {
     /// 任务 ID
     fn task_id(&self) -> String;
+    /// 轮询任务状态之间的等待方式
+    fn sleeper(&self) -> &dyn Sleeper;
+    /// 轮询任务状态的退避策略
+    fn backoff(&self, timeout: Option<u64>, poll_interval: Option<Duration>) -> Backoff;
 }
 
 /// 聚类任务
 pub(crate) trait Task: TaskProperty {
-    type Output;
+    type Item: DeserializeOwned;
 
-    /// 批量上传需要处理的文本序列
-    fn push(&mut self, contents: &[ClusterContent]) -> Result<bool>;
+    /// 任务类型，即 ``on_task`` 回调的第一个参数
+    const KIND: &'static str;
+
+    /// 设置上传文本时单次请求最多发送的文本数量
+    fn set_chunk_size(&mut self, chunk_size: usize);
+    /// 批量上传需要处理的文本序列，返回服务器确认收到的文本数量
+    fn push(&mut self, contents: &[ClusterContent]) -> Result<usize>;
     /// 启动分析任务
     fn analysis(&self, alpha: f32, beta: f32) -> Result<()>;
     /// 获取任务状态
     fn status(&self) -> Result<TaskStatus>;
     /// 获取任务结果
-    fn result(&self) -> Result<Self::Output>;
+    fn result(&self) -> Result<Vec<Self::Item>>;
+    /// 以流的方式获取任务结果
+    fn result_iter(&self) -> Result<ResultIter<Self::Item>>;
     /// 清空服务器端缓存的文本和结果
     fn clear(&self) -> Result<()>;
 
-    /// 等待任务完成
-    fn wait(&self, timeout: Option<u64>) -> Result<()> {
-        let mut elapsed = Duration::from_secs(0u64);
-        let mut seconds_to_sleep = Duration::from_secs(0u64);
-        if let Some(_timeout) = timeout {
-            seconds_to_sleep = min(seconds_to_sleep, Duration::from_secs(_timeout));
-        }
-        let mut i = 0usize;
-        loop {
-            thread::sleep(seconds_to_sleep);
+    /// 等待任务完成，``cancel`` 被设置时返回 `Error::Cancelled`
+    fn wait(&self, timeout: Option<u64>, poll_interval: Option<Duration>, cancel: Option<&CancelFlag>) -> Result<()> {
+        validate_poll_interval(poll_interval)?;
+        let cancelled = || cancel.is_some_and(|cancel| cancel.is_set());
+        for seconds_to_sleep in self.backoff(timeout, poll_interval) {
+            if cancelled() {
+                return Err(Error::Cancelled(self.task_id()));
+            }
+            self.sleeper().sleep(seconds_to_sleep);
+            if cancelled() {
+                return Err(Error::Cancelled(self.task_id()));
+            }
             let status = self.status()?;
             if status == TaskStatus::Done {
                 return Ok(());
             }
-            elapsed += seconds_to_sleep;
-            if let Some(_timeout) = timeout {
-                if elapsed >= Duration::from_secs(_timeout) {
-                    return Err(Error::Timeout(self.task_id()));
-                }
-            }
-            i += 1usize;
-            if i % 3usize == 0usize && seconds_to_sleep < Duration::from_secs(64u64) {
-                seconds_to_sleep += seconds_to_sleep;
-            }
         }
+        Err(Error::Timeout(self.task_id()))
+    }
+
+    /// 按 ``options`` 中的超时时间、轮询间隔和取消标志等待任务完成
+    fn wait_with(&self, options: &ClusterOptions) -> Result<()> {
+        self.wait(options.timeout, options.poll_interval, options.cancel.as_ref())
     }
 }
 
@@ -59,6 +78,7 @@ pub(crate) trait Task: TaskProperty {
 pub(crate) struct ClusterTask<'a> {
     task_id: String,
     contents: Vec<ClusterContent>,
+    chunk_size: usize,
     nlp: &'a BosonNLP,
 }
 
@@ -67,7 +87,8 @@ impl<'a> ClusterTask<'a> {
         ClusterTask {
             task_id: task_id.into(),
             contents: vec![],
-            nlp: nlp,
+            chunk_size: 100,
+            nlp,
         }
     }
 }
@@ -76,32 +97,51 @@ impl<'a> TaskProperty for ClusterTask<'a> {
     fn task_id(&self) -> String {
         self.task_id.clone()
     }
+
+    fn sleeper(&self) -> &dyn Sleeper {
+        &*self.nlp.sleeper
+    }
+
+    fn backoff(&self, timeout: Option<u64>, poll_interval: Option<Duration>) -> Backoff {
+        self.nlp.poll_backoff(timeout, poll_interval)
+    }
 }
 
 impl<'a> Task for ClusterTask<'a> {
-    type Output = Vec<TextCluster>;
+    type Item = TextCluster;
+
+    const KIND: &'static str = "cluster";
+
+    fn set_chunk_size(&mut self, chunk_size: usize) {
+        self.chunk_size = chunk_size;
+    }
 
     /// 批量上传需要处理的文本序列
-    fn push(&mut self, contents: &[ClusterContent]) -> Result<bool> {
-        let endpoint = format!("/cluster/push/{}", self.task_id());
+    fn push(&mut self, contents: &[ClusterContent]) -> Result<usize> {
+        let endpoint = task_endpoint("cluster", "push", &self.task_id())?;
         if contents.is_empty() {
-            return Ok(false);
+            return Ok(0);
         }
-        for parts in contents.chunks(100) {
-            let _: TaskPushResp = self.nlp.post(&endpoint, vec![], &parts)?;
+        let mut count = 0;
+        for parts in contents.chunks(self.chunk_size.max(1)) {
+            let resp: TaskPushResp = self.nlp.post(&endpoint, vec![], &parts)?;
+            count += resp.count;
             info!(
                 "Pushed {} of {} documents for clustering",
                 parts.len(),
                 contents.len()
             );
         }
+        if count != contents.len() {
+            warn!("Task {} received {} of {} pushed documents", self.task_id(), count, contents.len());
+        }
         self.contents.extend_from_slice(contents);
-        Ok(true)
+        Ok(count)
     }
 
     /// 启动分析任务
     fn analysis(&self, alpha: f32, beta: f32) -> Result<()> {
-        let endpoint = format!("/cluster/analysis/{}", self.task_id());
+        let endpoint = task_endpoint("cluster", "analysis", &self.task_id())?;
         let alpha_str = alpha.to_string();
         let beta_str = beta.to_string();
         let params = vec![("alpha", alpha_str.as_ref()), ("beta", beta_str.as_ref())];
@@ -112,42 +152,171 @@ impl<'a> Task for ClusterTask<'a> {
 
     /// 获取任务状态
     fn status(&self) -> Result<TaskStatus> {
-        let endpoint = format!("/cluster/status/{}", self.task_id());
-        let status_resp: TaskStatusResp = self.nlp.get(&endpoint, vec![])?;
-        let status_str = status_resp.status.to_lowercase();
-        info!("Cluster task {} status: {}", self.task_id(), status_str);
-        let ret = match status_str.as_ref() {
-            "received" => TaskStatus::Received,
-            "running" => TaskStatus::Running,
-            "done" => TaskStatus::Done,
-            "error" => TaskStatus::Error,
-            "not found" => return Err(Error::TaskNotFound(self.task_id())),
-            _ => unreachable!(),
-        };
-        Ok(ret)
+        let endpoint = task_endpoint("cluster", "status", &self.task_id())?;
+        let status_resp: TaskStatusResp = self.nlp.get_status(&endpoint)?;
+        info!("Cluster task {} status: {:?}", self.task_id(), status_resp.status);
+        match status_resp.status {
+            TaskStatus::NotFound => Err(Error::TaskNotFound(self.task_id())),
+            status => Ok(status),
+        }
     }
 
     /// 获取任务结果
     fn result(&self) -> Result<Vec<TextCluster>> {
-        let endpoint = format!("/cluster/result/{}", self.task_id());
+        let endpoint = task_endpoint("cluster", "result", &self.task_id())?;
         self.nlp.get(&endpoint, vec![])
     }
 
+    /// 以流的方式获取任务结果
+    fn result_iter(&self) -> Result<ResultIter<TextCluster>> {
+        let endpoint = task_endpoint("cluster", "result", &self.task_id())?;
+        Ok(ResultIter::new(self.nlp.get_reader(&endpoint, vec![])?))
+    }
+
     /// 清空服务器端缓存的文本和结果
     fn clear(&self) -> Result<()> {
-        let endpoint = format!("/cluster/clear/{}", self.task_id());
-        self.nlp
-            .get::<String>(&endpoint, vec![])
-            .unwrap_or_else(|_| "".to_owned());
+        let endpoint = task_endpoint("cluster", "clear", &self.task_id())?;
+        let _: Value = self.nlp.get(&endpoint, vec![])?;
         info!("Cluster task {} cleared", self.task_id());
         Ok(())
     }
 }
 
+/// 文本聚类任务句柄
+///
+/// 可以分步提交任务、查询任务状态和获取结果，
+/// 使用相同的 ``task_id`` 可以在其它进程中继续查询同一个任务
+pub struct ClusterTaskHandle<'a> {
+    task: ClusterTask<'a>,
+}
+
+impl<'a> ClusterTaskHandle<'a> {
+    pub(crate) fn new(task: ClusterTask<'a>) -> ClusterTaskHandle<'a> {
+        ClusterTaskHandle { task }
+    }
+
+    /// 设置上传文本时单次请求最多发送的文本数量，默认为 100
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> ClusterTaskHandle<'a> {
+        self.task.chunk_size = chunk_size;
+        self
+    }
+
+    /// 任务 ID
+    pub fn task_id(&self) -> String {
+        self.task.task_id()
+    }
+
+    /// 批量上传需要处理的文本序列，返回服务器确认收到的文本数量，``contents`` 为空时返回 0
+    ///
+    /// 返回的数量与 ``contents`` 的长度不一致时说明有文本没有被服务器接收
+    ///
+    /// # 使用示例
+    ///
+    /// ```no_run
+    /// extern crate bosonnlp;
+    ///
+    /// use bosonnlp::BosonNLP;
+    ///
+    /// fn main() {
+    ///     let nlp = BosonNLP::new(std::env::var("BOSON_API_TOKEN").unwrap());
+    ///     let mut task = nlp.cluster_task("task");
+    ///     let contents = ["今天天气好", "今天天气不错", "点点楼头细雨"];
+    ///     let count = task.push(&contents).unwrap();
+    ///     assert_eq!(contents.len(), count, "server dropped documents");
+    /// }
+    /// ```
+    pub fn push<T: AsRef<str>>(&mut self, contents: &[T]) -> Result<usize> {
+        let contents: Vec<ClusterContent> = contents.iter().map(|c| c.into()).collect();
+        self.task.push(&contents)
+    }
+
+    /// 批量上传指定了文档编号的文本序列，返回服务器确认收到的文本数量，``contents`` 为空时返回 0
+    pub fn push_contents(&mut self, contents: &[ClusterContent]) -> Result<usize> {
+        self.task.push(contents)
+    }
+
+    /// 启动分析任务
+    ///
+    /// ``alpha``: 聚类最大 cluster 大小，一般为 0.8
+    ///
+    /// ``beta``: 聚类平均 cluster 大小，一般为 0.45
+    pub fn analysis(&self, alpha: f32, beta: f32) -> Result<()> {
+        self.task.analysis(alpha, beta)
+    }
+
+    /// 获取任务状态
+    pub fn status(&self) -> Result<TaskStatus> {
+        self.task.status()
+    }
+
+    /// 等待任务完成
+    ///
+    /// ``timeout``: 等待的秒数，``None`` 表示一直等待
+    ///
+    /// ``poll_interval``: 首次查询任务状态前等待的时间，默认为 1 秒，不能为 0
+    pub fn wait(&self, timeout: Option<u64>, poll_interval: Option<Duration>) -> Result<()> {
+        self.task.wait(timeout, poll_interval, None)
+    }
+
+    /// 等待任务完成，``cancel`` 被设置为 true 时停止等待并返回 `Error::Cancelled`
+    pub fn wait_cancellable(&self, timeout: Option<u64>, poll_interval: Option<Duration>, cancel: Arc<AtomicBool>) -> Result<()> {
+        self.task.wait(timeout, poll_interval, Some(&CancelFlag(cancel)))
+    }
+
+    /// 获取任务结果
+    pub fn result(&self) -> Result<Vec<TextCluster>> {
+        self.task.result()
+    }
+
+    /// 以迭代器的方式获取任务结果，每次迭代时才从响应中解析下一个聚类
+    ///
+    /// 与 [`result`](#method.result) 相比不需要一次性分配整个结果数组，适合结果较大、内存受限的场景，
+    /// 但迭代过程中会一直占用 HTTP 连接，且解析到不合法的数据时才会返回错误，之前的结果已经被处理
+    ///
+    /// # 使用示例
+    ///
+    /// ```no_run
+    /// extern crate bosonnlp;
+    ///
+    /// use bosonnlp::BosonNLP;
+    ///
+    /// fn main() {
+    ///     let nlp = BosonNLP::new(std::env::var("BOSON_API_TOKEN").unwrap());
+    ///     let task = nlp.cluster_task("task");
+    ///     for cluster in task.result_iter().unwrap() {
+    ///         let cluster = cluster.unwrap();
+    ///         println!("{}: {} documents", cluster._id, cluster.num);
+    ///     }
+    /// }
+    /// ```
+    pub fn result_iter(&self) -> Result<ResultIter<TextCluster>> {
+        self.task.result_iter()
+    }
+
+    /// 清空服务器端缓存的文本和结果，请求失败时返回错误
+    ///
+    /// # 使用示例
+    ///
+    /// ```no_run
+    /// extern crate bosonnlp;
+    ///
+    /// use bosonnlp::BosonNLP;
+    ///
+    /// fn main() {
+    ///     let nlp = BosonNLP::new(std::env::var("BOSON_API_TOKEN").unwrap());
+    ///     nlp.cluster_task("task").clear().unwrap();
+    /// }
+    /// ```
+    pub fn clear(&self) -> Result<()> {
+        self.task.clear()
+    }
+}
+
 /// 典型意见任务
 pub(crate) struct CommentsTask<'a> {
     pub task_id: String,
     contents: Vec<ClusterContent>,
+    chunk_size: usize,
     nlp: &'a BosonNLP,
 }
 
@@ -156,7 +325,8 @@ impl<'a> CommentsTask<'a> {
         CommentsTask {
             task_id: task_id.into(),
             contents: vec![],
-            nlp: nlp,
+            chunk_size: 100,
+            nlp,
         }
     }
 }
@@ -165,32 +335,51 @@ impl<'a> TaskProperty for CommentsTask<'a> {
     fn task_id(&self) -> String {
         self.task_id.clone()
     }
+
+    fn sleeper(&self) -> &dyn Sleeper {
+        &*self.nlp.sleeper
+    }
+
+    fn backoff(&self, timeout: Option<u64>, poll_interval: Option<Duration>) -> Backoff {
+        self.nlp.poll_backoff(timeout, poll_interval)
+    }
 }
 
 impl<'a> Task for CommentsTask<'a> {
-    type Output = Vec<CommentsCluster>;
+    type Item = CommentsCluster;
+
+    const KIND: &'static str = "comments";
+
+    fn set_chunk_size(&mut self, chunk_size: usize) {
+        self.chunk_size = chunk_size;
+    }
 
     /// 批量上传需要处理的文本序列
-    fn push(&mut self, contents: &[ClusterContent]) -> Result<bool> {
-        let endpoint = format!("/comments/push/{}", self.task_id());
+    fn push(&mut self, contents: &[ClusterContent]) -> Result<usize> {
+        let endpoint = task_endpoint("comments", "push", &self.task_id())?;
         if contents.is_empty() {
-            return Ok(false);
+            return Ok(0);
         }
-        for parts in contents.chunks(100) {
-            let _: TaskPushResp = self.nlp.post(&endpoint, vec![], &parts)?;
+        let mut count = 0;
+        for parts in contents.chunks(self.chunk_size.max(1)) {
+            let resp: TaskPushResp = self.nlp.post(&endpoint, vec![], &parts)?;
+            count += resp.count;
             info!(
                 "Pushed {} of {} documents for comments clustering",
                 parts.len(),
                 contents.len()
             );
         }
+        if count != contents.len() {
+            warn!("Task {} received {} of {} pushed documents", self.task_id(), count, contents.len());
+        }
         self.contents.extend_from_slice(contents);
-        Ok(true)
+        Ok(count)
     }
 
     /// 启动分析任务
     fn analysis(&self, alpha: f32, beta: f32) -> Result<()> {
-        let endpoint = format!("/comments/analysis/{}", self.task_id());
+        let endpoint = task_endpoint("comments", "analysis", &self.task_id())?;
         let alpha_str = alpha.to_string();
         let beta_str = beta.to_string();
         let params = vec![("alpha", alpha_str.as_ref()), ("beta", beta_str.as_ref())];
@@ -201,34 +390,234 @@ impl<'a> Task for CommentsTask<'a> {
 
     /// 获取任务状态
     fn status(&self) -> Result<TaskStatus> {
-        let endpoint = format!("/comments/status/{}", self.task_id());
-        let status_resp: TaskStatusResp = self.nlp.get(&endpoint, vec![])?;
-        let status_str = status_resp.status.to_lowercase();
-        info!("Comments task {} status: {}", self.task_id(), status_str);
-        let ret = match status_str.as_ref() {
-            "received" => TaskStatus::Received,
-            "running" => TaskStatus::Running,
-            "done" => TaskStatus::Done,
-            "error" => TaskStatus::Error,
-            "not found" => return Err(Error::TaskNotFound(self.task_id())),
-            _ => unreachable!(),
-        };
-        Ok(ret)
+        let endpoint = task_endpoint("comments", "status", &self.task_id())?;
+        let status_resp: TaskStatusResp = self.nlp.get_status(&endpoint)?;
+        info!("Comments task {} status: {:?}", self.task_id(), status_resp.status);
+        match status_resp.status {
+            TaskStatus::NotFound => Err(Error::TaskNotFound(self.task_id())),
+            status => Ok(status),
+        }
     }
 
     /// 获取任务结果
     fn result(&self) -> Result<Vec<CommentsCluster>> {
-        let endpoint = format!("/comments/result/{}", self.task_id());
+        let endpoint = task_endpoint("comments", "result", &self.task_id())?;
         self.nlp.get(&endpoint, vec![])
     }
 
+    /// 以流的方式获取任务结果
+    fn result_iter(&self) -> Result<ResultIter<CommentsCluster>> {
+        let endpoint = task_endpoint("comments", "result", &self.task_id())?;
+        Ok(ResultIter::new(self.nlp.get_reader(&endpoint, vec![])?))
+    }
+
     /// 清空服务器端缓存的文本和结果
     fn clear(&self) -> Result<()> {
-        let endpoint = format!("/comments/clear/{}", self.task_id());
-        self.nlp
-            .get::<String>(&endpoint, vec![])
-            .unwrap_or_else(|_| "".to_owned());
+        let endpoint = task_endpoint("comments", "clear", &self.task_id())?;
+        let _: Value = self.nlp.get(&endpoint, vec![])?;
         info!("Comments task {} cleared", self.task_id());
         Ok(())
     }
 }
+
+/// 典型意见任务句柄
+///
+/// 可以分步提交任务、查询任务状态和获取结果，
+/// 使用相同的 ``task_id`` 可以在其它进程中继续查询同一个任务
+pub struct CommentsTaskHandle<'a> {
+    task: CommentsTask<'a>,
+}
+
+impl<'a> CommentsTaskHandle<'a> {
+    pub(crate) fn new(task: CommentsTask<'a>) -> CommentsTaskHandle<'a> {
+        CommentsTaskHandle { task }
+    }
+
+    /// 设置上传文本时单次请求最多发送的文本数量，默认为 100
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> CommentsTaskHandle<'a> {
+        self.task.chunk_size = chunk_size;
+        self
+    }
+
+    /// 任务 ID
+    pub fn task_id(&self) -> String {
+        self.task.task_id()
+    }
+
+    /// 批量上传需要处理的文本序列，返回服务器确认收到的文本数量，``contents`` 为空时返回 0
+    ///
+    /// 返回的数量与 ``contents`` 的长度不一致时说明有文本没有被服务器接收
+    pub fn push<T: AsRef<str>>(&mut self, contents: &[T]) -> Result<usize> {
+        let contents: Vec<ClusterContent> = contents.iter().map(|c| c.into()).collect();
+        self.task.push(&contents)
+    }
+
+    /// 批量上传指定了文档编号的文本序列，返回服务器确认收到的文本数量，``contents`` 为空时返回 0
+    pub fn push_contents(&mut self, contents: &[ClusterContent]) -> Result<usize> {
+        self.task.push(contents)
+    }
+
+    /// 启动分析任务
+    ///
+    /// ``alpha``: 聚类最大 cluster 大小，一般为 0.8
+    ///
+    /// ``beta``: 聚类平均 cluster 大小，一般为 0.45
+    pub fn analysis(&self, alpha: f32, beta: f32) -> Result<()> {
+        self.task.analysis(alpha, beta)
+    }
+
+    /// 获取任务状态
+    pub fn status(&self) -> Result<TaskStatus> {
+        self.task.status()
+    }
+
+    /// 等待任务完成
+    ///
+    /// ``timeout``: 等待的秒数，``None`` 表示一直等待
+    ///
+    /// ``poll_interval``: 首次查询任务状态前等待的时间，默认为 1 秒，不能为 0
+    pub fn wait(&self, timeout: Option<u64>, poll_interval: Option<Duration>) -> Result<()> {
+        self.task.wait(timeout, poll_interval, None)
+    }
+
+    /// 等待任务完成，``cancel`` 被设置为 true 时停止等待并返回 `Error::Cancelled`
+    pub fn wait_cancellable(&self, timeout: Option<u64>, poll_interval: Option<Duration>, cancel: Arc<AtomicBool>) -> Result<()> {
+        self.task.wait(timeout, poll_interval, Some(&CancelFlag(cancel)))
+    }
+
+    /// 获取任务结果
+    ///
+    /// 结果接口不支持分页，评论数量很多时一次性返回的结果很大，
+    /// 可以使用 [`result_iter`](#method.result_iter) 边接收边解析
+    pub fn result(&self) -> Result<Vec<CommentsCluster>> {
+        self.task.result()
+    }
+
+    /// 以迭代器的方式获取任务结果，每次迭代时才从响应中解析下一个典型意见聚类
+    ///
+    /// 与 [`result`](#method.result) 相比不需要一次性分配整个结果数组，适合结果较大、内存受限的场景，
+    /// 但迭代过程中会一直占用 HTTP 连接，且解析到不合法的数据时才会返回错误，之前的结果已经被处理
+    ///
+    /// 结果接口没有 offset、limit 等分页参数，需要分批处理时可以配合 `Iterator` 的适配器使用
+    ///
+    /// # 使用示例
+    ///
+    /// ```no_run
+    /// extern crate bosonnlp;
+    ///
+    /// use bosonnlp::{BosonNLP, CommentsCluster};
+    ///
+    /// fn main() {
+    ///     let nlp = BosonNLP::new(std::env::var("BOSON_API_TOKEN").unwrap());
+    ///     let task = nlp.comments_task("task");
+    ///     let mut iter = task.result_iter().unwrap();
+    ///     loop {
+    ///         // 每次最多处理 100 个典型意见
+    ///         let page: Vec<CommentsCluster> = iter.by_ref().take(100).collect::<Result<_, _>>().unwrap();
+    ///         if page.is_empty() {
+    ///             break;
+    ///         }
+    ///         println!("{} opinions", page.len());
+    ///     }
+    /// }
+    /// ```
+    pub fn result_iter(&self) -> Result<ResultIter<CommentsCluster>> {
+        self.task.result_iter()
+    }
+
+    /// 清空服务器端缓存的文本和结果，请求失败时返回错误
+    pub fn clear(&self) -> Result<()> {
+        self.task.clear()
+    }
+}
+
+/// 聚类任务结果的迭代器，从 HTTP 响应中逐个解析 JSON 数组的元素
+///
+/// 由 [`ClusterTaskHandle::result_iter`](struct.ClusterTaskHandle.html#method.result_iter) 和
+/// [`CommentsTaskHandle::result_iter`](struct.CommentsTaskHandle.html#method.result_iter) 返回，
+/// 遇到错误后迭代结束
+pub struct ResultIter<T> {
+    reader: BufReader<Box<dyn Read + Send>>,
+    started: bool,
+    finished: bool,
+    _marker: PhantomData<T>,
+}
+
+impl<T: DeserializeOwned> ResultIter<T> {
+    pub(crate) fn new(reader: Box<dyn Read + Send>) -> ResultIter<T> {
+        ResultIter {
+            reader: BufReader::new(reader),
+            started: false,
+            finished: false,
+            _marker: PhantomData,
+        }
+    }
+
+    /// 跳过空白字符，返回下一个字符但不消耗它，响应结束时返回 `None`
+    fn peek(&mut self) -> Result<Option<u8>> {
+        loop {
+            let (skipped, next) = {
+                let buf = self.reader.fill_buf()?;
+                if buf.is_empty() {
+                    return Ok(None);
+                }
+                let skipped = buf.iter().take_while(|b| b.is_ascii_whitespace()).count();
+                (skipped, buf.get(skipped).cloned())
+            };
+            self.reader.consume(skipped);
+            if next.is_some() {
+                return Ok(next);
+            }
+        }
+    }
+
+    /// 消耗下一个非空白字符，该字符不是 ``expected`` 时返回错误
+    fn expect(&mut self, expected: u8) -> Result<()> {
+        match self.peek()? {
+            Some(b) if b == expected => {
+                self.reader.consume(1);
+                Ok(())
+            }
+            Some(b) => Err(Error::InvalidResponse(format!("expected '{}' in result array, got '{}'", expected as char, b as char))),
+            None => Err(Error::InvalidResponse("unexpected end of result array".to_owned())),
+        }
+    }
+
+    fn next_item(&mut self) -> Result<Option<T>> {
+        if !self.started {
+            self.expect(b'[')?;
+        }
+        if self.peek()? == Some(b']') {
+            self.reader.consume(1);
+            return Ok(None);
+        }
+        if self.started {
+            self.expect(b',')?;
+        }
+        self.started = true;
+        // 结果数组的元素均为 JSON 对象，解析到 ``}`` 即结束，不会多读取后续的内容
+        let mut de = serde_json::Deserializer::from_reader(&mut self.reader);
+        Ok(Some(T::deserialize(&mut de)?))
+    }
+}
+
+impl<T: DeserializeOwned> Iterator for ResultIter<T> {
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Result<T>> {
+        if self.finished {
+            return None;
+        }
+        match self.next_item() {
+            Ok(Some(item)) => Some(Ok(item)),
+            Ok(None) => {
+                self.finished = true;
+                None
+            }
+            Err(err) => {
+                self.finished = true;
+                Some(Err(body_size_error(err)))
+            }
+        }
+    }
+}
@@ -0,0 +1,58 @@
+/// 句末标点，连续出现时归入同一个句子
+const TERMINATORS: &[char] = &['。', '！', '？', '!', '?', '…'];
+
+/// 可以跟在句末标点之后的右引号和右括号
+const CLOSINGS: &[char] = &['”', '’', '」', '』', '）', '】', '》', ')', ']', '"', '\''];
+
+/// 按中文和英文的句末标点将文本切分为句子
+///
+/// 句末标点保留在所属句子的末尾，连续的句末标点（如 ``！？``）以及紧随其后的右引号、右括号
+/// 同样归入前一个句子。英文句点只在其后为空白字符或文本结尾时视为句末，以免切开小数。
+/// 句子两端的空白字符会被去除，最后一个句子没有句末标点时同样返回
+///
+/// 情感分析、关键词提取等接口处理单个句子时通常效果更好，可以先切分再调用批量接口
+///
+/// # 使用示例
+///
+/// ```
+/// extern crate bosonnlp;
+///
+/// use bosonnlp::split_sentences;
+///
+/// fn main() {
+///     let sentences = split_sentences("今天天气好。适合出门吗？当然！！价格涨了3.5元. 没有句号");
+///     assert_eq!(vec!["今天天气好。", "适合出门吗？", "当然！！", "价格涨了3.5元.", "没有句号"], sentences);
+/// }
+/// ```
+pub fn split_sentences(text: &str) -> Vec<&str> {
+    let mut sentences = Vec::new();
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+    while let Some((idx, c)) = chars.next() {
+        let is_end = TERMINATORS.contains(&c)
+            || (c == '.' && chars.peek().map(|&(_, next)| next.is_whitespace()).unwrap_or(true));
+        if !is_end {
+            continue;
+        }
+        let mut end = idx + c.len_utf8();
+        while let Some(&(idx, next)) = chars.peek() {
+            if !(TERMINATORS.contains(&next) || next == '.' || CLOSINGS.contains(&next)) {
+                break;
+            }
+            end = idx + next.len_utf8();
+            chars.next();
+        }
+        push_sentence(&mut sentences, &text[start..end]);
+        start = end;
+    }
+    push_sentence(&mut sentences, &text[start..]);
+    sentences
+}
+
+/// 去除两端空白字符后添加非空的句子
+fn push_sentence<'a>(sentences: &mut Vec<&'a str>, sentence: &'a str) {
+    let sentence = sentence.trim();
+    if !sentence.is_empty() {
+        sentences.push(sentence);
+    }
+}
@@ -0,0 +1,120 @@
+use std::time::Duration;
+
+use crate::errors::*;
+
+/// 新闻摘要接口单篇正文的最大字数
+pub(crate) const SUMMARY_MAX_CHARS: usize = 10000;
+
+/// 检查新闻摘要的正文长度和摘要比例，``percentage`` 为 ``None`` 时表示按字数限制摘要长度
+pub(crate) fn validate_summary(content: &str, percentage: Option<f32>) -> Result<()> {
+    let len = content.chars().count();
+    if len > SUMMARY_MAX_CHARS {
+        return Err(Error::InvalidArgument {
+            name: "content".to_owned(),
+            message: format!("expected at most {} characters, got {}", SUMMARY_MAX_CHARS, len),
+        });
+    }
+    if let Some(percentage) = percentage {
+        if !(percentage > 0.0 && percentage <= 1.0) {
+            return Err(Error::InvalidArgument {
+                name: "percentage".to_owned(),
+                message: format!("expected a value in (0, 1], got {}", percentage),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// 检查时间转换接口的基准时间是否为空
+pub(crate) fn validate_basetime(basetime: &str) -> Result<()> {
+    if basetime.trim().is_empty() {
+        return Err(Error::InvalidArgument {
+            name: "basetime".to_owned(),
+            message: "expected a time like \"2013-02-28 16:30:29\", got an empty string".to_owned(),
+        });
+    }
+    Ok(())
+}
+
+/// 检查 ``top_k`` 是否在 1-100 之间
+pub(crate) fn validate_top_k(top_k: usize) -> Result<()> {
+    if top_k == 0 || top_k > 100 {
+        return Err(Error::InvalidArgument {
+            name: "top_k".to_owned(),
+            message: format!("expected 1-100, got {}", top_k),
+        });
+    }
+    Ok(())
+}
+
+/// 批量接口会因为单个空文本拒绝整个请求，发送前检查并返回第一个空文本的位置
+pub(crate) fn validate_contents<T: AsRef<str>>(contents: &[T]) -> Result<()> {
+    if let Some(idx) = contents.iter().position(|c| c.as_ref().trim().is_empty()) {
+        return Err(Error::InvalidArgument {
+            name: "contents".to_owned(),
+            message: format!("document at index {} is empty", idx),
+        });
+    }
+    Ok(())
+}
+
+/// 将分词结果以空格连接为已分词接口需要的格式，词为空或包含空白字符时返回错误
+pub(crate) fn join_words<W: AsRef<str>>(words: &[W]) -> Result<String> {
+    if let Some(idx) = words.iter().position(|w| w.as_ref().is_empty() || w.as_ref().contains(char::is_whitespace)) {
+        return Err(Error::InvalidArgument {
+            name: "words".to_owned(),
+            message: format!("word at index {} is empty or contains whitespace", idx),
+        });
+    }
+    Ok(words.iter().map(|w| w.as_ref()).collect::<Vec<_>>().join(" "))
+}
+
+/// 取出单个文本请求的结果，API 没有返回结果时返回 `Error::InvalidResponse`
+pub(crate) fn single<T>(rs: Vec<T>) -> Result<T> {
+    let len = rs.len();
+    match rs.into_iter().next() {
+        Some(item) if len == 1 => Ok(item),
+        _ => Err(Error::InvalidResponse(format!("expected 1 result, got {}", len))),
+    }
+}
+
+/// 检查聚类参数 ``alpha`` 和 ``beta`` 是否都在 (0, 1) 之间，并且 ``alpha`` 大于 ``beta``
+pub(crate) fn validate_alpha_beta(alpha: f32, beta: f32) -> Result<()> {
+    for &(name, value) in &[("alpha", alpha), ("beta", beta)] {
+        if !(value > 0.0 && value < 1.0) {
+            return Err(Error::InvalidArgument {
+                name: name.to_owned(),
+                message: format!("expected a value in (0, 1), got {}", value),
+            });
+        }
+    }
+    if alpha <= beta {
+        return Err(Error::InvalidArgument {
+            name: "alpha".to_owned(),
+            message: format!("expected alpha greater than beta {}, got {}", beta, alpha),
+        });
+    }
+    Ok(())
+}
+
+/// 检查 ``task_id`` 是否只由字母和数字组成，避免生成错误的请求路径
+pub(crate) fn validate_task_id(task_id: &str) -> Result<()> {
+    if task_id.is_empty() || !task_id.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(Error::InvalidArgument {
+            name: "task_id".to_owned(),
+            message: format!("expected letters and digits only, got {:?}", task_id),
+        });
+    }
+    Ok(())
+}
+
+/// 检查轮询任务状态的间隔是否大于 0，避免不停地请求状态接口
+pub(crate) fn validate_poll_interval(poll_interval: Option<Duration>) -> Result<()> {
+    if poll_interval == Some(Duration::from_secs(0)) {
+        return Err(Error::InvalidArgument {
+            name: "poll_interval".to_owned(),
+            message: "expected a positive duration, got 0".to_owned(),
+        });
+    }
+    Ok(())
+}
@@ -0,0 +1,157 @@
+//! API 响应中新增字段或数组元素时，响应类型仍然可以正常解析
+
+use bosonnlp::{
+    BosonApiError, ClusterContent, CommentItem, CommentsCluster, ConvertedTime, Dependency, NamedEntity, ScoredWord, Sentiment, SentimentLabel, Tag, TextCluster,
+    Usage, write_ndjson,
+};
+
+#[test]
+fn test_sentiment_extra_elements() {
+    let rs: Vec<Sentiment> = serde_json::from_str("[[0.7,0.3,0.5,\"new\"]]").unwrap();
+    assert_eq!(0.7, rs[0].positive);
+    assert_eq!(0.3, rs[0].negative);
+    assert!(serde_json::from_str::<Sentiment>("[0.7]").is_err());
+}
+
+#[test]
+fn test_sentiment_two_and_three_classes() {
+    let rs: Vec<Sentiment> = serde_json::from_str("[[0.7,0.3],[0.1,0.2,0.7]]").unwrap();
+    assert_eq!(None, rs[0].neutral);
+    assert_eq!(vec![0.7, 0.3], rs[0].probabilities());
+    assert_eq!(SentimentLabel::Positive, rs[0].label());
+    assert_eq!(0.1, rs[1].positive);
+    assert_eq!(0.2, rs[1].negative);
+    assert_eq!(Some(0.7), rs[1].neutral);
+    assert_eq!(SentimentLabel::Neutral, rs[1].label());
+    assert_eq!("[[0.7,0.3],[0.1,0.2,0.7]]", serde_json::to_string(&rs).unwrap());
+}
+
+#[test]
+fn test_scored_word_extra_elements() {
+    let rs: Vec<ScoredWord> = serde_json::from_str(r#"[[0.8,"病毒式",{"weight":1}]]"#).unwrap();
+    assert_eq!(0.8, rs[0].score);
+    assert_eq!("病毒式", rs[0].word);
+    assert!(serde_json::from_str::<ScoredWord>("[0.8]").is_err());
+}
+
+#[test]
+fn test_dependency_extra_fields() {
+    let dep: Dependency = serde_json::from_str(
+        r#"{"head":[-1],"role":["ROOT"],"tag":["VA"],"word":["好"],"score":[0.9]}"#,
+    )
+    .unwrap();
+    assert_eq!(vec!["好"], dep.word);
+}
+
+#[test]
+fn test_named_entity_extra_fields() {
+    let ner: NamedEntity = serde_json::from_str(
+        r#"{"entity":[[0,1,"org_name"]],"tag":["nt"],"word":["微软"],"confidence":[0.9]}"#,
+    )
+    .unwrap();
+    assert_eq!(vec![(0, 1, "org_name".to_owned())], ner.entity);
+}
+
+#[test]
+fn test_tag_extra_fields() {
+    let tag: Tag = serde_json::from_str(r#"{"word":["成都"],"tag":["ns"],"offset":[0]}"#).unwrap();
+    assert_eq!(vec!["成都"], tag.word);
+}
+
+#[test]
+fn test_converted_time_extra_fields() {
+    let time: ConvertedTime = serde_json::from_str(
+        r#"{"timestamp":"2013-02-28 16:30:29","timedelta":null,"timespan":null,"type":"timestamp","timezone":"+08:00"}"#,
+    )
+    .unwrap();
+    assert_eq!(Some("2013-02-28 16:30:29".to_owned()), time.timestamp);
+}
+
+#[test]
+fn test_cluster_extra_fields() {
+    let rs: Vec<TextCluster> = serde_json::from_str(r#"[{"_id":"a","list":["a","b"],"num":2,"score":0.5}]"#).unwrap();
+    assert_eq!(2, rs[0].num);
+
+    let rs: Vec<CommentsCluster> = serde_json::from_str(
+        r#"[{"_id":0,"list":[["好吃","a"]],"num":1,"opinion":"好吃","sentiment":0.9}]"#,
+    )
+    .unwrap();
+    assert_eq!("好吃", rs[0].opinion);
+}
+
+#[test]
+fn test_comments_items() {
+    let rs: Vec<CommentsCluster> = serde_json::from_str(
+        r#"[{"_id":0,"list":[["味道很好","b"],["味道好","a",0.9]],"num":2,"opinion":"味道好"}]"#,
+    )
+    .unwrap();
+    let mut cluster = rs[0].clone();
+    assert_eq!("b", cluster.list[0].id);
+    assert_eq!(vec!["味道很好", "味道好"], cluster.opinion_texts());
+    cluster.sort_list();
+    assert_eq!(vec![CommentItem::new("味道好", "a"), CommentItem::new("味道很好", "b")], cluster.list);
+    assert_eq!(
+        serde_json::json!([["味道好", "a"], ["味道很好", "b"]]),
+        serde_json::to_value(&cluster).unwrap()["list"]
+    );
+    assert!(serde_json::from_str::<CommentItem>(r#"["味道好"]"#).is_err());
+}
+
+#[test]
+fn test_usage_extra_fields() {
+    let usage: Usage = serde_json::from_str(
+        r#"{"status":200,"limits":{"sentiment":{"count-limit":500,"count-remaining":497,"updated-at":"2017-01-01"}}}"#,
+    )
+    .unwrap();
+    assert_eq!(3, usage.get("sentiment").unwrap().calls_used());
+}
+
+#[test]
+fn test_api_error_extra_fields() {
+    let err: BosonApiError = serde_json::from_str(r#"{"message":"invalid token","status":403,"trace":"x"}"#).unwrap();
+    assert_eq!(Some("invalid token".to_owned()), err.message);
+}
+
+#[test]
+fn test_id_fields_round_trip() {
+    let cluster = TextCluster {
+        _id: "a".to_owned(),
+        list: vec!["a".to_owned(), "b".to_owned()],
+        num: 2,
+    };
+    let value = serde_json::to_value(&cluster).unwrap();
+    assert_eq!("a", value["_id"]);
+    assert_eq!(cluster, serde_json::from_value(value).unwrap());
+
+    let comments = CommentsCluster {
+        _id: 0,
+        list: vec![CommentItem::new("好吃", "a")],
+        num: 1,
+        opinion: "好吃".to_owned(),
+    };
+    let value = serde_json::to_value(&comments).unwrap();
+    assert_eq!(0, value["_id"]);
+    assert_eq!(comments, serde_json::from_value(value).unwrap());
+
+    let content = ClusterContent::with_id("doc1", "今天天气好");
+    assert_eq!(
+        serde_json::json!({"_id": "doc1", "text": "今天天气好"}),
+        serde_json::to_value(&content).unwrap()
+    );
+}
+
+#[test]
+fn test_write_ndjson_tags() {
+    let tags: Vec<Tag> = serde_json::from_str(
+        r#"[{"word":["成都","商报"],"tag":["ns","n"]},{"word":["记者"],"tag":["n"]},{"word":[],"tag":[]}]"#,
+    )
+    .unwrap();
+    let mut buf = Vec::new();
+    write_ndjson(&mut buf, &tags).unwrap();
+    let output = String::from_utf8(buf).unwrap();
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(3, lines.len());
+    for (line, tag) in lines.iter().zip(&tags) {
+        assert_eq!(*tag, serde_json::from_str::<Tag>(line).unwrap());
+    }
+}
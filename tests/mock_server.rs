@@ -0,0 +1,1553 @@
+//! 使用本地模拟服务器测试各接口的请求格式和响应解析，不需要 API Token 和网络
+
+use std::io::{Cursor, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use bosonnlp::{BosonNLP, ClusterOptions, CompressionMode, KeywordsOptions, NerOptions, NewsCategory, Sleeper, TagOptions};
+use serde_json::{json, Value};
+
+/// 模拟服务器收到的请求
+#[derive(Debug, Clone)]
+struct Request {
+    method: String,
+    path: String,
+    headers: Vec<(String, String)>,
+    body: String,
+}
+
+impl Request {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn json(&self) -> Value {
+        serde_json::from_str(&self.body).unwrap()
+    }
+}
+
+/// 在本地随机端口上监听的 HTTP 服务器，按 ``handler`` 返回的状态码和响应体应答，并记录收到的请求
+struct MockServer {
+    url: String,
+    requests: Arc<Mutex<Vec<Request>>>,
+}
+
+impl MockServer {
+    fn start<F>(handler: F) -> MockServer
+    where
+        F: Fn(&Request) -> (u16, String) + Send + 'static,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let recorded = requests.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                respond(stream.unwrap(), &handler, &recorded);
+            }
+        });
+        MockServer { url, requests }
+    }
+
+    /// 与 ``start`` 相同，但每个连接在单独的线程中处理，用于测试并发请求
+    fn start_concurrent<F>(handler: F) -> MockServer
+    where
+        F: Fn(&Request) -> (u16, String) + Send + Sync + 'static,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let recorded = requests.clone();
+        let handler = Arc::new(handler);
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let (stream, handler, recorded) = (stream.unwrap(), handler.clone(), recorded.clone());
+                thread::spawn(move || respond(stream, &*handler, &recorded));
+            }
+        });
+        MockServer { url, requests }
+    }
+
+    fn client(&self) -> BosonNLP {
+        BosonNLP::with_options("token", &self.url, false).with_sleeper(Arc::new(NoSleep))
+    }
+
+    fn requests(&self) -> Vec<Request> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+/// 读取请求并按 ``handler`` 的返回值应答
+fn respond<F>(mut stream: TcpStream, handler: &F, recorded: &Mutex<Vec<Request>>)
+where
+    F: Fn(&Request) -> (u16, String),
+{
+    let req = read_request(&mut stream);
+    let (status, body) = handler(&req);
+    recorded.lock().unwrap().push(req);
+    write!(
+        stream,
+        "HTTP/1.1 {} Mock\r\nContent-Type: application/json\r\nConnection: close\r\nContent-Length: {}\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    )
+    .unwrap();
+}
+
+/// 读取完整的请求头和请求体
+fn read_request(stream: &mut TcpStream) -> Request {
+    let mut data = Vec::new();
+    let mut buf = [0; 4096];
+    loop {
+        let n = stream.read(&mut buf).unwrap();
+        data.extend_from_slice(&buf[..n]);
+        if let Some(pos) = data.windows(4).position(|w| w == b"\r\n\r\n") {
+            let head = String::from_utf8_lossy(&data[..pos]).into_owned();
+            let mut lines = head.split("\r\n");
+            let mut request_line = lines.next().unwrap().split_whitespace();
+            let method = request_line.next().unwrap().to_owned();
+            let path = request_line.next().unwrap().to_owned();
+            let headers: Vec<(String, String)> = lines
+                .filter_map(|line| {
+                    let idx = line.find(':')?;
+                    Some((line[..idx].trim().to_owned(), line[idx + 1..].trim().to_owned()))
+                })
+                .collect();
+            let len = headers
+                .iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case("content-length"))
+                .map_or(0, |(_, value)| value.parse().unwrap());
+            if data.len() >= pos + 4 + len || n == 0 {
+                let body = String::from_utf8_lossy(&data[pos + 4..]).into_owned();
+                return Request { method, path, headers, body };
+            }
+        }
+    }
+}
+
+/// 轮询任务状态时不实际等待
+#[derive(Debug)]
+struct NoSleep;
+
+impl Sleeper for NoSleep {
+    fn sleep(&self, _: Duration) {}
+}
+
+/// 聚类任务一直处于运行状态的服务器
+fn running_task_server() -> MockServer {
+    MockServer::start(|req| {
+        let body = if req.path.starts_with("/cluster/push/") {
+            r#"{"task_id":"task","count":1}"#
+        } else if req.path.starts_with("/cluster/analysis/") {
+            r#"{"_id":"task","status":"RECEIVED","count":1}"#
+        } else {
+            r#"{"_id":"task","status":"RUNNING","count":1}"#
+        };
+        (200, body.to_owned())
+    })
+}
+
+fn status_polls(server: &MockServer) -> usize {
+    server
+        .requests()
+        .iter()
+        .filter(|req| req.path.starts_with("/cluster/status/task"))
+        .count()
+}
+
+#[test]
+fn test_wait_zero_timeout_polls_once() {
+    let server = running_task_server();
+    match server.client().cluster(["今天天气好"], Some("task"), 0.8, 0.45, Some(0)) {
+        Err(bosonnlp::Error::Timeout(task_id)) => assert_eq!("task", task_id),
+        rs => panic!("unexpected result {:?}", rs),
+    }
+    assert_eq!(1, status_polls(&server));
+
+    let push = &server.requests()[0];
+    assert_eq!("POST", push.method);
+    assert_eq!(Some("token"), push.header("X-Token"));
+    assert_eq!("今天天气好", push.json()[0]["text"]);
+}
+
+#[test]
+fn test_wait_polls_bounded() {
+    // 前 3 次轮询各等待 1 秒
+    let server = running_task_server();
+    assert!(server.client().cluster(["今天天气好"], Some("task"), 0.8, 0.45, Some(3)).is_err());
+    assert_eq!(3, status_polls(&server));
+}
+
+#[test]
+fn test_builder_header() {
+    let server = MockServer::start(|_| (200, "[0]".to_owned()));
+    let nlp = BosonNLP::builder()
+        .token("token")
+        .url(server.url.as_str())
+        .header("X-Api-Gateway-Key", "secret")
+        .build()
+        .unwrap();
+    assert_eq!(vec![0], nlp.classify(&["测试"]).unwrap());
+
+    let requests = server.requests();
+    assert_eq!(Some("secret"), requests[0].header("X-Api-Gateway-Key"));
+    assert_eq!(Some("token"), requests[0].header("X-Token"));
+}
+
+#[test]
+fn test_summary_by_words() {
+    let server = MockServer::start(|_| (200, r#""黄冬加盟芒果TV任CTO""#.to_owned()));
+    let title = "前优酷土豆技术副总裁黄冬加盟芒果TV任CTO";
+    let content = "腾讯科技讯（刘亚澜）10月22日消息，前优酷土豆技术副总裁黄冬已于日前正式加盟芒果TV，出任CTO一职。";
+    let rs = server.client().summary_by_words(title, content, 50, true).unwrap();
+    assert_eq!("黄冬加盟芒果TV任CTO", rs);
+
+    let data = server.requests()[0].json();
+    assert_eq!(json!(50), data["percentage"]);
+    assert_eq!(json!(1), data["not_exceed"]);
+    assert_eq!(json!(title), data["title"]);
+}
+
+#[test]
+fn test_task_clear_failure() {
+    // 清空任务的请求返回 500
+    let server = MockServer::start(|req| {
+        let path = req.path.as_str();
+        if path.starts_with("/cluster/push/") {
+            (200, r#"{"task_id":"task","count":1}"#.to_owned())
+        } else if path.starts_with("/cluster/analysis/") {
+            (200, r#"{"_id":"task","status":"RECEIVED","count":1}"#.to_owned())
+        } else if path.starts_with("/cluster/status/") {
+            (200, r#"{"_id":"task","status":"DONE","count":1}"#.to_owned())
+        } else if path.starts_with("/cluster/result/") {
+            (200, "[]".to_owned())
+        } else {
+            (500, r#"{"message":"clear failed"}"#.to_owned())
+        }
+    });
+    let nlp = server.client();
+    match nlp.cluster_task("task").clear() {
+        Err(bosonnlp::Error::Api { code, .. }) => assert_eq!(500, code.as_u16()),
+        rs => panic!("unexpected result {:?}", rs),
+    }
+
+    // cluster 在获取结果后清空任务失败只会记录警告，仍然返回结果
+    let options = ClusterOptions::default().task_id("task").poll_interval(Duration::from_millis(10));
+    assert!(nlp.cluster_with(["今天天气好"], &options).unwrap().is_empty());
+    assert!(server.requests().last().unwrap().path.starts_with("/cluster/clear/"));
+}
+
+#[test]
+fn test_sentiment_parallel() {
+    // 文本 "n" 的正面概率为 n / 100，遇到 "fail" 时返回 500
+    let server = MockServer::start(|req| {
+        let texts: Vec<String> = serde_json::from_str(&req.body).unwrap();
+        if texts.iter().any(|t| t == "fail") {
+            return (500, r#"{"message":"boom"}"#.to_owned());
+        }
+        let rs: Vec<(f32, f32)> = texts
+            .iter()
+            .map(|t| {
+                let p = t.parse::<f32>().unwrap() / 100.0;
+                (p, 1.0 - p)
+            })
+            .collect();
+        (200, serde_json::to_string(&rs).unwrap())
+    });
+    let nlp = server.client().with_batch_size(3);
+    let mut contents: Vec<String> = (0..20).map(|i| i.to_string()).collect();
+    let rs = nlp.sentiment_parallel(&contents, "general", 4).unwrap();
+    assert_eq!(20, rs.len());
+    for (i, sentiment) in rs.iter().enumerate() {
+        assert_eq!(i as f32 / 100.0, sentiment.positive);
+    }
+    assert_eq!(7, server.requests().len());
+
+    contents[10] = "fail".to_owned();
+    match nlp.sentiment_parallel(&contents, "general", 4) {
+        Err(bosonnlp::Error::Api { code, .. }) => assert_eq!(500, code.as_u16()),
+        rs => panic!("unexpected result {:?}", rs),
+    }
+}
+
+#[test]
+fn test_sentiment_parallel_later_failure() {
+    // 文本 "3" 立即返回 500，之前的文本仍在处理中
+    let server = MockServer::start_concurrent(|req| {
+        let texts: Vec<String> = serde_json::from_str(&req.body).unwrap();
+        match texts[0].as_str() {
+            "3" => (500, r#"{"message":"boom"}"#.to_owned()),
+            "0" | "1" | "2" => {
+                thread::sleep(Duration::from_millis(200));
+                (200, "[[0.6,0.4]]".to_owned())
+            }
+            _ => (200, "[[0.6,0.4]]".to_owned()),
+        }
+    });
+    let nlp = server.client().with_batch_size(1);
+    let contents: Vec<String> = (0..8).map(|i| i.to_string()).collect();
+    match nlp.sentiment_parallel(&contents, "general", 4) {
+        Err(bosonnlp::Error::Api { code, .. }) => assert_eq!(500, code.as_u16()),
+        rs => panic!("unexpected result {:?}", rs),
+    }
+    // 失败项之前的文本都已处理，之后的文本没有发送
+    let mut texts: Vec<String> = server.requests().iter().map(|req| req.json()[0].as_str().unwrap().to_owned()).collect();
+    texts.sort();
+    assert_eq!(vec!["0", "1", "2", "3"], texts);
+}
+
+#[test]
+fn test_cluster_with_ids() {
+    // 将上传的全部文档聚为一类
+    let ids = Mutex::new(Vec::<String>::new());
+    let server = MockServer::start(move |req| {
+        let mut ids = ids.lock().unwrap();
+        let body = if req.path.starts_with("/cluster/push/") {
+            let docs = req.json();
+            let docs = docs.as_array().unwrap();
+            ids.extend(docs.iter().map(|doc| doc["_id"].as_str().unwrap().to_owned()));
+            json!({"task_id": "task", "count": docs.len()}).to_string()
+        } else if req.path.starts_with("/cluster/result/") {
+            json!([{"_id": ids[0], "list": *ids, "num": ids.len()}]).to_string()
+        } else {
+            r#"{"_id":"task","status":"DONE","count":2}"#.to_owned()
+        };
+        (200, body)
+    });
+    let docs = vec![("doc1", "今天天气好"), ("doc2", "今天天气不错")];
+    let options = ClusterOptions::default().poll_interval(Duration::from_millis(10));
+    let rs = server.client().cluster_with_ids(docs, &options).unwrap();
+    assert_eq!("doc1", rs[0]._id);
+    assert_eq!(vec!["doc1".to_owned(), "doc2".to_owned()], rs[0].list);
+}
+
+#[test]
+fn test_verify_token() {
+    let server = MockServer::start(|req| match req.header("X-Token") {
+        Some("valid") => (200, "{}".to_owned()),
+        _ => (401, r#"{"message":"invalid token"}"#.to_owned()),
+    });
+    assert!(BosonNLP::with_options("valid", &server.url, true).verify_token().unwrap());
+    assert!(!BosonNLP::with_options("invalid", &server.url, true).verify_token().unwrap());
+    assert!(server.requests().iter().all(|req| req.path.starts_with("/application/rate_limit_status.json")));
+    // 无法连接服务器时返回错误
+    assert!(BosonNLP::with_options("valid", "http://127.0.0.1:1", true).verify_token().is_err());
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn test_convert_time_at() {
+    use chrono::{TimeZone, Utc};
+
+    // 将收到的基准时间作为结果返回
+    let server = MockServer::start(|req| {
+        let url = url::Url::parse(&format!("http://localhost{}", req.path)).unwrap();
+        let basetime = url.query_pairs().find(|(k, _)| k == "basetime").unwrap().1.into_owned();
+        (200, json!({"timestamp": basetime, "type": "timestamp"}).to_string())
+    });
+    let nlp = server.client();
+    let basetime = Utc.with_ymd_and_hms(2013, 2, 28, 8, 30, 29).unwrap();
+    let time = nlp.convert_time_at("明天下午三点", basetime).unwrap();
+    assert_eq!(Some("2013-02-28 16:30:29".to_owned()), time.timestamp);
+    assert_eq!("timestamp", time.format);
+    let time = nlp.convert_time_at_timestamp("明天下午三点", 1362040229).unwrap();
+    assert_eq!(Some("2013-02-28 16:30:29".to_owned()), time.timestamp);
+    // 空字符串不是合法的基准时间
+    assert!(nlp.convert_time("明天下午三点", Some("")).is_err());
+}
+
+#[test]
+fn test_cluster_result_iter() {
+    let server = MockServer::start(|_| {
+        let body = r#" [ {"_id": "0", "list": ["0", "1"], "num": 2},
+            {"_id": "2", "list": ["2"], "num": 1} ,{"_id": "3", "list": ["3", "4", "5"], "num": 3} ]"#;
+        (200, body.to_owned())
+    });
+    let nlp = server.client();
+    let clusters = nlp.cluster_task("task").result_iter().unwrap().collect::<bosonnlp::Result<Vec<_>>>().unwrap();
+    assert_eq!(3, clusters.len());
+    assert!(clusters.iter().all(|cluster| cluster.num == cluster.list.len()));
+    assert_eq!(6, clusters.iter().map(|cluster| cluster.num).sum::<usize>());
+}
+
+#[test]
+fn test_keywords_batch() {
+    // 将文本本身作为唯一的关键词返回
+    let server = MockServer::start(|req| {
+        let text: String = serde_json::from_str(&req.body).unwrap();
+        (200, serde_json::to_string(&[(0.5, text)]).unwrap())
+    });
+    let texts = ["病毒式媒体", "新闻", "迅速蔓延", "网站", "传播"];
+    let rs = server.client().keywords_batch(&texts, 2, false).unwrap();
+    assert_eq!(texts.len(), rs.len());
+    for (text, words) in texts.iter().zip(&rs) {
+        assert_eq!(*text, words[0].word);
+    }
+
+    let requests = server.requests();
+    assert_eq!(texts.len(), requests.len());
+    assert!(requests.iter().all(|req| req.path == "/keywords/analysis?top_k=2"));
+}
+
+#[test]
+fn test_keywords_batch_failure() {
+    // 文本 "网站" 返回 500，之前的文本仍在处理中
+    let server = MockServer::start_concurrent(|req| {
+        let text: String = serde_json::from_str(&req.body).unwrap();
+        if text == "网站" {
+            return (500, r#"{"message":"boom"}"#.to_owned());
+        }
+        thread::sleep(Duration::from_millis(100));
+        (200, serde_json::to_string(&[(0.5, text)]).unwrap())
+    });
+    let texts = ["病毒式媒体", "新闻", "迅速蔓延", "网站", "传播", "蔓延"];
+    match server.client().keywords_batch(&texts, 2, false) {
+        Err(bosonnlp::Error::Api { code, .. }) => assert_eq!(500, code.as_u16()),
+        rs => panic!("unexpected result {:?}", rs),
+    }
+    assert_eq!(4, server.requests().len());
+}
+
+#[test]
+fn test_builder_user_agent() {
+    let server = MockServer::start(|_| (200, "[0]".to_owned()));
+    let nlp = BosonNLP::builder()
+        .token("token")
+        .url(server.url.as_str())
+        .user_agent("myapp/2.0")
+        .build()
+        .unwrap();
+    assert_eq!(vec![0], nlp.classify(&["测试"]).unwrap());
+
+    let user_agent = server.requests()[0].header("User-Agent").unwrap().to_owned();
+    assert!(user_agent.starts_with("myapp/2.0 bosonnlp-rs/"));
+}
+
+#[test]
+fn test_depparser_segmented() {
+    let server = MockServer::start(|_| {
+        (200, r#"[{"head":[2,2,-1],"role":["TMOD","SBJ","ROOT"],"tag":["NT","NN","VA"],"word":["今天","天气","好"]}]"#.to_owned())
+    });
+    let nlp = server.client();
+    let rs = nlp.depparser_segmented(&["今天 天气 好"], true).unwrap();
+    assert_eq!(vec!["今天", "天气", "好"], rs[0].word);
+    nlp.depparser_segmented(&["今天天气好"], false).unwrap();
+
+    let requests = server.requests();
+    assert_eq!("/depparser/analysis?segmented=1", requests[0].path);
+    assert_eq!(json!(["今天 天气 好"]), requests[0].json());
+    assert_eq!("/depparser/analysis?", requests[1].path);
+}
+
+#[test]
+fn test_builder_api_prefix() {
+    let server = MockServer::start(|_| (200, "[[0.9,0.1]]".to_owned()));
+    let nlp = BosonNLP::builder()
+        .token("token")
+        .url(server.url.as_str())
+        .api_prefix("/v2/")
+        .build()
+        .unwrap();
+    assert_eq!(1, nlp.sentiment(&["这家味道还不错"], "food").unwrap().len());
+    assert!(server.requests()[0].path.starts_with("/v2/sentiment/analysis?"));
+}
+
+#[test]
+fn test_sentiment_model_query() {
+    let server = MockServer::start(|_| (200, "[[0.9,0.1]]".to_owned()));
+    let nlp = server.client();
+    nlp.sentiment(&["这家味道还不错"], bosonnlp::SentimentModel::Food).unwrap();
+    nlp.sentiment(&["这家味道还不错"], "my model&x=1").unwrap();
+
+    let requests = server.requests();
+    assert_eq!("/sentiment/analysis?food", requests[0].path);
+    assert_eq!("/sentiment/analysis?my+model%26x%3D1", requests[1].path);
+}
+
+#[test]
+fn test_clear_tasks() {
+    let server = MockServer::start(|req| {
+        if req.path.contains("/missing") {
+            (404, r#"{"message":"task not found"}"#.to_owned())
+        } else {
+            (200, r#"{"status":"OK"}"#.to_owned())
+        }
+    });
+    let nlp = server.client();
+    nlp.clear_cluster_task("crashed").unwrap();
+    nlp.clear_comments_task("crashed").unwrap();
+    match nlp.clear_comments_task("missing") {
+        Err(bosonnlp::Error::Api { code, .. }) => assert_eq!(404, code.as_u16()),
+        rs => panic!("unexpected result {:?}", rs),
+    }
+
+    let paths: Vec<String> = server.requests().into_iter().map(|req| req.path).collect();
+    assert_eq!(vec!["/cluster/clear/crashed?", "/comments/clear/crashed?", "/comments/clear/missing?"], paths);
+}
+
+#[test]
+fn test_ner_with_t2s() {
+    // 分词接口开启 t2s 时返回简体的分词结果，命名实体识别和关键词提取接口收到已分词的简体输入
+    let server = MockServer::start(|req| {
+        let body = if req.path.starts_with("/tag/analysis") {
+            r#"[{"word":["微软","操作系统","今日","正式","退休"],"tag":["nt","n","t","ad","v"]}]"#
+        } else if req.path.starts_with("/ner/analysis") {
+            r#"[{"entity":[[0,1,"org_name"]],"word":["微软","操作系统","今日","正式","退休"],"tag":["nt","n","t","ad","v"]}]"#
+        } else {
+            r#"[[0.8,"微软"]]"#
+        };
+        (200, body.to_owned())
+    });
+    let nlp = server.client();
+    let rs = nlp.ner_with(&["微軟作業系統今日正式退休"], &NerOptions::default().t2s(true)).unwrap();
+    assert_eq!(vec![("微软".to_owned(), "org_name".to_owned())], rs[0].entity_texts());
+    let rs = nlp.keywords_with("微軟作業系統今日正式退休", &KeywordsOptions::default().top_k(1).t2s(true)).unwrap();
+    assert_eq!("微软", rs[0].word);
+
+    let requests = server.requests();
+    assert_eq!(4, requests.len());
+    for tag in &[&requests[0], &requests[2]] {
+        assert!(tag.path.starts_with("/tag/analysis") && tag.path.contains("t2s=1"), "{}", tag.path);
+        assert_eq!(json!(["微軟作業系統今日正式退休"]), tag.json());
+    }
+    assert!(requests[1].path.starts_with("/ner/analysis") && requests[1].path.contains("segmented=1"));
+    assert_eq!(json!(["微软 操作系统 今日 正式 退休"]), requests[1].json());
+    assert!(requests[3].path.starts_with("/keywords/analysis") && requests[3].path.contains("segmented=1"));
+    assert_eq!(json!("微软 操作系统 今日 正式 退休"), requests[3].json());
+}
+
+#[test]
+fn test_summary() {
+    let server = MockServer::start(|_| (200, r#""黄冬加盟芒果TV任CTO""#.to_owned()));
+    let content = format!("{}已于日前正式加盟芒果TV，出任CTO一职。", "前优酷土豆技术副总裁黄冬");
+    let rs = server.client().summary("", content.clone(), 0.3, false).unwrap();
+    assert_eq!("黄冬加盟芒果TV任CTO", rs);
+
+    let requests = server.requests();
+    assert_eq!("/summary/analysis", requests[0].path.trim_end_matches('?'));
+    let data = requests[0].json();
+    assert_eq!(json!(""), data["title"]);
+    assert_eq!(json!(content), data["content"]);
+    assert!((data["percentage"].as_f64().unwrap() - 0.3).abs() < 1e-6);
+    assert_eq!(json!(0), data["not_exceed"]);
+}
+
+#[test]
+fn test_comments_result_iter_pages() {
+    let server = MockServer::start(|_| {
+        let items: Vec<String> = (0..250)
+            .map(|i| format!(r#"{{"_id":{},"list":[["{}","评论{}"]],"num":1,"opinion":"意见{}"}}"#, i, i, i, i))
+            .collect();
+        (200, format!("[{}]", items.join(",")))
+    });
+    let nlp = server.client();
+    let task = nlp.comments_task("task");
+    let mut iter = task.result_iter().unwrap();
+    let mut pages = Vec::new();
+    loop {
+        let page: Vec<bosonnlp::CommentsCluster> = iter.by_ref().take(100).collect::<bosonnlp::Result<_>>().unwrap();
+        if page.is_empty() {
+            break;
+        }
+        pages.push(page);
+    }
+    assert_eq!(vec![100, 100, 50], pages.iter().map(|p| p.len()).collect::<Vec<_>>());
+    assert_eq!("意见249", pages[2][49].opinion);
+}
+
+/// 记录每次等待的时间
+#[derive(Debug, Default)]
+struct RecordingSleeper(Mutex<Vec<Duration>>);
+
+impl Sleeper for RecordingSleeper {
+    fn sleep(&self, duration: Duration) {
+        self.0.lock().unwrap().push(duration);
+    }
+}
+
+#[test]
+fn test_poll_backoff_sequence() {
+    let server = MockServer::start(|_| (200, r#"{"_id":"task","status":"RUNNING","count":3}"#.to_owned()));
+    let sleeper = Arc::new(RecordingSleeper::default());
+    let nlp = server.client().with_sleeper(sleeper.clone());
+    match nlp.cluster_task("task").wait(Some(500), None) {
+        Err(bosonnlp::Error::Timeout(task_id)) => assert_eq!("task", task_id),
+        rs => panic!("unexpected result {:?}", rs),
+    }
+    // 每查询 3 次等待时间翻倍，最长 64 秒，总等待时间不超过超时时间
+    let expected = vec![1, 1, 1, 2, 2, 2, 4, 4, 4, 8, 8, 8, 16, 16, 16, 32, 32, 32, 64, 64, 64, 64, 55];
+    let sleeps: Vec<u64> = sleeper.0.lock().unwrap().iter().map(|sleep| sleep.as_secs()).collect();
+    assert_eq!(expected, sleeps);
+    assert_eq!(expected.len(), server.requests().len());
+}
+
+#[test]
+fn test_cluster_chunk_size() {
+    let server = MockServer::start(|req| {
+        let body = if req.path.contains("/push/") {
+            r#"{"task_id":"task","count":2}"#
+        } else if req.path.contains("/result/") {
+            "[]"
+        } else {
+            r#"{"_id":"task","status":"DONE","count":5}"#
+        };
+        (200, body.to_owned())
+    });
+    let contents = vec!["今天天气好", "今天天气不错", "点点楼头细雨", "重重江外平湖", "当年戏马会东徐"];
+    let options = ClusterOptions::default().task_id("task").chunk_size(2);
+    server.client().cluster_with(&contents, &options).unwrap();
+    // 5 个文本分 3 次上传
+    let pushes: Vec<usize> = server
+        .requests()
+        .iter()
+        .filter(|req| req.path.contains("/push/"))
+        .map(|req| req.json().as_array().unwrap().len())
+        .collect();
+    assert_eq!(vec![2, 2, 1], pushes);
+}
+
+#[test]
+fn test_usage() {
+    let server = MockServer::start(|_| {
+        let body = r#"{"status":200,"limits":{"sentiment":{"count-limit":500,"count-remaining":497,"frequency-limit":100,"frequency-remaining":99}}}"#;
+        (200, body.to_owned())
+    });
+    let usage = server.client().usage().unwrap();
+    let sentiment = usage.get("sentiment").unwrap();
+    assert_eq!(500, sentiment.calls_limit);
+    assert_eq!(497, sentiment.calls_remaining);
+    assert_eq!(3, sentiment.calls_used());
+    assert!(usage.get("cluster").is_none());
+}
+
+#[test]
+fn test_keywords_segmented() {
+    let server = MockServer::start(|_| (200, r#"[[0.8,"病毒式"]]"#.to_owned()));
+    let nlp = server.client();
+    let rs = nlp.keywords_segmented(&["病毒式", "媒体", "网站"], 1).unwrap();
+    assert_eq!("病毒式", rs[0].word);
+    match nlp.keywords_segmented(&["病毒式", "媒体 网站"], 1) {
+        Err(bosonnlp::Error::InvalidArgument { name, .. }) => assert_eq!("words", name),
+        rs => panic!("unexpected result {:?}", rs),
+    }
+
+    let requests = server.requests();
+    assert_eq!(1, requests.len());
+    assert_eq!("/keywords/analysis?top_k=1&segmented=1", requests[0].path);
+    assert_eq!(json!("病毒式 媒体 网站"), requests[0].json());
+}
+
+#[test]
+fn test_ner_segmented() {
+    let server = MockServer::start(|_| {
+        (200, r#"[{"entity":[[3,4,"person_name"]],"word":["成都","商报","记者","姚永忠"],"tag":["ns","n","n","nr"]}]"#.to_owned())
+    });
+    let words = vec![vec!["成都", "商报", "记者", "姚永忠"]];
+    let rs = server.client().ner_segmented(&words, 3).unwrap();
+    assert_eq!(vec![("姚永忠".to_owned(), "person_name".to_owned())], rs[0].entity_texts());
+
+    let requests = server.requests();
+    assert_eq!("/ner/analysis?sensitivity=3&segmented=1", requests[0].path);
+    assert_eq!(json!(["成都 商报 记者 姚永忠"]), requests[0].json());
+}
+
+#[test]
+fn test_sentiment() {
+    let server = MockServer::start(|_| (200, "[[0.7,0.3],[0.2,0.8]]".to_owned()));
+    let rs = server.client().sentiment(&["这家味道还不错", "太难吃了"], "food").unwrap();
+    assert_eq!(2, rs.len());
+    assert_eq!(0.7, rs[0].positive);
+    assert_eq!(0.8, rs[1].negative);
+
+    let requests = server.requests();
+    assert_eq!(1, requests.len());
+    assert_eq!("POST", requests[0].method);
+    assert_eq!("/sentiment/analysis?food", requests[0].path);
+    assert_eq!(Some("token"), requests[0].header("X-Token"));
+    assert_eq!(json!(["这家味道还不错", "太难吃了"]), requests[0].json());
+}
+
+#[test]
+fn test_request_headers() {
+    let server = MockServer::start(|_| (200, "[5]".to_owned()));
+    let nlp = server.client();
+    nlp.classify(&["俄否决安理会谴责叙军战机空袭阿勒颇平民"]).unwrap();
+    nlp.raw_get("/application/rate_limit_status.json", vec![]).unwrap();
+
+    let requests = server.requests();
+    let user_agent = format!("bosonnlp-rs/{}", env!("CARGO_PKG_VERSION"));
+    for req in &requests {
+        assert_eq!(Some("token"), req.header("X-Token"));
+        assert_eq!(Some("application/json"), req.header("Accept"));
+        assert_eq!(Some(user_agent.as_str()), req.header("User-Agent"));
+    }
+    assert_eq!(Some("application/json"), requests[0].header("Content-Type"));
+    assert_eq!(None, requests[1].header("Content-Type"));
+}
+
+#[test]
+fn test_max_response_bytes() {
+    let server = MockServer::start(|_| (200, format!("[{}]", vec!["5"; 1000].join(","))));
+    let nlp = server.client().with_max_response_bytes(100);
+    match nlp.raw_get("/classify/analysis", vec![]) {
+        Err(bosonnlp::Error::InvalidResponse(ref message)) => assert_eq!("response body exceeds 100 bytes", message),
+        rs => panic!("unexpected result {:?}", rs),
+    }
+    let rs = nlp.with_max_response_bytes(2001).raw_get("/classify/analysis", vec![]).unwrap();
+    assert_eq!(1000, rs.as_array().unwrap().len());
+}
+
+#[test]
+fn test_max_response_bytes_streaming() {
+    let server = MockServer::start(|_| {
+        let clusters = (0..100).map(|i| format!(r#"{{"_id":"{}","list":["{}"],"num":1}}"#, i, i)).collect::<Vec<_>>();
+        (200, format!("[{}]", clusters.join(",")))
+    });
+    let nlp = server.client().with_max_response_bytes(100);
+    let rs = nlp.cluster_task("task").result_iter().unwrap().collect::<bosonnlp::Result<Vec<_>>>();
+    match rs {
+        Err(bosonnlp::Error::InvalidResponse(ref message)) => assert_eq!("response body exceeds 100 bytes", message),
+        rs => panic!("unexpected result {:?}", rs),
+    }
+    let nlp = nlp.with_max_response_bytes(10000);
+    let clusters = nlp.cluster_task("task").result_iter().unwrap().collect::<bosonnlp::Result<Vec<_>>>().unwrap();
+    assert_eq!(100, clusters.len());
+}
+
+#[test]
+fn test_classify() {
+    let server = MockServer::start(|_| (200, "[10,4]".to_owned()));
+    let nlp = server.client();
+    let rs = nlp.classify_labeled(&["俄否决安理会谴责叙军战机空袭阿勒颇平民", "邓紫棋谈男友林宥嘉"]).unwrap();
+    assert_eq!(vec![NewsCategory::International, NewsCategory::Entertainment], rs);
+
+    let requests = server.requests();
+    assert_eq!("POST", requests[0].method);
+    assert_eq!("/classify/analysis?", requests[0].path);
+    assert_eq!(json!(["俄否决安理会谴责叙军战机空袭阿勒颇平民", "邓紫棋谈男友林宥嘉"]), requests[0].json());
+}
+
+#[test]
+fn test_tag() {
+    let server = MockServer::start(|_| (200, r#"[{"word":["成都","商报","记者"],"tag":["ns","n","n"]}]"#.to_owned()));
+    let rs = server.client().tag(&["成都商报记者"], 0, 3, false, false).unwrap();
+    assert_eq!(vec!["成都", "商报", "记者"], rs[0].word);
+    assert_eq!(vec!["ns", "n", "n"], rs[0].tag);
+
+    let requests = server.requests();
+    assert_eq!("POST", requests[0].method);
+    assert!(requests[0].path.starts_with("/tag/analysis?"));
+    assert!(requests[0].path.contains("oov_level=3"));
+    assert_eq!(json!(["成都商报记者"]), requests[0].json());
+}
+
+#[test]
+fn test_api_error() {
+    let server = MockServer::start(|_| (403, r#"{"message":"invalid token"}"#.to_owned()));
+    match server.client().classify(&["今天天气好"]) {
+        Err(bosonnlp::Error::Api { code, reason, .. }) => {
+            assert_eq!(403, code.as_u16());
+            assert_eq!("invalid token", reason);
+        }
+        rs => panic!("unexpected result {:?}", rs),
+    }
+}
+
+#[test]
+fn test_cluster_cycle() {
+    let server = MockServer::start(|req| {
+        let path = req.path.as_str();
+        let body = if path.starts_with("/cluster/push/task") {
+            let count = req.json().as_array().unwrap().len();
+            format!(r#"{{"task_id":"task","count":{}}}"#, count)
+        } else if path.starts_with("/cluster/analysis/task") || path.starts_with("/cluster/status/task") {
+            r#"{"_id":"task","status":"DONE","count":3,"progress":1.0}"#.to_owned()
+        } else if path.starts_with("/cluster/result/task") {
+            r#"[{"_id":"a","list":["a","b"],"num":2}]"#.to_owned()
+        } else if path.starts_with("/cluster/clear/task") {
+            "{}".to_owned()
+        } else {
+            return (404, format!(r#"{{"message":"unexpected path {}"}}"#, path));
+        };
+        (200, body)
+    });
+    let contents = vec![("a", "今天天气好"), ("b", "今天天气不错"), ("c", "点点楼头细雨")];
+    let options = ClusterOptions::default().task_id("task");
+    let rs = server.client().cluster_with_ids(contents, &options).unwrap();
+    assert_eq!(1, rs.len());
+    assert_eq!("a", rs[0]._id);
+    assert_eq!(vec!["a", "b"], rs[0].list);
+
+    let requests = server.requests();
+    let calls: Vec<(&str, &str)> = requests.iter().map(|r| (r.method.as_str(), r.path.as_str())).collect();
+    assert_eq!(
+        vec![
+            ("POST", "/cluster/push/task?"),
+            ("GET", "/cluster/analysis/task?alpha=0.8&beta=0.45"),
+            ("GET", "/cluster/status/task?"),
+            ("GET", "/cluster/result/task?"),
+            ("GET", "/cluster/clear/task?"),
+        ],
+        calls
+    );
+    assert_eq!(
+        json!([
+            {"_id": "a", "text": "今天天气好"},
+            {"_id": "b", "text": "今天天气不错"},
+            {"_id": "c", "text": "点点楼头细雨"},
+        ]),
+        requests[0].json()
+    );
+}
+
+#[test]
+fn test_on_task_records_generated_ids() {
+    let server = MockServer::start(|req| {
+        let path = req.path.as_str();
+        let body = if path.contains("/push/") {
+            r#"{"task_id":"task","count":1}"#
+        } else if path.contains("/result/") {
+            "[]"
+        } else {
+            r#"{"_id":"task","status":"DONE","count":1}"#
+        };
+        (200, body.to_owned())
+    });
+    let tasks = Arc::new(Mutex::new(Vec::new()));
+    let recorded = tasks.clone();
+    let nlp = server
+        .client()
+        .with_on_task(move |kind: &str, task_id: &str| recorded.lock().unwrap().push((kind.to_owned(), task_id.to_owned())));
+    let options = ClusterOptions::default().keep_result(true);
+    nlp.cluster_with(["今天天气好"], &options).unwrap();
+    nlp.comments_with(["这家味道还不错"], &options).unwrap();
+
+    let tasks = tasks.lock().unwrap().clone();
+    let kinds: Vec<&str> = tasks.iter().map(|(kind, _)| kind.as_str()).collect();
+    assert_eq!(vec!["cluster", "comments"], kinds);
+    let requests = server.requests();
+    for (kind, task_id) in &tasks {
+        assert_eq!(32, task_id.len());
+        let push = format!("/{}/push/{}?", kind, task_id);
+        assert!(requests.iter().any(|r| r.path == push), "missing {}", push);
+    }
+}
+
+#[test]
+fn test_cluster_cancel() {
+    let cancelled = Arc::new(AtomicBool::new(false));
+    let flag = cancelled.clone();
+    let server = MockServer::start(move |req| {
+        let path = req.path.as_str();
+        let body = if path.starts_with("/cluster/push/task") {
+            r#"{"task_id":"task","count":1}"#
+        } else if path.starts_with("/cluster/status/task") {
+            flag.store(true, Ordering::SeqCst);
+            r#"{"_id":"task","status":"RUNNING","count":1}"#
+        } else {
+            r#"{"_id":"task","status":"RECEIVED","count":1}"#
+        };
+        (200, body.to_owned())
+    });
+    let options = ClusterOptions::default().task_id("task").timeout(None).cancel_flag(cancelled);
+    match server.client().cluster_with(["今天天气好"], &options) {
+        Err(bosonnlp::Error::Cancelled(ref task_id)) => assert_eq!("task", task_id),
+        rs => panic!("unexpected result {:?}", rs),
+    }
+    let statuses = server.requests().iter().filter(|r| r.path.starts_with("/cluster/status/")).count();
+    assert_eq!(1, statuses);
+}
+
+#[test]
+fn test_comments_options() {
+    let cancelled = Arc::new(AtomicBool::new(false));
+    let flag = cancelled.clone();
+    let server = MockServer::start(move |req| {
+        let path = req.path.as_str();
+        let body = if path.starts_with("/comments/push/") {
+            r#"{"task_id":"task","count":2}"#
+        } else if path.starts_with("/comments/result/") {
+            "[]"
+        } else if flag.load(Ordering::SeqCst) {
+            r#"{"_id":"task","status":"RUNNING","count":3}"#
+        } else {
+            r#"{"_id":"task","status":"DONE","count":3}"#
+        };
+        (200, body.to_owned())
+    });
+    let nlp = server.client();
+    let contents = ["这家味道还不错", "服务很好", "价格偏贵"];
+    let paths = || -> Vec<String> { server.requests().into_iter().map(|req| req.path).collect() };
+
+    // 按 chunk_size 分批上传，获取结果后清空任务
+    let options = ClusterOptions::default().task_id("task").chunk_size(2);
+    nlp.comments_with(contents, &options).unwrap();
+    let pushes = paths().iter().filter(|path| path.starts_with("/comments/push/task")).count();
+    assert_eq!(2, pushes);
+    assert_eq!("/comments/clear/task?", paths().last().unwrap());
+
+    // keep_result 时不清空
+    let count = paths().len();
+    nlp.comments_with(contents, &options.clone().keep_result(true)).unwrap();
+    assert!(paths()[count..].iter().all(|path| !path.starts_with("/comments/clear/")));
+
+    // 设置取消标志后停止等待
+    cancelled.store(true, Ordering::SeqCst);
+    match nlp.comments_with(contents, &options.cancel_flag(cancelled)) {
+        Err(bosonnlp::Error::Cancelled(ref task_id)) => assert_eq!("task", task_id),
+        rs => panic!("unexpected result {:?}", rs),
+    }
+}
+
+#[test]
+fn test_compression_feature() {
+    let server = MockServer::start(|_| (200, "[[0.7, 0.3]]".to_owned()));
+    let nlp = BosonNLP::with_options("token", &server.url, true).with_compress_threshold(0);
+    nlp.sentiment(&["这家味道还不错"], "food").unwrap();
+
+    let requests = server.requests();
+    let expected = if cfg!(feature = "compression") { Some("gzip") } else { None };
+    assert_eq!(expected, requests[0].header("content-encoding"));
+    assert_eq!(expected, requests[0].header("accept-encoding"));
+}
+
+#[test]
+fn test_compression_mode() {
+    let server = MockServer::start(|_| (200, "[5]".to_owned()));
+    // 默认阈值下这么短的请求体不会被压缩
+    let nlp = BosonNLP::with_options("token", &server.url, true);
+    let data = json!(["俄否决安理会谴责叙军战机空袭阿勒颇平民"]);
+    for mode in &[CompressionMode::Auto, CompressionMode::Always, CompressionMode::Never] {
+        nlp.raw_post_with("/classify/analysis", vec![], &data, *mode).unwrap();
+    }
+    nlp.with_compress_threshold(0).raw_post_with("/classify/analysis", vec![], &data, CompressionMode::Never).unwrap();
+
+    let gzip = if cfg!(feature = "compression") { Some("gzip") } else { None };
+    let encodings: Vec<Option<String>> = server
+        .requests()
+        .iter()
+        .map(|req| req.header("content-encoding").map(str::to_owned))
+        .collect();
+    assert_eq!(vec![None, gzip.map(str::to_owned), None, None], encodings);
+    assert_eq!(CompressionMode::Auto, CompressionMode::default());
+}
+
+#[cfg(feature = "compression")]
+#[test]
+fn test_compress_threshold_and_level() {
+    let server = MockServer::start(|_| (200, "[5]".to_owned()));
+    // 请求体超过阈值时才压缩
+    let nlp = BosonNLP::with_options("token", &server.url, true).with_compress_threshold(100);
+    for len in &[99, 100, 101] {
+        let data = json!(["a".repeat(len - 4)]);
+        assert_eq!(*len, data.to_string().len());
+        nlp.raw_post("/classify/analysis", vec![], &data).unwrap();
+    }
+    let encodings: Vec<Option<String>> = server
+        .requests()
+        .iter()
+        .map(|req| req.header("content-encoding").map(str::to_owned))
+        .collect();
+    assert_eq!(vec![None, None, Some("gzip".to_owned())], encodings);
+
+    // 级别 0 只存储不压缩，级别 9 压缩率最高，超过 9 按 9 处理
+    let data = json!(["今天天气好".repeat(100)]);
+    let mut lengths = Vec::new();
+    for level in &[0, 9, 100] {
+        let nlp = nlp.clone().with_compress_level(*level);
+        assert_eq!((*level).min(9), nlp.compress_level);
+        nlp.raw_post("/classify/analysis", vec![], &data).unwrap();
+        let req = server.requests().pop().unwrap();
+        lengths.push(req.header("content-length").unwrap().parse::<usize>().unwrap());
+    }
+    assert!(lengths[0] > data.to_string().len());
+    assert!(lengths[1] < data.to_string().len() / 10);
+    assert_eq!(lengths[1], lengths[2]);
+}
+
+#[test]
+fn test_cluster_from_iterator() {
+    let server = MockServer::start(|req| {
+        let path = req.path.as_str();
+        let body = if path.starts_with("/cluster/push/task") {
+            let count = req.json().as_array().unwrap().len();
+            format!(r#"{{"task_id":"task","count":{}}}"#, count)
+        } else if path.starts_with("/cluster/analysis/task") || path.starts_with("/cluster/status/task") {
+            r#"{"_id":"task","status":"DONE","count":2}"#.to_owned()
+        } else if path.starts_with("/cluster/result/task") {
+            "[]".to_owned()
+        } else {
+            "{}".to_owned()
+        };
+        (200, body)
+    });
+    let lines = "今天天气好\n\n今天天气不错\n";
+    let options = ClusterOptions::default().task_id("task");
+    let rs = server
+        .client()
+        .cluster_with(lines.lines().filter(|l| !l.is_empty()), &options)
+        .unwrap();
+    assert!(rs.is_empty());
+
+    let requests = server.requests();
+    let texts: Vec<String> = requests[0]
+        .json()
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|c| c["text"].as_str().unwrap().to_owned())
+        .collect();
+    assert_eq!(vec!["今天天气好", "今天天气不错"], texts);
+}
+
+#[test]
+fn test_cluster_from_reader() {
+    let server = MockServer::start(|req| {
+        let path = req.path.as_str();
+        let body = if path.starts_with("/cluster/push/task") {
+            let count = req.json().as_array().unwrap().len();
+            format!(r#"{{"task_id":"task","count":{}}}"#, count)
+        } else if path.starts_with("/cluster/analysis/task") || path.starts_with("/cluster/status/task") {
+            r#"{"_id":"task","status":"DONE","count":3}"#.to_owned()
+        } else if path.starts_with("/cluster/result/task") {
+            r#"[{"_id":"a","list":["a","b"],"num":2}]"#.to_owned()
+        } else {
+            "{}".to_owned()
+        };
+        (200, body)
+    });
+    let reader = Cursor::new("今天天气好\n\n今天天气不错\n  \n点点楼头细雨");
+    let rs = server.client().cluster_from_reader(reader, Some("task"), 0.8, 0.45, None).unwrap();
+    assert_eq!(1, rs.len());
+
+    let requests = server.requests();
+    assert_eq!("/cluster/push/task?", requests[0].path);
+    let pushed = requests[0].json();
+    let pushed = pushed.as_array().unwrap();
+    let texts: Vec<&str> = pushed.iter().map(|c| c["text"].as_str().unwrap()).collect();
+    assert_eq!(vec!["今天天气好", "今天天气不错", "点点楼头细雨"], texts);
+    assert!(pushed.iter().all(|c| !c["_id"].as_str().unwrap().is_empty()));
+    assert_eq!("/cluster/analysis/task?alpha=0.8&beta=0.45", requests[1].path);
+
+    let rs = server.client().cluster_from_reader(Cursor::new("\n\n"), Some("task"), 0.8, 0.45, None).unwrap();
+    assert!(rs.is_empty());
+    assert_eq!(requests.len(), server.requests().len());
+}
+
+#[test]
+fn test_sentiment_one() {
+    let server = MockServer::start(|_| (200, "[[0.7,0.3]]".to_owned()));
+    let rs = server.client().sentiment_one("这家味道还不错", "food").unwrap();
+    assert_eq!(0.7, rs.positive);
+    assert_eq!(json!(["这家味道还不错"]), server.requests()[0].json());
+}
+
+#[test]
+fn test_classify_one() {
+    let server = MockServer::start(|_| (200, "[10]".to_owned()));
+    let rs = server.client().classify_one("俄否决安理会谴责叙军战机空袭阿勒颇平民").unwrap();
+    assert_eq!(NewsCategory::International, NewsCategory::from(rs));
+}
+
+#[test]
+fn test_ner_one() {
+    let server = MockServer::start(|_| {
+        (200, r#"[{"entity":[[3,4,"person_name"]],"word":["成都","商报","记者","姚永忠"],"tag":["ns","n","n","nr"]}]"#.to_owned())
+    });
+    let rs = server.client().ner_one("成都商报记者姚永忠", 3, false).unwrap();
+    assert_eq!(vec![("姚永忠".to_owned(), "person_name".to_owned())], rs.entity_texts());
+    assert_eq!("/ner/analysis?sensitivity=3", server.requests()[0].path);
+}
+
+#[test]
+fn test_tag_one() {
+    let server = MockServer::start(|_| (200, r#"[{"word":["成都","商报","记者"],"tag":["ns","n","n"]}]"#.to_owned()));
+    let rs = server.client().tag_one("成都商报记者", 0, 3, false, false).unwrap();
+    assert_eq!(vec!["成都", "商报", "记者"], rs.word);
+}
+
+#[test]
+fn test_depparser_one() {
+    let server = MockServer::start(|_| {
+        (200, r#"[{"head":[2,2,-1],"role":["TMOD","SBJ","ROOT"],"tag":["NT","NN","VA"],"word":["今天","天气","好"]}]"#.to_owned())
+    });
+    let rs = server.client().depparser_one("今天天气好").unwrap();
+    assert_eq!(Some(2), rs.root());
+}
+
+#[test]
+fn test_single_empty_result() {
+    let server = MockServer::start(|_| (200, "[]".to_owned()));
+    match server.client().classify_one("今天天气好") {
+        Err(err @ bosonnlp::Error::InvalidResponse(..)) => {
+            assert_eq!("Invalid response: expected 1 result, got 0", err.to_string());
+        }
+        rs => panic!("unexpected result {:?}", rs),
+    }
+}
+
+#[test]
+fn test_resume_cluster_after_timeout() {
+    let polls = Arc::new(Mutex::new(0));
+    let server = MockServer::start(move |req| {
+        let path = req.path.as_str();
+        let body = if path.starts_with("/cluster/push/task") {
+            r#"{"task_id":"task","count":2}"#.to_owned()
+        } else if path.starts_with("/cluster/analysis/task") {
+            r#"{"_id":"task","status":"RECEIVED","count":2}"#.to_owned()
+        } else if path.starts_with("/cluster/status/task") {
+            // 前 3 次查询时任务仍在运行
+            let mut polls = polls.lock().unwrap();
+            *polls += 1;
+            let status = if *polls > 3 { "DONE" } else { "RUNNING" };
+            format!(r#"{{"_id":"task","status":"{}","count":2}}"#, status)
+        } else if path.starts_with("/cluster/result/task") {
+            r#"[{"_id":"a","list":["a","b"],"num":2}]"#.to_owned()
+        } else if path.starts_with("/cluster/clear/task") {
+            "{}".to_owned()
+        } else {
+            return (404, format!(r#"{{"message":"unexpected path {}"}}"#, path));
+        };
+        (200, body)
+    });
+    let nlp = server.client();
+    let options = ClusterOptions::default().task_id("task").timeout(2);
+    let task_id = match nlp.cluster_with(["今天天气好", "今天天气不错"], &options) {
+        Err(bosonnlp::Error::Timeout(task_id)) => task_id,
+        rs => panic!("unexpected result {:?}", rs),
+    };
+    assert_eq!("task", task_id);
+    assert!(server.requests().iter().all(|r| !r.path.starts_with("/cluster/clear/")));
+
+    let rs = nlp.resume_cluster(&task_id, Some(10)).unwrap();
+    assert_eq!(vec!["a", "b"], rs[0].list);
+    let requests = server.requests();
+    assert_eq!(1, requests.iter().filter(|r| r.path.starts_with("/cluster/push/")).count());
+    assert_eq!(1, requests.iter().filter(|r| r.path.starts_with("/cluster/analysis/")).count());
+    assert_eq!("/cluster/clear/task?", requests.last().unwrap().path);
+}
+
+/// 返回 `Error::InvalidArgument` 中的参数名和错误信息
+fn invalid_argument<T: std::fmt::Debug>(rs: bosonnlp::Result<T>) -> (String, String) {
+    match rs {
+        Err(bosonnlp::Error::InvalidArgument { name, message }) => (name, message),
+        rs => panic!("unexpected result {:?}", rs),
+    }
+}
+
+#[test]
+fn test_alpha_beta_validation() {
+    let server = MockServer::start(|req| {
+        let path = req.path.as_str();
+        let body = if path.contains("/push/") {
+            r#"{"task_id":"task","count":1}"#
+        } else if path.contains("/result/") {
+            "[]"
+        } else {
+            r#"{"_id":"task","status":"DONE","count":1}"#
+        };
+        (200, body.to_owned())
+    });
+    let nlp = server.client();
+    let options = ClusterOptions::default().task_id("task");
+    nlp.cluster_with(["今天天气好"], &options.clone().alpha(0.9).beta(0.1)).unwrap();
+    let sent = server.requests().len();
+
+    let (name, message) = invalid_argument(nlp.cluster_with(["今天天气好"], &options.clone().alpha(5.0)));
+    assert_eq!("alpha", name);
+    assert_eq!("expected a value in (0, 1), got 5", message);
+    let (name, _) = invalid_argument(nlp.comments_with(["今天天气好"], &options.clone().beta(-0.1)));
+    assert_eq!("beta", name);
+    for beta in &[0.45, 0.5] {
+        let (name, message) = invalid_argument(nlp.cluster_with(["今天天气好"], &options.clone().alpha(0.45).beta(*beta)));
+        assert_eq!("alpha", name);
+        assert!(message.contains("greater than beta"), "{}", message);
+    }
+    assert_eq!(sent, server.requests().len());
+
+    let unchecked = options.alpha(0.3).beta(0.6).check_alpha_beta(false);
+    nlp.cluster_with(["今天天气好"], &unchecked).unwrap();
+    assert!(server.requests().iter().any(|r| r.path == "/cluster/analysis/task?alpha=0.3&beta=0.6"));
+}
+
+#[test]
+fn test_empty_contents_rejected() {
+    let server = MockServer::start(|_| (200, "[]".to_owned()));
+    let nlp = server.client();
+    let contents = ["今天天气好", "  ", "点点楼头细雨"];
+    let expected = ("contents".to_owned(), "document at index 1 is empty".to_owned());
+    assert_eq!(expected, invalid_argument(nlp.sentiment(&contents, "general")));
+    assert_eq!(expected, invalid_argument(nlp.tag(&contents, 0, 3, false, false)));
+    assert_eq!(expected, invalid_argument(nlp.ner(&contents, 3, false)));
+    assert_eq!(expected, invalid_argument(nlp.depparser(&contents)));
+    assert_eq!(expected, invalid_argument(nlp.cluster_with(contents, &ClusterOptions::default().task_id("task"))));
+    assert_eq!(expected, invalid_argument(nlp.comments_with(contents, &ClusterOptions::default().task_id("task"))));
+    assert_eq!(
+        ("contents".to_owned(), "document at index 0 is empty".to_owned()),
+        invalid_argument(nlp.keywords("\n", 5, false))
+    );
+    assert!(server.requests().is_empty());
+}
+
+#[test]
+fn test_allow_empty_contents() {
+    let server = MockServer::start(|_| (200, "[[0.7,0.3],[0.5,0.5]]".to_owned()));
+    let nlp = server.client().with_allow_empty_contents(true);
+    let rs = nlp.sentiment(&["今天天气好", ""], "general").unwrap();
+    assert_eq!(2, rs.len());
+    assert_eq!(json!(["今天天气好", ""]), server.requests()[0].json());
+}
+
+#[test]
+fn test_retry_delay_capped() {
+    let server = MockServer::start(|_| (503, r#"{"message":"unavailable"}"#.to_owned()));
+    let sleeper = Arc::new(RecordingSleeper::default());
+    let nlp = server.client().with_sleeper(sleeper.clone()).with_retry(100, Duration::from_secs(1));
+    assert!(nlp.classify(&["今天天气好"]).is_err());
+    assert_eq!(101, server.requests().len());
+
+    let sleeps = sleeper.0.lock().unwrap().clone();
+    assert_eq!(100, sleeps.len());
+    assert_eq!(Duration::from_secs(1), sleeps[0]);
+    assert_eq!(Duration::from_secs(32), sleeps[5]);
+    assert!(sleeps[6..].iter().all(|sleep| *sleep == Duration::from_secs(64)));
+}
+
+#[test]
+fn test_poll_jitter_bounds() {
+    let server = MockServer::start(|_| (200, r#"{"_id":"task","status":"RUNNING","count":2}"#.to_owned()));
+    let sleeper = Arc::new(RecordingSleeper::default());
+    let nlp = server.client().with_sleeper(sleeper.clone()).with_jitter_seed(7);
+    match nlp.cluster_task("task").wait(Some(300), None) {
+        Err(bosonnlp::Error::Timeout(task_id)) => assert_eq!("task", task_id),
+        rs => panic!("unexpected result {:?}", rs),
+    }
+
+    let sleeps = sleeper.0.lock().unwrap().clone();
+    let (last, sleeps) = sleeps.split_last().unwrap();
+    let mut jittered = false;
+    for (i, sleep) in sleeps.iter().enumerate() {
+        // 未抖动时的等待时间为 1, 1, 1, 2, 2, 2, 4, ... 秒，最长 64 秒
+        let base = Duration::from_secs(std::cmp::min(1 << (i / 3), 64));
+        assert!(*sleep >= base / 2 && *sleep < base * 3 / 2, "sleep {} was {:?}", i, sleep);
+        jittered |= *sleep != base;
+    }
+    assert!(jittered);
+    assert!(*last > Duration::from_secs(0));
+    assert_eq!(Duration::from_secs(300), sleeps.iter().sum::<Duration>() + *last);
+}
+
+#[test]
+fn test_pool_settings() {
+    let server = MockServer::start(|_| (200, "[5]".to_owned()));
+    let nlp = BosonNLP::builder()
+        .token("token")
+        .url(server.url.as_str())
+        .pool_idle_timeout(Duration::from_secs(30))
+        .pool_max_idle_per_host(4)
+        .build()
+        .unwrap();
+    assert_eq!(vec![5], nlp.classify(&["今天天气好"]).unwrap());
+    assert_eq!(vec![5], nlp.classify(&["今天天气不错"]).unwrap());
+    assert_eq!(2, server.requests().len());
+}
+
+#[test]
+fn test_post_text_body() {
+    let server = MockServer::start(|_| (200, r#"[[0.5,"病毒式"]]"#.to_owned()));
+    let nlp = server.client();
+    let rs: Value = nlp.post_text("/custom/analysis", vec![("top_k", "1")], "病毒\"式").unwrap();
+    assert_eq!(json!([[0.5, "病毒式"]]), rs);
+    nlp.suggest("粉丝", 1).unwrap();
+
+    let requests = server.requests();
+    assert_eq!("/custom/analysis?top_k=1", requests[0].path);
+    assert_eq!(r#""病毒\"式""#, requests[0].body);
+    assert_eq!(r#""粉丝""#, requests[1].body);
+}
+
+#[test]
+fn test_depparser_mismatched_lengths() {
+    let server = MockServer::start(|_| {
+        (200, r#"[{"head":[2,2,-1],"role":["TMOD","SBJ"],"tag":["NT","NN","VA"],"word":["今天","天气","好"]}]"#.to_owned())
+    });
+    match server.client().depparser(&["今天天气好"]) {
+        Err(bosonnlp::Error::InvalidResponse(message)) => assert_eq!(
+            "dependency arrays have different lengths: head 3, role 2, tag 3, word 3",
+            message
+        ),
+        rs => panic!("unexpected result {:?}", rs),
+    }
+}
+
+#[test]
+fn test_request_timeout_override() {
+    let server = MockServer::start(|_| {
+        thread::sleep(Duration::from_millis(500));
+        (200, "[5]".to_owned())
+    });
+    let nlp = BosonNLP::builder()
+        .token("token")
+        .url(server.url.as_str())
+        .timeout(Duration::from_secs(10))
+        .build()
+        .unwrap();
+    assert_eq!(vec![5], nlp.classify(&["今天天气好"]).unwrap());
+
+    let quick = nlp.clone().with_request_timeout(Duration::from_millis(100));
+    match quick.classify(&["今天天气好"]) {
+        Err(bosonnlp::Error::RequestTimeout(..)) => {}
+        rs => panic!("unexpected result {:?}", rs),
+    }
+}
+
+#[test]
+fn test_poll_request_timeout() {
+    let server = MockServer::start(|_| {
+        thread::sleep(Duration::from_millis(500));
+        (200, r#"{"_id":"task","status":"RUNNING","count":2}"#.to_owned())
+    });
+    let nlp = server.client().with_poll_request_timeout(Some(Duration::from_millis(100)));
+    match nlp.cluster_task("task").wait(Some(1800), None) {
+        Err(bosonnlp::Error::RequestTimeout(..)) => {}
+        rs => panic!("unexpected result {:?}", rs),
+    }
+}
+
+#[test]
+fn test_wait_zero_poll_interval() {
+    let server = MockServer::start(|_| (200, r#"{"_id":"task","status":"RUNNING","count":2}"#.to_owned()));
+    let nlp = server.client();
+    let rs = nlp.cluster_task("task").wait(Some(1800), Some(Duration::from_secs(0)));
+    assert_eq!(("poll_interval".to_owned(), "expected a positive duration, got 0".to_owned()), invalid_argument(rs));
+    let options = ClusterOptions::default().poll_interval(Duration::from_secs(0));
+    let rs = nlp.cluster_with(["今天天气好", "今天天气不错"], &options);
+    assert_eq!("poll_interval", invalid_argument(rs).0);
+    assert!(server.requests().is_empty());
+}
+
+#[test]
+fn test_clone_with_token() {
+    let server = MockServer::start(|_| (200, "[5]".to_owned()));
+    let nlp = server.client().with_retry(1, Duration::from_millis(1)).with_poll_jitter(true);
+    let tenant = nlp.clone_with_token("tenant-token");
+    assert_eq!("tenant-token", tenant.token);
+    assert_eq!(nlp.url(), tenant.url());
+    assert_eq!(nlp.compress, tenant.compress);
+    assert_eq!(nlp.max_retries, tenant.max_retries);
+    assert_eq!(nlp.poll_jitter, tenant.poll_jitter);
+
+    assert_eq!(vec![5], tenant.classify(&["俄否决安理会谴责叙军战机空袭阿勒颇平民"]).unwrap());
+    assert_eq!(vec![5], nlp.classify(&["俄否决安理会谴责叙军战机空袭阿勒颇平民"]).unwrap());
+    let requests = server.requests();
+    assert_eq!(Some("tenant-token"), requests[0].header("X-Token"));
+    assert_eq!(Some("token"), requests[1].header("X-Token"));
+}
+
+#[test]
+fn test_result_iter_on_request() {
+    let server = MockServer::start(|req| {
+        if req.path.starts_with("/cluster/result/ok") {
+            (200, r#"[{"_id":"0","list":["0","1"],"num":2}]"#.to_owned())
+        } else {
+            (404, r#"{"message":"task not found"}"#.to_owned())
+        }
+    });
+    let metrics = Arc::new(Mutex::new(Vec::new()));
+    let recorded = metrics.clone();
+    let nlp = server.client().with_on_request(move |m: &bosonnlp::RequestMetrics| recorded.lock().unwrap().push(m.clone()));
+    let clusters = nlp.cluster_task("ok").result_iter().unwrap().collect::<bosonnlp::Result<Vec<_>>>().unwrap();
+    assert_eq!(2, clusters[0].num);
+    assert!(nlp.cluster_task("missing").result_iter().is_err());
+
+    let metrics = metrics.lock().unwrap();
+    assert_eq!(2, metrics.len());
+    assert_eq!("/cluster/result/ok", metrics[0].endpoint);
+    assert_eq!("GET", metrics[0].method.as_str());
+    assert_eq!(Some(200), metrics[0].status.map(|status| status.as_u16()));
+    assert_eq!(Some(404), metrics[1].status.map(|status| status.as_u16()));
+    assert!(metrics[1].response_size > 0);
+}
+
+#[test]
+fn test_preview_tag() {
+    let server = MockServer::start(|_| (200, "[]".to_owned()));
+    let nlp = BosonNLP::builder()
+        .token("token")
+        .url(server.url.as_str())
+        .header("X-Tenant", "demo")
+        .build()
+        .unwrap();
+    let options = TagOptions::default().space_mode(1).oov_level(3).t2s(true);
+    let req = nlp.preview_tag(&["成都商报记者 姚永忠"], &options).unwrap();
+    assert_eq!("POST", req.method.as_str());
+    assert_eq!("/tag/analysis", req.url.path());
+    let query: Vec<(String, String)> = req.url.query_pairs().into_owned().collect();
+    for param in &[("space_mode", "1"), ("oov_level", "3"), ("t2s", "1"), ("special_char_conv", "0")] {
+        assert!(
+            query.contains(&(param.0.to_owned(), param.1.to_owned())),
+            "missing {:?} in {:?}",
+            param,
+            query
+        );
+    }
+    assert_eq!(json!(["成都商报记者 姚永忠"]), serde_json::from_str::<Value>(req.body.as_ref().unwrap()).unwrap());
+    assert_eq!("demo", req.headers["X-Tenant"]);
+    assert!(req.headers.get("X-Token").is_none());
+    assert!(server.requests().is_empty());
+
+    let rs = nlp.preview_tag(&["成都商报记者"], &TagOptions::default().oov_level(5));
+    assert_eq!("oov_level", invalid_argument(rs).0);
+}
+
+#[test]
+fn test_builder_custom_client() {
+    let server = MockServer::start(|req| match req.header("X-Tenant") {
+        Some("tenant-42") => (200, "[5]".to_owned()),
+        _ => (400, r#"{"message":"missing tenant"}"#.to_owned()),
+    });
+    let mut headers = reqwest::header::HeaderMap::new();
+    headers.insert("X-Tenant", reqwest::header::HeaderValue::from_static("tenant-42"));
+    let client = reqwest::blocking::Client::builder().default_headers(headers).build().unwrap();
+    let nlp = BosonNLP::builder()
+        .token("token")
+        .url(server.url.as_str())
+        .compress(false)
+        .client(client)
+        .build()
+        .unwrap();
+    assert!(!nlp.compress);
+    assert_eq!(vec![5], nlp.classify(&["俄否决安理会谴责叙军战机空袭阿勒颇平民"]).unwrap());
+    // 通过 client() 发送的请求同样带有注入的请求头
+    let resp = nlp.client().get(nlp.url()).send().unwrap();
+    assert!(resp.status().is_success());
+}
+
+#[test]
+fn test_convert_time_batch() {
+    // 将收到的基准时间和时间描述作为结果返回
+    let server = MockServer::start(|req| {
+        let url = url::Url::parse(&format!("http://localhost{}", req.path)).unwrap();
+        let param = |name: &str| url.query_pairs().find(|(k, _)| k == name).unwrap().1.into_owned();
+        let body = json!({"timestamp": format!("{} {}", param("basetime"), param("pattern")), "type": "timestamp"});
+        (200, body.to_string())
+    });
+    let rs = server.client().convert_time_batch(&["今天", "明天下午三点", "下周一"], Some("2013-02-28 16:30:29"), 2).unwrap();
+    let timestamps: Vec<String> = rs.into_iter().map(|t| t.timestamp.unwrap()).collect();
+    assert_eq!(
+        vec!["2013-02-28 16:30:29 今天", "2013-02-28 16:30:29 明天下午三点", "2013-02-28 16:30:29 下周一"],
+        timestamps
+    );
+}
+
+#[test]
+fn test_convert_time_batch_first_error() {
+    // "明天" 稍后返回 400，排在它之后的 "下周一" 立即返回 500
+    let server = MockServer::start_concurrent(|req| {
+        let url = url::Url::parse(&format!("http://localhost{}", req.path)).unwrap();
+        let pattern = url.query_pairs().find(|(k, _)| k == "pattern").unwrap().1.into_owned();
+        match pattern.as_str() {
+            "明天" => {
+                thread::sleep(Duration::from_millis(100));
+                (400, r#"{"message":"first"}"#.to_owned())
+            }
+            "下周一" => (500, r#"{"message":"second"}"#.to_owned()),
+            _ => (200, r#"{"timestamp":"2013-02-28 16:30:29","type":"timestamp"}"#.to_owned()),
+        }
+    });
+    match server.client().convert_time_batch(&["今天", "明天", "后天", "下周一", "下周二"], None, 5) {
+        Err(bosonnlp::Error::Api { code, .. }) => assert_eq!(400, code.as_u16()),
+        rs => panic!("unexpected result {:?}", rs),
+    }
+}
+
+#[test]
+fn test_on_request_metrics() {
+    let server = MockServer::start(|_| {
+        thread::sleep(Duration::from_millis(20));
+        (200, "[5]".to_owned())
+    });
+    let metrics = Arc::new(Mutex::new(Vec::new()));
+    let recorded = metrics.clone();
+    let nlp = server.client().with_on_request(move |m: &bosonnlp::RequestMetrics| recorded.lock().unwrap().push(m.clone()));
+    nlp.classify(&["今天天气好"]).unwrap();
+    let m = metrics.lock().unwrap()[0].clone();
+    assert_eq!("/classify/analysis", m.endpoint);
+    assert_eq!(Some(200), m.status.map(|status| status.as_u16()));
+    assert!(m.duration >= Duration::from_millis(20));
+    assert_eq!(r#"["今天天气好"]"#.len(), m.request_size);
+    assert_eq!(3, m.response_size);
+
+    // 连接失败时也会调用，此时没有状态码
+    let recorded = metrics.clone();
+    let nlp = BosonNLP::with_options("token", "http://127.0.0.1:1", false)
+        .with_on_request(move |m: &bosonnlp::RequestMetrics| recorded.lock().unwrap().push(m.clone()));
+    assert!(nlp.classify(&["今天天气好"]).is_err());
+    assert_eq!(None, metrics.lock().unwrap()[1].status);
+}
+
+#[test]
+fn test_jitter_seed_reproducible() {
+    let server = MockServer::start(|_| (200, r#"{"_id":"task","status":"RUNNING","count":3}"#.to_owned()));
+    let sleeper = Arc::new(RecordingSleeper::default());
+    let nlp = server.client().with_sleeper(sleeper.clone()).with_jitter_seed(42);
+    assert!(nlp.cluster_task("task").wait(Some(60), None).is_err());
+    let first = sleeper.0.lock().unwrap().clone();
+    assert!(first[0] >= Duration::from_millis(500) && first[0] < Duration::from_millis(1500));
+    assert_eq!(Duration::from_secs(60), first.iter().sum());
+
+    // 相同的种子得到相同的等待时间
+    sleeper.0.lock().unwrap().clear();
+    assert!(nlp.cluster_task("task").wait(Some(60), None).is_err());
+    assert_eq!(first, *sleeper.0.lock().unwrap());
+}
+
+#[cfg(feature = "async")]
+#[tokio::test]
+async fn test_async_concurrent_requests() {
+    let server = MockServer::start(|req| {
+        let body = if req.path.starts_with("/sentiment/") {
+            "[[0.7,0.3]]"
+        } else if req.path.starts_with("/classify/") {
+            "[5]"
+        } else {
+            r#"[[0.5,"粉丝"],[0.4,"脑残粉"]]"#
+        };
+        (200, body.to_owned())
+    });
+    let nlp = bosonnlp::AsyncBosonNLP::with_options("token", &server.url, false);
+    let (sentiment, classify, suggest) = tokio::join!(
+        nlp.sentiment(&["这家味道还不错"], "food"),
+        nlp.classify(&["俄否决安理会谴责叙军战机空袭阿勒颇平民"]),
+        nlp.suggest("粉丝", 2),
+    );
+    assert_eq!(0.7, sentiment.unwrap()[0].positive);
+    assert_eq!(vec![5], classify.unwrap());
+    assert_eq!("脑残粉", suggest.unwrap()[1].word);
+
+    let mut paths: Vec<String> = server.requests().into_iter().map(|req| req.path).collect();
+    paths.sort();
+    assert_eq!(3, paths.len());
+    assert!(paths[0].starts_with("/classify/analysis"));
+    assert!(paths[1].starts_with("/sentiment/analysis"));
+    assert!(paths[2].starts_with("/suggest/analysis"));
+}
+
+#[test]
+fn test_task_push_count() {
+    // 每次上传只确认收到 2 个文本
+    let server = MockServer::start(|_| (200, r#"{"task_id":"task","count":2}"#.to_owned()));
+    let nlp = server.client();
+    let mut task = nlp.cluster_task("task");
+    let contents = ["今天天气好", "今天天气不错", "点点楼头细雨"];
+    assert_eq!(2, task.push(&contents).unwrap());
+    assert_eq!(0, task.push::<&str>(&[]).unwrap());
+    assert_eq!(1, server.requests().len());
+}
@@ -0,0 +1,29 @@
+//! 文本处理工具函数的测试，不需要 API Token 和网络
+
+use bosonnlp::split_sentences;
+
+#[test]
+fn test_split_mixed_punctuation() {
+    let text = "这家店很好吃！！你去过吗?去过。Really?! Yes. 价格是3.14元…";
+    assert_eq!(
+        vec!["这家店很好吃！！", "你去过吗?", "去过。", "Really?!", "Yes.", "价格是3.14元…"],
+        split_sentences(text)
+    );
+}
+
+#[test]
+fn test_split_trailing_text() {
+    assert_eq!(vec!["第一句。", "没有结尾标点"], split_sentences("第一句。没有结尾标点  "));
+    assert_eq!(vec!["只有一句"], split_sentences("只有一句"));
+    assert!(split_sentences("").is_empty());
+    assert!(split_sentences("  \n ").is_empty());
+}
+
+#[test]
+fn test_split_quotes_and_brackets() {
+    let text = "他说：“味道不错。”我们点了招牌菜（很辣！）。《好吃吗？》 \"Great!\" she said.";
+    assert_eq!(
+        vec!["他说：“味道不错。”", "我们点了招牌菜（很辣！）。", "《好吃吗？》", "\"Great!\"", "she said."],
+        split_sentences(text)
+    );
+}